@@ -1,7 +1,9 @@
 use clap::Parser;
-use reaclib::{Format, Iter};
+use reaclib::{to_hash_map, Format, Iter, Reaction, Set};
+use serde::Serialize;
 use serde_json::to_writer_pretty;
 use std::{
+    collections::HashMap,
     error::Error,
     fs::File,
     io::{stdout, BufReader},
@@ -16,6 +18,10 @@ struct Cli {
     #[arg(short, long, value_parser = format_parse)]
     format: Format,
 
+    /// Write an object with the sets grouped by reaction, instead of a list of sets.
+    #[arg(short, long)]
+    group: bool,
+
     /// File to read from.
     file: PathBuf,
 }
@@ -28,14 +34,28 @@ fn format_parse(s: &str) -> Result<Format, String> {
     }
 }
 
+/// The sets grouped by reaction, which are written as strings like `he4 c12 -> o16`, since JSON
+/// keys must be strings.
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Grouped {
+    #[serde(with = "reaclib::reaction_map")]
+    map: HashMap<Reaction, Vec<Set>>,
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
     let file = File::open(cli.file)?;
     let file = BufReader::new(file);
 
-    let v = Iter::new(file, cli.format).collect::<Result<Vec<_>, _>>()?;
     let writer = stdout().lock();
-    to_writer_pretty(writer, &v)?;
+    if cli.group {
+        let map = to_hash_map(file, cli.format)?;
+        to_writer_pretty(writer, &Grouped { map })?;
+    } else {
+        let v = Iter::new(file, cli.format).collect::<Result<Vec<_>, _>>()?;
+        to_writer_pretty(writer, &v)?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,55 @@
+use clap::Parser;
+use reaclib::{write_sets, Format, Library, SetList};
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs::{read_to_string, File},
+    io::{stdout, BufReader},
+    path::PathBuf,
+};
+
+/// Example program for replacing reactions in a reaclib file with sets from a toml file
+#[derive(Parser, Debug)]
+#[command(about, long_about = None)]
+struct Cli {
+    /// The reaclib format of the file (1, 2).
+    #[arg(short, long, value_parser = format_parse)]
+    format: Format,
+
+    /// File to read from.
+    file: PathBuf,
+
+    /// Toml file with the sets to use instead, as `[[set]]` tables.
+    overrides: PathBuf,
+}
+
+fn format_parse(s: &str) -> Result<Format, String> {
+    match s.parse::<u8>() {
+        Ok(1) => Ok(Format::Reaclib1),
+        Ok(2) => Ok(Format::Reaclib2),
+        _ => Err("Only '1' and '2' are valid formats".to_string()),
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    let file = File::open(cli.file)?;
+    let file = BufReader::new(file);
+    let library = Library::from_reader(file, cli.format)?;
+
+    let overrides: SetList = toml::from_str(&read_to_string(cli.overrides)?)?;
+    let replaced = overrides
+        .set
+        .iter()
+        .map(reaclib::Set::reaction)
+        .collect::<HashSet<_>>();
+
+    let sets = library
+        .sets()
+        .iter()
+        .filter(|s| !replaced.contains(&s.reaction()))
+        .chain(&overrides.set);
+    write_sets(stdout().lock(), sets, cli.format)?;
+
+    Ok(())
+}
@@ -0,0 +1,8 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use reaclib::{assert_round_trip, Format};
+
+fuzz_target!(|data: &[u8]| {
+    assert_round_trip(data, Format::Reaclib1);
+    assert_round_trip(data, Format::Reaclib2);
+});
@@ -0,0 +1,222 @@
+//! The rules of the reaclib format, shared by the [reaclib](https://docs.rs/reaclib) crate and
+//! its `include_reaclib` macro, so that a file is read the same way at runtime and at compile
+//! time.
+//!
+//! This is an implementation detail of the reaclib crate, which should be used instead of
+//! depending on this crate directly.
+
+use std::{
+    fmt,
+    io::{self, BufRead},
+    num::{ParseFloatError, ParseIntError},
+    ops::Range,
+};
+
+/// What went wrong while reading a field.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Error {
+    ParseInt(ParseIntError),
+    ParseFloat(ParseFloatError),
+    UnknownResonance(String),
+    TooShortLine,
+    StrIndex,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseInt(_) => write!(f, "int parsing error"),
+            Self::ParseFloat(_) => write!(f, "float parsing error"),
+            Self::UnknownResonance(s) => write!(f, "unknown resonance: {s}"),
+            Self::TooShortLine => write!(f, "line too short"),
+            Self::StrIndex => write!(f, "string indexing error"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ParseInt(e) => Some(e),
+            Self::ParseFloat(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Self {
+        Self::ParseInt(e)
+    }
+}
+
+impl From<ParseFloatError> for Error {
+    fn from(e: ParseFloatError) -> Self {
+        Self::ParseFloat(e)
+    }
+}
+
+/// The columns of the label in the first line of a set.
+pub const LABEL: Range<usize> = 43..47;
+/// The columns of the resonance flag in the first line of a set.
+pub const RESONANCE: Range<usize> = 47..48;
+/// The columns of the reverse flag in the first line of a set.
+pub const REVERSE: Range<usize> = 48..49;
+/// The columns of the Q-value in the first line of a set.
+pub const Q_VALUE: Range<usize> = 52..64;
+
+/// The columns of the `i`th nuclide in the first line of a set, where each takes up `width`
+/// columns (5 in the standard format).
+#[must_use]
+pub const fn species(i: usize, width: usize) -> Range<usize> {
+    (5 + width * i)..(5 + width * (i + 1))
+}
+
+/// The line of a set (1 or 2) and the columns of the `i`th rate parameter.
+///
+/// The first 4 parameters are on the second line, and the rest are on the third.
+#[must_use]
+pub const fn param(i: usize) -> (usize, Range<usize>) {
+    if i < 4 {
+        (1, 13 * i..13 * (i + 1))
+    } else {
+        (2, 13 * (i - 4)..13 * (i - 3))
+    }
+}
+
+/// The text in `range` of `line`, without surrounding whitespace.
+///
+/// # Errors
+///
+/// Will return `Err` if `line` is too short, or `range` isn't on character boundaries.
+pub fn field(line: &str, range: Range<usize>) -> Result<&str, Error> {
+    if line.len() < range.end {
+        Err(Error::TooShortLine)
+    } else {
+        Ok(line.get(range).ok_or(Error::StrIndex)?.trim())
+    }
+}
+
+/// Parse a number, which may also use a Fortran-style `D` exponent, like `1.234560D+00`.
+///
+/// # Errors
+///
+/// Will return `Err` if `s` isn't a number.
+pub fn parse_float(s: &str) -> Result<f64, Error> {
+    match s.parse() {
+        Ok(x) => Ok(x),
+        Err(e) => match s.find(['D', 'd']) {
+            Some(i) => Ok(format!("{}e{}", &s[..i], &s[i + 1..]).parse()?),
+            None => Err(e.into()),
+        },
+    }
+}
+
+/// The resonance flag in `s`, as `'n'`, `'r'`, `'w'`, or `'s'`.
+///
+/// A blank flag is non-resonant, like `n`.
+///
+/// # Errors
+///
+/// Will return `Err` if the flag isn't known.
+pub fn resonance(s: &str) -> Result<char, Error> {
+    match s {
+        "" | " " | "n" => Ok('n'),
+        "r" => Ok('r'),
+        "w" => Ok('w'),
+        "s" => Ok('s'),
+        _ => Err(Error::UnknownResonance(s.to_string())),
+    }
+}
+
+/// Whether the reverse flag in `s` is set.
+#[must_use]
+pub fn reverse(s: &str) -> bool {
+    s == "v"
+}
+
+/// The number of reactants and products in the standard chapter numbered `number`.
+#[must_use]
+pub const fn chapter_counts(number: u8) -> Option<(usize, usize)> {
+    match number {
+        1 => Some((1, 1)),
+        2 => Some((1, 2)),
+        3 => Some((1, 3)),
+        4 => Some((2, 1)),
+        5 => Some((2, 2)),
+        6 => Some((2, 3)),
+        7 => Some((2, 4)),
+        8 => Some((3, 1)),
+        9 => Some((3, 2)),
+        10 => Some((4, 2)),
+        11 => Some((1, 4)),
+        _ => None,
+    }
+}
+
+/// The chapter number in a group of 3 lines of a REACLIB 1 file, if it is a chapter header.
+///
+/// A group is a chapter header if its last two lines are blank, and is otherwise a set.
+///
+/// # Errors
+///
+/// Will return `Some(Err)` if it is a chapter header, but the number doesn't parse.
+#[must_use]
+pub fn chapter_v1(lines: [&str; 3]) -> Option<Result<u8, Error>> {
+    let [l1, l2, l3] = lines;
+    (l2.trim().is_empty() && l3.trim().is_empty()).then(|| chapter_v2(l1))
+}
+
+/// The chapter number in the line before a set in a REACLIB 2 file.
+///
+/// # Errors
+///
+/// Will return `Err` if the number doesn't parse.
+pub fn chapter_v2(line: &str) -> Result<u8, Error> {
+    Ok(line.trim().parse()?)
+}
+
+/// Read a line into `bytes`, without the line ending, returning whether anything was read.
+///
+/// A line ends at a single `\n`, `\r\n`, or `\r`, so that files with Windows or old Mac line
+/// endings are read the same, including their empty lines.
+///
+/// # Errors
+///
+/// Will return `Err` if reading fails.
+pub fn read_raw_line<R: BufRead + ?Sized>(reader: &mut R, bytes: &mut Vec<u8>) -> io::Result<bool> {
+    let mut read = false;
+    loop {
+        // retry if interrupted, like `BufRead::read_until`
+        match reader.fill_buf() {
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+            Ok(_) => {}
+        }
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            return Ok(read);
+        }
+        read = true;
+        let Some(i) = available.iter().position(|&b| b == b'\n' || b == b'\r') else {
+            let len = available.len();
+            bytes.extend_from_slice(available);
+            reader.consume(len);
+            continue;
+        };
+        let cr = available[i] == b'\r';
+        bytes.extend_from_slice(&available[..i]);
+        reader.consume(i + 1);
+        if cr && reader.fill_buf()?.first() == Some(&b'\n') {
+            reader.consume(1);
+        }
+        return Ok(true);
+    }
+}
+
+/// `line` without control characters, such as a stray `\r` or an end of file marker (`^Z`), at
+/// its end, since they would otherwise be read as part of the last field.
+#[must_use]
+pub fn trim_end_control(line: &str) -> &str {
+    line.trim_end_matches(char::is_control)
+}
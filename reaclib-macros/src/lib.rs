@@ -0,0 +1,233 @@
+//! Compile-time embedding of reaclib data.
+//!
+//! This is re-exported by the [reaclib](https://docs.rs/reaclib) crate with its `macros`
+//! feature, which should be used instead of depending on this crate directly.
+
+use proc_macro::{Delimiter, Group, Span, TokenStream, TokenTree};
+use std::{env, fmt::Write, fs, path::PathBuf};
+
+/// Parse a reaclib file at compile time into a `&'static [reaclib::StaticSet]`.
+///
+/// The path is relative to the directory containing the crate's `Cargo.toml`, and it can be
+/// followed by the format, `Reaclib1` or `Reaclib2` (the default).
+/// A parsing error is a compile error, and the crate is rebuilt when the file changes.
+#[proc_macro]
+pub fn include_reaclib(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(tokens) => tokens,
+        Err((message, span)) => compile_error(&message, span),
+    }
+}
+
+type Error = (String, Span);
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Format {
+    Reaclib1,
+    Reaclib2,
+}
+
+fn expand(input: TokenStream) -> Result<TokenStream, Error> {
+    let (path, format) = parse_args(input)?;
+    let dir = env::var_os("CARGO_MANIFEST_DIR").ok_or_else(|| {
+        (
+            "CARGO_MANIFEST_DIR is not set".to_string(),
+            Span::call_site(),
+        )
+    })?;
+    let path = PathBuf::from(dir).join(path);
+    let data = fs::read_to_string(&path).map_err(|e| {
+        (
+            format!("couldn't read {}: {e}", path.display()),
+            Span::call_site(),
+        )
+    })?;
+
+    let sets = parse(&data, format)
+        .map_err(|(line, e)| (format!("{}:{line}: {e}", path.display()), Span::call_site()))?;
+
+    // including the file makes cargo rebuild the crate when it changes
+    let mut code = format!(
+        "{{ const _: &[u8] = ::core::include_bytes!({:?}); \
+         const SETS: &[::reaclib::StaticSet] = &[",
+        path.display()
+    );
+    for set in &sets {
+        code.push_str(set);
+        code.push(',');
+    }
+    code.push_str("]; SETS }");
+    code.parse()
+        .map_err(|e| (format!("invalid expansion: {e:?}"), Span::call_site()))
+}
+
+fn parse_args(input: TokenStream) -> Result<(String, Format), Error> {
+    let usage = "expected a path, optionally followed by `Reaclib1` or `Reaclib2`";
+    let mut tokens = input.into_iter();
+
+    let path = match tokens.next() {
+        Some(TokenTree::Literal(literal)) => {
+            let s = literal.to_string();
+            match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+                Some(path) if !path.contains('\\') => path.to_string(),
+                _ => return Err((usage.to_string(), literal.span())),
+            }
+        }
+        Some(t) => return Err((usage.to_string(), t.span())),
+        None => return Err((usage.to_string(), Span::call_site())),
+    };
+
+    let format = match (tokens.next(), tokens.next()) {
+        (None, _) => Format::Reaclib2,
+        (Some(TokenTree::Punct(p)), None) if p.as_char() == ',' => Format::Reaclib2,
+        (Some(TokenTree::Punct(p)), Some(TokenTree::Ident(i))) if p.as_char() == ',' => {
+            match i.to_string().as_str() {
+                "Reaclib1" => Format::Reaclib1,
+                "Reaclib2" => Format::Reaclib2,
+                _ => return Err((usage.to_string(), i.span())),
+            }
+        }
+        (Some(t), _) => return Err((usage.to_string(), t.span())),
+    };
+    match tokens.next() {
+        None => Ok((path, format)),
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' && tokens.next().is_none() => {
+            Ok((path, format))
+        }
+        Some(t) => Err((usage.to_string(), t.span())),
+    }
+}
+
+fn compile_error(message: &str, span: Span) -> TokenStream {
+    let mut tokens: TokenStream = format!("::core::compile_error!({message:?})")
+        .parse()
+        .expect("a compile_error invocation is valid");
+    tokens = tokens
+        .into_iter()
+        .map(|mut t| {
+            t.set_span(span);
+            t
+        })
+        .collect();
+    TokenStream::from(TokenTree::Group(Group::new(Delimiter::Brace, tokens)))
+}
+
+// parse `data` into the code for each set, with the rules of the format in `reaclib_core`, which
+// `reaclib::Iter` also uses
+// errors have the (1-based) line number that they were found on
+fn parse(data: &str, format: Format) -> Result<Vec<String>, (usize, String)> {
+    let lines = lines(data);
+    let counts = |i: usize, number: Result<u8, reaclib_core::Error>| {
+        let number = number.map_err(|e| (i + 1, format!("chapter: {e}")))?;
+        reaclib_core::chapter_counts(number)
+            .ok_or_else(|| (i + 1, format!("unknown chapter: {number}")))
+    };
+
+    let mut sets = Vec::new();
+    let mut chapter = None;
+    let mut i = 0;
+    while i < lines.len() {
+        match format {
+            Format::Reaclib1 => {
+                if i + 3 > lines.len() {
+                    return Err((i + 1, "too few lines in a set".to_string()));
+                }
+                if let Some(number) =
+                    reaclib_core::chapter_v1([&lines[i], &lines[i + 1], &lines[i + 2]])
+                {
+                    chapter = Some(counts(i, number)?);
+                } else {
+                    let counts = chapter.ok_or((i + 1, "no chapter set".to_string()))?;
+                    sets.push(parse_set(counts, &lines[i..i + 3], i)?);
+                }
+                i += 3;
+            }
+            Format::Reaclib2 => {
+                if i + 4 > lines.len() {
+                    return Err((i + 1, "too few lines in a set".to_string()));
+                }
+                let counts = counts(i, reaclib_core::chapter_v2(&lines[i]))?;
+                sets.push(parse_set(counts, &lines[i + 1..i + 4], i + 1)?);
+                i += 4;
+            }
+        }
+    }
+    Ok(sets)
+}
+
+// split `data` into lines like `reaclib::Iter`
+fn lines(data: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut rest = data.as_bytes();
+    let mut bytes = Vec::new();
+    while reaclib_core::read_raw_line(&mut rest, &mut bytes)
+        .expect("reading from a slice can't fail")
+    {
+        let line = String::from_utf8(std::mem::take(&mut bytes))
+            .expect("`data` is a str, and lines end at ASCII characters");
+        lines.push(reaclib_core::trim_end_control(&line).to_string());
+    }
+    lines
+}
+
+fn parse_set(
+    (reactants, products): (usize, usize),
+    lines: &[String],
+    first: usize,
+) -> Result<String, (usize, String)> {
+    // an error in `field` on line `l` of the set
+    let error =
+        |l: usize, field: &str, e: reaclib_core::Error| (first + l + 1, format!("{field}: {e}"));
+    let float = |l: usize, range, field: &str| -> Result<String, (usize, String)> {
+        let x = reaclib_core::field(&lines[l], range)
+            .and_then(reaclib_core::parse_float)
+            .map_err(|e| error(l, field, e))?;
+        Ok(if x.is_nan() {
+            "::core::f64::NAN".to_string()
+        } else if x.is_infinite() {
+            format!("{}::core::f64::INFINITY", if x < 0.0 { "-" } else { "" })
+        } else {
+            format!("{x:?}")
+        })
+    };
+
+    let nuclides = |range: std::ops::Range<usize>, field: &str| {
+        let mut s = String::from("&[");
+        for (n, i) in range.enumerate() {
+            let name = reaclib_core::field(&lines[0], reaclib_core::species(i, 5))
+                .map_err(|e| error(0, &format!("{field} {}", n + 1), e))?;
+            write!(s, "{name:?},").expect("writing to a String can't fail");
+        }
+        s.push(']');
+        Ok::<_, (usize, String)>(s)
+    };
+    let reactants_code = nuclides(0..reactants, "reactant")?;
+    let products_code = nuclides(reactants..reactants + products, "product")?;
+    let label =
+        reaclib_core::field(&lines[0], reaclib_core::LABEL).map_err(|e| error(0, "label", e))?;
+    let resonance = reaclib_core::field(&lines[0], reaclib_core::RESONANCE)
+        .and_then(reaclib_core::resonance)
+        .map_err(|e| error(0, "resonance flag", e))?;
+    let resonance = match resonance {
+        'r' => "Resonant",
+        'w' => "Weak",
+        's' => "S",
+        _ => "NonResonant",
+    };
+    let reverse = reaclib_core::field(&lines[0], reaclib_core::REVERSE)
+        .map_err(|e| error(0, "reverse flag", e))?;
+    let reverse = reaclib_core::reverse(reverse);
+    let q_value = float(0, reaclib_core::Q_VALUE, "Q-value")?;
+
+    let mut params = String::new();
+    for i in 0..7 {
+        let (l, range) = reaclib_core::param(i);
+        write!(params, "{},", float(l, range, &format!("parameter a{i}"))?)
+            .expect("writing to a String can't fail");
+    }
+
+    Ok(format!(
+        "::reaclib::StaticSet::new({reactants_code}, {products_code}, {label:?}, \
+         ::reaclib::Resonance::{resonance}, {reverse}, {q_value}, [{params}])"
+    ))
+}
@@ -0,0 +1,113 @@
+use crate::{Chapter, Library, Set};
+use arrow_array::{
+    builder::{BooleanBuilder, Float64Builder, ListBuilder, StringBuilder, UInt8Builder},
+    ArrayRef, RecordBatch,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use std::sync::Arc;
+
+// the names of the columns holding the parameters
+const PARAMS: [&str; 7] = ["a0", "a1", "a2", "a3", "a4", "a5", "a6"];
+
+impl Library {
+    /// The schema of the record batches made by [`to_record_batch`][Self::to_record_batch].
+    ///
+    /// There is a row for each set, with the columns:
+    ///
+    /// * `reactants` and `products`: lists of nuclide names
+    /// * `chapter`: the reaclib chapter, or null if the reaction doesn't fit in one
+    /// * `label`: the source label
+    /// * `resonance`: the resonance flag, as it is written in reaclib (`n`, `r`, `w`, or `s`)
+    /// * `reverse`: whether the set is a reverse rate
+    /// * `q_value`: the Q-value, in MeV
+    /// * `a0` to `a6`: the parameters
+    #[must_use]
+    pub fn arrow_schema() -> SchemaRef {
+        let names = || Field::new_list_field(DataType::Utf8, true);
+        let mut fields = vec![
+            Field::new_list("reactants", names(), false),
+            Field::new_list("products", names(), false),
+            Field::new("chapter", DataType::UInt8, true),
+            Field::new("label", DataType::Utf8, false),
+            Field::new("resonance", DataType::Utf8, false),
+            Field::new("reverse", DataType::Boolean, false),
+            Field::new("q_value", DataType::Float64, false),
+        ];
+        fields.extend(PARAMS.map(|p| Field::new(p, DataType::Float64, false)));
+        Arc::new(Schema::new(fields))
+    }
+
+    /// Convert all of the sets in the library into a single Arrow [`RecordBatch`], with the
+    /// schema given by [`arrow_schema`][Self::arrow_schema].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reaclib::{Format, Library};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"1
+    ///          n    p                            wc12w     7.82300e-01
+    /// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00");
+    /// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+    ///
+    /// let batch = library.to_record_batch();
+    /// assert_eq!(batch.num_rows(), 1);
+    /// assert_eq!(batch.schema(), Library::arrow_schema());
+    /// ```
+    #[must_use]
+    pub fn to_record_batch(&self) -> RecordBatch {
+        record_batch(self.sets())
+    }
+
+    /// An iterator of Arrow [`RecordBatch`]es with up to `batch_size` sets each, in the order the
+    /// sets were added.
+    ///
+    /// See [`to_record_batch`][Self::to_record_batch].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is 0.
+    pub fn record_batches(&self, batch_size: usize) -> impl Iterator<Item = RecordBatch> + '_ {
+        self.sets().chunks(batch_size).map(record_batch)
+    }
+}
+
+fn record_batch(sets: &[Set]) -> RecordBatch {
+    let mut reactants = ListBuilder::new(StringBuilder::new());
+    let mut products = ListBuilder::new(StringBuilder::new());
+    let mut chapter = UInt8Builder::with_capacity(sets.len());
+    let mut label = StringBuilder::new();
+    let mut resonance = StringBuilder::new();
+    let mut reverse = BooleanBuilder::with_capacity(sets.len());
+    let mut q_value = Float64Builder::with_capacity(sets.len());
+    let mut params = PARAMS.map(|_| Float64Builder::with_capacity(sets.len()));
+
+    for set in sets {
+        reactants.append_value(set.reactants.iter().map(|n| Some(n.as_str())));
+        products.append_value(set.products.iter().map(|n| Some(n.as_str())));
+        chapter.append_option(
+            Chapter::from_counts(set.reactants.len(), set.products.len()).map(|c| c.number()),
+        );
+        label.append_value(set.label);
+        resonance.append_value(set.resonance.as_char().encode_utf8(&mut [0; 4]));
+        reverse.append_value(set.reverse);
+        q_value.append_value(set.q_value);
+        for (builder, p) in params.iter_mut().zip(set.params) {
+            builder.append_value(p);
+        }
+    }
+
+    let mut columns: Vec<ArrayRef> = vec![
+        Arc::new(reactants.finish()),
+        Arc::new(products.finish()),
+        Arc::new(chapter.finish()),
+        Arc::new(label.finish()),
+        Arc::new(resonance.finish()),
+        Arc::new(reverse.finish()),
+        Arc::new(q_value.finish()),
+    ];
+    columns.extend(params.map(|mut b| Arc::new(b.finish()) as ArrayRef));
+    RecordBatch::try_new(Library::arrow_schema(), columns).expect("the columns match the schema")
+}
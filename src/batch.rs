@@ -0,0 +1,166 @@
+use crate::{error::ReaclibError as RError, write_sets, Format, Iter, Set};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// The outcome of converting a single file with [`convert_dir`] or [`convert_dir_with`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Conversion {
+    /// The file that was read.
+    pub input: PathBuf,
+    /// The file that was (or would have been) written.
+    pub output: PathBuf,
+    /// The number of sets that were converted, or the error that stopped the conversion.
+    pub result: Result<usize, RError>,
+}
+
+/// A record of every file converted by [`convert_dir`] or [`convert_dir_with`].
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Manifest {
+    /// The conversions, sorted by input path.
+    pub conversions: Vec<Conversion>,
+}
+
+impl Manifest {
+    /// An iterator over the conversions that succeeded.
+    pub fn succeeded(&self) -> impl Iterator<Item = &Conversion> {
+        self.conversions.iter().filter(|c| c.result.is_ok())
+    }
+
+    /// An iterator over the conversions that failed.
+    pub fn failed(&self) -> impl Iterator<Item = &Conversion> {
+        self.conversions.iter().filter(|c| c.result.is_err())
+    }
+
+    /// Whether every conversion succeeded.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.conversions.iter().all(|c| c.result.is_ok())
+    }
+
+    /// Write the manifest as tab-separated values, with one line for each file.
+    ///
+    /// Each line has `ok` or `error`, the input path, the output path, and either the number of
+    /// sets or the error message.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error.
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), RError> {
+        for c in &self.conversions {
+            let (status, detail) = match &c.result {
+                Ok(sets) => ("ok", sets.to_string()),
+                Err(e) => ("error", e.to_string()),
+            };
+            writeln!(
+                writer,
+                "{status}\t{}\t{}\t{detail}",
+                c.input.display(),
+                c.output.display()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Convert every file in the directory tree `input` from `from` to `to`.
+///
+/// Each file is written to the same relative path under `output`, and the directories are created
+/// as needed.
+/// A file that can't be read or parsed doesn't stop the other conversions, it is recorded as a
+/// failure in the returned [`Manifest`].
+/// If `output` is inside `input`, it is skipped.
+///
+/// With the `rayon` feature, the files are converted in parallel.
+///
+/// # Errors
+///
+/// Will return `Err` if there is an io error while listing the files in `input`.
+pub fn convert_dir(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    from: Format,
+    to: Format,
+) -> Result<Manifest, RError> {
+    convert_dir_with(input, output, from, None, |sets, writer| {
+        write_sets(writer, sets, to)
+    })
+}
+
+/// Like [`convert_dir`], but with `write` writing the sets of each file.
+///
+/// This allows converting to formats other than reaclib, such as JSON.
+/// If `extension` is given, it replaces the extension of each output file.
+///
+/// # Errors
+///
+/// Will return `Err` if there is an io error while listing the files in `input`.
+pub fn convert_dir_with<F>(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    from: Format,
+    extension: Option<&str>,
+    write: F,
+) -> Result<Manifest, RError>
+where
+    F: Fn(&[Set], &mut dyn Write) -> Result<(), RError> + Sync,
+{
+    let (input, output) = (input.as_ref(), output.as_ref());
+    let mut files = Vec::new();
+    list_files(input, output, &mut files)?;
+    files.sort();
+
+    let convert = |path: PathBuf| {
+        let mut target = output.join(path.strip_prefix(input).unwrap_or(&path));
+        if let Some(extension) = extension {
+            target.set_extension(extension);
+        }
+        let result = convert_file(&path, &target, from, &write);
+        Conversion {
+            input: path,
+            output: target,
+            result,
+        }
+    };
+
+    #[cfg(feature = "rayon")]
+    let conversions = files.into_par_iter().map(convert).collect();
+    #[cfg(not(feature = "rayon"))]
+    let conversions = files.into_iter().map(convert).collect();
+    Ok(Manifest { conversions })
+}
+
+// every file under `dir`, except for those under `skip`
+fn list_files(dir: &Path, skip: &Path, files: &mut Vec<PathBuf>) -> Result<(), RError> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path == skip {
+            continue;
+        }
+        if path.is_dir() {
+            list_files(&path, skip, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn convert_file<F>(input: &Path, output: &Path, from: Format, write: &F) -> Result<usize, RError>
+where
+    F: Fn(&[Set], &mut dyn Write) -> Result<(), RError>,
+{
+    let sets =
+        Iter::new(BufReader::new(File::open(input)?), from).collect::<Result<Vec<_>, _>>()?;
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut writer = BufWriter::new(File::create(output)?);
+    write(&sets, &mut writer)?;
+    writer.flush()?;
+    Ok(sets.len())
+}
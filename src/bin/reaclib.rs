@@ -0,0 +1,398 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use reaclib::{
+    convert_dir_with, log_grid, write_sets, Format, Library, Nuclide, Reaction, ReactionRate,
+    Resonance, Set, Stats,
+};
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, stdin, stdout, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// Tools for working with files in the reaclib format.
+#[derive(Parser, Debug)]
+#[command(version, long_about = None)]
+struct Cli {
+    /// The reaclib format of the input files (1, 2).
+    #[arg(short, long, global = true, default_value = "2", value_parser = format_parse)]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Convert a file to another format.
+    Convert {
+        /// The format to write.
+        #[arg(short, long)]
+        to: Output,
+
+        /// File to read from, or `-` for stdin.
+        file: PathBuf,
+    },
+    /// Convert every file in a directory tree to another format.
+    ///
+    /// A manifest with a line for each file is written to stdout (or to `--manifest`), and the
+    /// exit status is 1 if any file couldn't be converted.
+    ConvertDir {
+        /// The format to write.
+        #[arg(short, long)]
+        to: Output,
+
+        /// Write the manifest to this file instead of stdout.
+        #[arg(short, long)]
+        manifest: Option<PathBuf>,
+
+        /// The directory to read from.
+        input: PathBuf,
+
+        /// The directory to write to.
+        output: PathBuf,
+    },
+    /// Write the sets that match all of the given filters.
+    Filter {
+        #[command(flatten)]
+        filter: Filters,
+
+        /// The format to write (the same as the input by default).
+        #[arg(short, long)]
+        to: Option<Output>,
+
+        /// File to read from, or `-` for stdin.
+        file: PathBuf,
+    },
+    /// Print a summary of a file.
+    Info {
+        /// Also list every nuclide.
+        #[arg(short, long)]
+        nuclides: bool,
+
+        /// File to read from, or `-` for stdin.
+        file: PathBuf,
+    },
+    /// Print the sets that are only in one of two files.
+    ///
+    /// Exits with status 1 if the files differ.
+    Diff {
+        /// The original file.
+        old: PathBuf,
+
+        /// The changed file.
+        new: PathBuf,
+    },
+    /// Print the reactions that were added, removed, or refit between two files, with how much
+    /// the rates of the refit reactions changed.
+    Changelog {
+        /// Evaluate the rates on a logarithmic grid of temperatures (in GK).
+        #[arg(
+            short,
+            long,
+            num_args = 3,
+            value_names = ["MIN", "MAX", "POINTS"],
+            default_values = ["0.01", "10", "31"]
+        )]
+        grid: Vec<String>,
+
+        /// Write the changelog as JSON.
+        #[arg(short, long)]
+        json: bool,
+
+        /// The original file.
+        old: PathBuf,
+
+        /// The changed file.
+        new: PathBuf,
+    },
+    /// Print the total rate of a reaction, followed by the rate of each of its sets.
+    Rate {
+        /// Evaluate the rate on a logarithmic grid of temperatures (in GK).
+        #[arg(
+            short,
+            long,
+            num_args = 3,
+            value_names = ["MIN", "MAX", "POINTS"],
+            conflicts_with = "temperatures"
+        )]
+        grid: Option<Vec<String>>,
+
+        /// File to read from, or `-` for stdin.
+        file: PathBuf,
+
+        /// The reaction, like `he4 c12 -> o16` or `c12(a,g)o16`.
+        reaction: Reaction,
+
+        /// The temperatures (in GK) to evaluate the rate at.
+        #[arg(required_unless_present = "grid")]
+        temperatures: Vec<f64>,
+    },
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+enum Output {
+    /// Reaclib 1
+    #[value(name = "1")]
+    Reaclib1,
+    /// Reaclib 2
+    #[value(name = "2")]
+    Reaclib2,
+    /// A JSON list of sets
+    Json,
+}
+
+#[derive(Args, Debug)]
+struct Filters {
+    /// Only keep sets involving this nuclide (may be repeated).
+    #[arg(short, long)]
+    nuclide: Vec<Nuclide>,
+
+    /// Only keep sets with this label (may be repeated).
+    #[arg(short, long)]
+    label: Vec<String>,
+
+    /// Only keep sets in this chapter.
+    #[arg(short, long)]
+    chapter: Option<u8>,
+
+    /// Only keep sets of this reaction, like `he4 c12 -> o16`.
+    #[arg(short, long)]
+    reaction: Option<Reaction>,
+}
+
+impl Filters {
+    fn matches(&self, set: &Set) -> bool {
+        let reaction = set.reaction();
+        self.nuclide.iter().all(|n| reaction.contains(n))
+            && (self.label.is_empty() || self.label.iter().any(|l| l == set.label.as_str()))
+            && self
+                .chapter
+                .is_none_or(|c| reaction.chapter().map(|ch| ch.number()) == Some(c))
+            && self.reaction.as_ref().is_none_or(|r| *r == reaction)
+    }
+}
+
+fn format_parse(s: &str) -> Result<Format, String> {
+    match s.parse::<u8>() {
+        Ok(1) => Ok(Format::Reaclib1),
+        Ok(2) => Ok(Format::Reaclib2),
+        _ => Err("Only '1' and '2' are valid formats".to_string()),
+    }
+}
+
+fn read(path: &Path, format: Format) -> Result<Library, Box<dyn Error>> {
+    let reader: Box<dyn BufRead> = if path == Path::new("-") {
+        Box::new(stdin().lock())
+    } else {
+        Box::new(BufReader::new(File::open(path)?))
+    };
+    Ok(Library::from_reader(reader, format)?)
+}
+
+fn write<'a>(
+    sets: impl IntoIterator<Item = &'a Set>,
+    output: Output,
+) -> Result<(), Box<dyn Error>> {
+    let mut writer = stdout().lock();
+    match output {
+        Output::Reaclib1 => write_sets(writer, sets, Format::Reaclib1)?,
+        Output::Reaclib2 => write_sets(writer, sets, Format::Reaclib2)?,
+        Output::Json => {
+            let sets = sets.into_iter().collect::<Vec<_>>();
+            serde_json::to_writer_pretty(&mut writer, &sets)?;
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
+
+fn print_info(stats: &Stats, list_nuclides: bool) {
+    println!("sets: {}", stats.sets);
+    println!("reactions: {}", stats.reactions);
+    println!("reverse sets: {}", stats.reverse);
+
+    let mut chapters = stats.chapters.iter().collect::<Vec<_>>();
+    chapters.sort_by_key(|(c, _)| c.number());
+    println!("chapters:");
+    for (chapter, count) in chapters {
+        println!("  {:>2}: {count}", chapter.number());
+    }
+
+    println!("resonance flags:");
+    let resonances = [
+        Resonance::NonResonant,
+        Resonance::Resonant,
+        Resonance::Weak,
+        Resonance::S,
+    ];
+    for resonance in resonances {
+        if let Some(count) = stats.resonances.get(&resonance) {
+            println!("  {}: {count}", resonance.as_char());
+        }
+    }
+
+    println!("labels:");
+    for (label, count) in &stats.labels {
+        println!("  {label:>4}: {count}");
+    }
+
+    println!("nuclides: {}", stats.nuclides.len());
+    if list_nuclides {
+        println!("  {}", stats.nuclides.join(" "));
+    }
+
+    for (name, set) in [("lowest Q", &stats.min_q), ("highest Q", &stats.max_q)] {
+        if let Some(set) = set {
+            println!(
+                "{name}: {} MeV ({}, {})",
+                set.q_value,
+                set.reaction(),
+                set.label
+            );
+        }
+    }
+}
+
+fn grid_parse(grid: &[String]) -> Result<Vec<f64>, Box<dyn Error>> {
+    let (min, max, points) = grid_bounds(grid)?;
+    Ok(log_grid(min, max, points))
+}
+
+fn grid_bounds(grid: &[String]) -> Result<(f64, f64, usize), Box<dyn Error>> {
+    let [min, max, points] = grid else {
+        return Err("a grid needs a minimum, a maximum, and a number of points".into());
+    };
+    let points = points.parse()?;
+    if points == 0 {
+        return Err("a grid needs at least one point".into());
+    }
+    Ok((min.parse()?, max.parse()?, points))
+}
+
+fn print_rate(rate: &ReactionRate, temperatures: &[f64]) {
+    println!("# {}", rate.reaction);
+    print!("#{:>11} {:>12}", "T9", "total");
+    for set in &rate.sets {
+        print!(
+            " {:>11}",
+            format!("{}({})", set.label, set.resonance.as_char())
+        );
+    }
+    println!();
+    for &t in temperatures {
+        print!("{t:>12.4e} {:>12.4e}", rate.rate(t));
+        for set in &rate.sets {
+            print!(" {:>11.4e}", set.rate(t));
+        }
+        println!();
+    }
+}
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let cli = Cli::parse();
+    let format = cli.format;
+
+    match cli.command {
+        Command::Convert { to, file } => write(read(&file, format)?.sets(), to)?,
+        Command::ConvertDir {
+            to,
+            manifest,
+            input,
+            output,
+        } => {
+            let extension = (to == Output::Json).then_some("json");
+            let manifest_data =
+                convert_dir_with(&input, &output, format, extension, |sets, w| match to {
+                    Output::Reaclib1 => write_sets(w, sets, Format::Reaclib1),
+                    Output::Reaclib2 => write_sets(w, sets, Format::Reaclib2),
+                    Output::Json => {
+                        serde_json::to_writer_pretty(&mut *w, sets).map_err(io::Error::from)?;
+                        Ok(writeln!(w)?)
+                    }
+                })?;
+            match manifest {
+                Some(path) => manifest_data.write(File::create(path)?)?,
+                None => manifest_data.write(stdout().lock())?,
+            }
+            if !manifest_data.is_success() {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Filter { filter, to, file } => {
+            let to = to.unwrap_or(match format {
+                Format::Reaclib1 => Output::Reaclib1,
+                _ => Output::Reaclib2,
+            });
+            let library = read(&file, format)?;
+            write(library.sets().iter().filter(|s| filter.matches(s)), to)?;
+        }
+        Command::Info { nuclides, file } => print_info(&read(&file, format)?.stats(), nuclides),
+        Command::Diff { old, new } => {
+            let (old, new) = (read(&old, format)?, read(&new, format)?);
+            let diff = old.diff(&new);
+            for (sign, sets) in [('-', &diff.removed), ('+', &diff.added)] {
+                for set in sets {
+                    println!("{sign} {} ({})", set.reaction(), set.label);
+                }
+            }
+            if !diff.is_empty() {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Changelog {
+            grid,
+            json,
+            old,
+            new,
+        } => {
+            let (old, new) = (read(&old, format)?, read(&new, format)?);
+            let (min, max, points) = grid_bounds(&grid)?;
+            let changelog = old.changelog(&new, min, max, points);
+            if json {
+                serde_json::to_writer_pretty(stdout().lock(), &changelog)?;
+                println!();
+            } else {
+                print!("{changelog}");
+            }
+        }
+        Command::Rate {
+            grid,
+            file,
+            reaction,
+            temperatures,
+        } => {
+            let temperatures = match grid {
+                Some(grid) => grid_parse(&grid)?,
+                None => temperatures,
+            };
+            let library = read(&file, format)?;
+            // fall back to matching the nuclides in any order, since the compact notation
+            // doesn't always put them in the same order as the file
+            let rate = library.reaction_rate(&reaction).or_else(|| {
+                let sets = library
+                    .get_canonical(&reaction)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let reaction = sets.first()?.reaction();
+                Some(ReactionRate { reaction, sets })
+            });
+            let rate = rate.ok_or_else(|| format!("{reaction} isn't in the file"))?;
+            print_rate(&rate, &temperatures);
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cli;
+    use clap::CommandFactory;
+
+    #[test]
+    fn verify_cli() {
+        Cli::command().debug_assert();
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{
+    error::ReaclibError as RError, nuclide::ELEMENTS, parse_float, Nuclide, ParseError, Reaction,
+    TabulatedRate,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::BufRead;
+
+/// Tabulated rates from the Brussels Nuclear Library for Astrophysics Applications (BRUSLIB),
+/// such as the Hauser-Feshbach rates used in r-process networks.
+///
+/// Each reaction is mapped into a [`TabulatedRate`], so that it can be evaluated and fit (see
+/// [`TabulatedRate::fit`]) alongside reaclib sets.
+///
+/// # Format
+///
+/// A file has a block of rates for each target nucleus.
+/// A block starts with a line that gives the target as `Z= <z>  A= <a>`, followed by a line of
+/// column names, which starts with `T9` and names each reaction in the usual `(x,y)` notation,
+/// such as `(n,g)` or `(p,a)`, and then a line for each temperature with the rate of each
+/// reaction.
+/// The particles `n`, `p`, `d`, `t`, `h` (helium-3), `a`, and `g` are understood.
+/// Other lines that don't start with a number, such as comments, are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::Bruslib;
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"
+/// # Hauser-Feshbach rates [cm3/mol/s]
+///  Z=  26 A=  56
+///  T9      (n,g)       (p,g)
+///  0.1     1.000E+07   0.000E+00
+///  1.0     2.000E+07   1.000E-05
+///  Z=  26 A=  57
+///  T9      (n,g)       (p,g)
+///  0.1     3.000E+07   0.000E+00
+///  1.0     4.000E+07   2.000E-05
+/// ");
+/// let bruslib = Bruslib::from_reader(reader).unwrap();
+///
+/// let reaction = "n fe56 -> fe57".parse().unwrap();
+/// let rate = bruslib.get(&reaction).unwrap();
+/// assert!((rate.rate(1.0) / 2e7 - 1.0).abs() < 1e-12);
+/// assert_eq!(bruslib.reactions().count(), 4);
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bruslib {
+    // in the order of the file
+    rates: Vec<(Reaction, TabulatedRate)>,
+}
+
+impl Bruslib {
+    /// Reads a `Bruslib` from `reader`.
+    ///
+    /// See the [format][Self#format].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error or a parsing error, a reaction isn't understood,
+    /// the rates come before a target or column names, a line has the wrong number of columns,
+    /// or the temperatures of a block aren't positive and strictly increasing.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, RError> {
+        let mut rates = Vec::new();
+        let mut block = Block::default();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let invalid =
+                |message: &str| ParseError::InvalidTable(format!("line {}: {message}", i + 1));
+            let mut fields = line.split_whitespace().peekable();
+            let Some(first) = fields.peek() else {
+                continue;
+            };
+
+            if let Some(target) = target(&line) {
+                block.finish(&mut rates)?;
+                block.target = Some(target?);
+            } else if first.eq_ignore_ascii_case("t9") {
+                let target = block.target.ok_or_else(|| invalid("no target"))?;
+                let reactions = fields
+                    .skip(1)
+                    .map(|name| reaction(target, name))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| invalid("unknown reaction"))?;
+                block.finish(&mut rates)?;
+                block.rates = vec![Vec::new(); reactions.len()];
+                block.reactions = reactions;
+            } else if let Ok(temperature) = parse_float(first) {
+                if block.reactions.is_empty() {
+                    return Err(invalid("no column names").into());
+                }
+                let values = fields
+                    .skip(1)
+                    .map(parse_float)
+                    .collect::<Result<Vec<_>, _>>()?;
+                if values.len() != block.reactions.len() {
+                    return Err(invalid("wrong number of columns").into());
+                }
+                block.temperatures.push(temperature);
+                for (rates, value) in block.rates.iter_mut().zip(values) {
+                    rates.push(value);
+                }
+            }
+        }
+        block.finish(&mut rates)?;
+
+        Ok(Self { rates })
+    }
+
+    /// The rate of `reaction`, if it is in the file.
+    #[must_use]
+    pub fn get(&self, reaction: &Reaction) -> Option<&TabulatedRate> {
+        self.rates
+            .iter()
+            .find_map(|(r, rate)| (r == reaction).then_some(rate))
+    }
+
+    /// The reactions, in the order of the file.
+    pub fn reactions(&self) -> impl Iterator<Item = &Reaction> {
+        self.rates.iter().map(|(r, _)| r)
+    }
+
+    /// The reactions and their rates, in the order of the file.
+    pub fn iter(&self) -> impl Iterator<Item = (&Reaction, &TabulatedRate)> {
+        self.rates.iter().map(|(r, rate)| (r, rate))
+    }
+}
+
+// the block of rates that is being read
+#[derive(Default)]
+struct Block {
+    target: Option<(u8, u16)>,
+    reactions: Vec<Reaction>,
+    temperatures: Vec<f64>,
+    // the rates of each reaction
+    rates: Vec<Vec<f64>>,
+}
+
+impl Block {
+    // move the rates that have been read into `rates`, and start a new block for the same target
+    fn finish(&mut self, rates: &mut Vec<(Reaction, TabulatedRate)>) -> Result<(), RError> {
+        let temperatures = std::mem::take(&mut self.temperatures);
+        for (reaction, values) in self.reactions.drain(..).zip(self.rates.drain(..)) {
+            let rate = TabulatedRate::new(&temperatures, &values).ok_or_else(|| {
+                ParseError::InvalidTable(format!("the rates of {reaction} aren't a valid table"))
+            })?;
+            rates.push((reaction, rate));
+        }
+        Ok(())
+    }
+}
+
+// the Z and A of the target, if `line` gives one
+fn target(line: &str) -> Option<Result<(u8, u16), RError>> {
+    let line = line.replace('=', " = ");
+    let fields = line.split_whitespace().collect::<Vec<_>>();
+    let value = |key: &str| {
+        fields
+            .windows(3)
+            .find(|w| w[0].eq_ignore_ascii_case(key) && w[1] == "=")
+            .map(|w| w[2])
+    };
+    let (z, a) = (value("z")?, value("a")?);
+    Some(
+        z.parse()
+            .and_then(|z| Ok((z, a.parse()?)))
+            .map_err(RError::from),
+    )
+}
+
+// the reaction on `target` named by `name`, e.g. `(n,g)`
+fn reaction((z, a): (u8, u16), name: &str) -> Option<Reaction> {
+    let (x, y) = name.strip_prefix('(')?.strip_suffix(')')?.split_once(',')?;
+    let (x, y) = (particle(x)?, particle(y)?);
+    // photons are left out
+    let light = |(z, a)| (a != 0).then(|| nuclide(z, a)).into_iter();
+
+    let ((z_x, a_x), (z_y, a_y)) = (x, y);
+    let z_product = z.checked_add(z_x)?.checked_sub(z_y)?;
+    let a_product = a.checked_add(a_x)?.checked_sub(a_y)?;
+    let reactants = light(x).chain([nuclide(z, a)]).collect::<Option<_>>()?;
+    let products = light(y)
+        .chain([nuclide(z_product, a_product)])
+        .collect::<Option<_>>()?;
+    Some(Reaction::new(reactants, products))
+}
+
+// the Z and A of a particle in a reaction name, which are 0 for a photon
+fn particle(name: &str) -> Option<(u8, u16)> {
+    match name.trim() {
+        "g" => Some((0, 0)),
+        "n" => Some((0, 1)),
+        "p" => Some((1, 1)),
+        "d" => Some((1, 2)),
+        "t" => Some((1, 3)),
+        "h" => Some((2, 3)),
+        "a" => Some((2, 4)),
+        _ => None,
+    }
+}
+
+// the reaclib name of the nuclide with `z` and `a`
+fn nuclide(z: u8, a: u16) -> Option<Nuclide> {
+    match (z, a) {
+        (0, 1) => "n".parse().ok(),
+        (1, 1) => "p".parse().ok(),
+        (1, 2) => "d".parse().ok(),
+        (1, 3) => "t".parse().ok(),
+        (0, _) => None,
+        _ => format!("{}{a}", ELEMENTS.get(usize::from(z))?).parse().ok(),
+    }
+}
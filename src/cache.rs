@@ -0,0 +1,105 @@
+use crate::{error::ReaclibError as RError, Library};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+// the start of every cache file
+const MAGIC: [u8; 8] = *b"reaclib\0";
+// this must be changed whenever the serialized form of `Library` changes
+const VERSION: u32 = 3;
+
+impl Library {
+    /// Write the library to `writer` in a compact binary form, which can be read back much
+    /// faster than the reaclib format.
+    /// The [metadata][Self::metadata] is kept along with the sets.
+    ///
+    /// The data starts with a header containing a format version, so that caches written by an
+    /// incompatible version of this library are rejected by [`read_cache`][Self::read_cache]
+    /// instead of being misread.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error, or a
+    /// [`ReaclibError::InvalidCache`][RError::InvalidCache] if the library can't be serialized.
+    pub fn write_cache<W: Write>(&self, mut writer: W) -> Result<(), RError> {
+        let data = postcard::to_stdvec(self).map_err(|_| RError::InvalidCache)?;
+
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&data)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read a library that was written by [`write_cache`][Self::write_cache].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error, if the data isn't a cache, or if the cache was
+    /// written with a different format version.
+    pub fn read_cache<R: Read>(mut reader: R) -> Result<Self, RError> {
+        let mut magic = [0; 8];
+        let mut version = [0; 4];
+        reader
+            .read_exact(&mut magic)
+            .and_then(|()| reader.read_exact(&mut version))
+            .map_err(|_| RError::InvalidCache)?;
+        if magic != MAGIC {
+            return Err(RError::InvalidCache);
+        }
+        let version = u32::from_le_bytes(version);
+        if version != VERSION {
+            return Err(RError::CacheVersion(version));
+        }
+
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        postcard::from_bytes(&data).map_err(|_| RError::InvalidCache)
+    }
+
+    /// Save the library to a cache file at `path`.
+    ///
+    /// See [`write_cache`][Self::write_cache].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use reaclib::{Format, Library};
+    /// use std::{fs::File, io::BufReader, path::Path};
+    ///
+    /// let cache = Path::new("reaclib.cache");
+    /// let library = if cache.exists() {
+    ///     Library::load_cache(cache)?
+    /// } else {
+    ///     let file = BufReader::new(File::open("reaclib")?);
+    ///     let library = Library::from_reader(file, Format::Reaclib1)?;
+    ///     library.save_cache(cache)?;
+    ///     library
+    /// };
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error, or a
+    /// [`ReaclibError::InvalidCache`][RError::InvalidCache] if the library can't be serialized.
+    pub fn save_cache(&self, path: impl AsRef<Path>) -> Result<(), RError> {
+        self.write_cache(BufWriter::new(File::create(path)?))
+    }
+
+    /// Load a library from a cache file at `path`.
+    ///
+    /// See [`read_cache`][Self::read_cache].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error, if the file isn't a cache, or if the cache was
+    /// written with a different format version.
+    pub fn load_cache(path: impl AsRef<Path>) -> Result<Self, RError> {
+        Self::read_cache(BufReader::new(File::open(path)?))
+    }
+}
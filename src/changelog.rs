@@ -0,0 +1,129 @@
+use crate::{Library, RateComparison, Reaction};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt};
+
+/// A summary of what changed between two releases of a library, from
+/// [`Library::changelog`].
+///
+/// With the `serde` feature, it can be serialized to share the changes as structured data, and it
+/// is written as a plain text report by [`Display`][fmt::Display].
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Library};
+/// use std::io::Cursor;
+///
+/// let old = Library::from_reader(Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00"), Format::Reaclib2).unwrap();
+/// let new = Library::from_reader(Cursor::new(r"4
+///          p  c12  n13                       nacrn     1.94400e+00
+///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+///  4.443620e+00-3.158980e+00-6.666670e-01"), Format::Reaclib2).unwrap();
+///
+/// let changelog = old.changelog(&new, 0.1, 10.0, 21);
+/// assert_eq!(changelog.added[0].to_string(), "p c12 -> n13");
+/// assert_eq!(changelog.removed[0].to_string(), "n -> p");
+/// assert!(changelog.to_string().starts_with("1 added, 1 removed, 0 refit, 0 unchanged"));
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Changelog {
+    /// The reactions that are only in the new library, sorted.
+    pub added: Vec<Reaction>,
+    /// The reactions that are only in the old library, sorted.
+    pub removed: Vec<Reaction>,
+    /// The reactions that are in both libraries but whose sets changed, with how much their rates
+    /// changed, from the largest change to the smallest.
+    pub refit: Vec<RateComparison>,
+    /// The number of reactions whose sets are the same in both libraries.
+    pub unchanged: usize,
+}
+
+impl Library {
+    /// Summarize what changed between this library and `new`, such as between two releases.
+    ///
+    /// A reaction is refit if any of its sets changed, as found by [`diff`][Self::diff], and
+    /// the change in its rate is found by [`compare_rates`][Self::compare_rates] on a grid of
+    /// `points` temperatures, logarithmically spaced between `min` and `max` (in GK).
+    /// If `points` is 0, no rates can be compared, so `refit` is empty and `unchanged` is 0, but
+    /// `added` and `removed` are still found.
+    #[must_use]
+    pub fn changelog(&self, new: &Self, min: f64, max: f64, points: usize) -> Changelog {
+        let diff = self.diff(new);
+        let changed = diff
+            .removed
+            .iter()
+            .chain(&diff.added)
+            .map(|s| s.reaction())
+            .collect::<HashSet<_>>();
+        let only_in = |a: &Self, b: &Self| {
+            let mut reactions = a
+                .reactions()
+                .filter(|r| b.get(r).next().is_none())
+                .cloned()
+                .collect::<Vec<_>>();
+            reactions.sort_by_cached_key(ToString::to_string);
+            reactions
+        };
+
+        let (added, removed) = (only_in(new, self), only_in(self, new));
+        let (refit, unchanged) = self
+            .compare_rates(new, min, max, points)
+            .into_iter()
+            .partition::<Vec<_>, _>(|c| changed.contains(&c.reaction));
+        Changelog {
+            added,
+            removed,
+            refit,
+            unchanged: unchanged.len(),
+        }
+    }
+}
+
+impl Changelog {
+    /// Whether nothing changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.refit.is_empty()
+    }
+}
+
+impl fmt::Display for Changelog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} added, {} removed, {} refit, {} unchanged",
+            self.added.len(),
+            self.removed.len(),
+            self.refit.len(),
+            self.unchanged
+        )?;
+        if !self.added.is_empty() {
+            writeln!(f, "\nAdded:")?;
+            for reaction in &self.added {
+                writeln!(f, "  + {reaction}")?;
+            }
+        }
+        if !self.removed.is_empty() {
+            writeln!(f, "\nRemoved:")?;
+            for reaction in &self.removed {
+                writeln!(f, "  - {reaction}")?;
+            }
+        }
+        if !self.refit.is_empty() {
+            writeln!(f, "\nRefit:")?;
+            for c in &self.refit {
+                writeln!(
+                    f,
+                    "  ~ {}: up to a factor of {:.3} (at T9 = {:.3}), mean ratio {:.3}",
+                    c.reaction, c.max_factor, c.max_temperature, c.mean_ratio
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
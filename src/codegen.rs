@@ -0,0 +1,175 @@
+use crate::{error::ReaclibError as RError, Nuclide, Reaction, ReactionRate};
+use std::io::Write;
+
+/// A language that rate routines can be generated in, with [`write_code`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum Language {
+    /// C99, using `math.h`.
+    C,
+    /// Free-form Fortran 2003, as a module named `reaclib_rates`.
+    Fortran,
+}
+
+/// Write standalone source code that evaluates each of `rates`, in `language`.
+///
+/// Each rate becomes a function of the temperature (in GK) that returns the total rate, summed
+/// over its sets.
+/// The functions are named `rate_` followed by the reactants, `_to_`, and the products, with
+/// each nuclide separated by `_` and any other characters replaced by `_`, so `he4 c12 -> o16`
+/// becomes `rate_he4_c12_to_o16`.
+/// The parameters are written with enough digits to reproduce them exactly.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{write_code, Format, Language, Library};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+/// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+/// let rates = library
+///     .reactions()
+///     .filter_map(|r| library.reaction_rate(r))
+///     .collect::<Vec<_>>();
+///
+/// let mut code = Vec::new();
+/// write_code(&mut code, &rates, Language::C).unwrap();
+/// let code = String::from_utf8(code).unwrap();
+/// assert!(code.contains("double rate_n_to_p(double t9)"));
+/// ```
+///
+/// # Errors
+///
+/// Will return `Err` if there is an io error.
+pub fn write_code<'a, W: Write>(
+    mut writer: W,
+    rates: impl IntoIterator<Item = &'a ReactionRate>,
+    language: Language,
+) -> Result<(), RError> {
+    match language {
+        Language::C => {
+            writeln!(writer, "/* generated by reaclib-rs */")?;
+            writeln!(writer, "#include <math.h>")?;
+            for rate in rates {
+                write_c(&mut writer, rate)?;
+            }
+        }
+        Language::Fortran => {
+            writeln!(writer, "! generated by reaclib-rs")?;
+            writeln!(writer, "module reaclib_rates")?;
+            writeln!(writer, "  implicit none")?;
+            writeln!(writer, "  integer, parameter :: dp = kind(1.0d0)")?;
+            writeln!(writer, "contains")?;
+            for rate in rates {
+                write_fortran(&mut writer, rate)?;
+            }
+            writeln!(writer, "end module reaclib_rates")?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_c<W: Write>(writer: &mut W, rate: &ReactionRate) -> Result<(), RError> {
+    let name = function_name(&rate.reaction);
+    let n = rate.sets.len();
+
+    writeln!(writer)?;
+    writeln!(writer, "/* {} */", rate.reaction)?;
+    writeln!(writer, "double {name}(double t9) {{")?;
+    if n == 0 {
+        writeln!(writer, "    return 0.0;")?;
+        writeln!(writer, "}}")?;
+        return Ok(());
+    }
+    writeln!(writer, "    static const double params[{n}][7] = {{")?;
+    for set in &rate.sets {
+        let params = set.params.map(|p| format!("{p:e}"));
+        writeln!(writer, "        {{{}}},", params.join(", "))?;
+    }
+    writeln!(writer, "    }};")?;
+    writeln!(writer, "    double t913 = cbrt(t9);")?;
+    writeln!(
+        writer,
+        "    double basis[7] = {{1.0, 1.0 / t9, 1.0 / t913, t913, t9, t9 * t913 * t913, log(t9)}};"
+    )?;
+    writeln!(writer, "    double rate = 0.0;")?;
+    writeln!(writer, "    for (int i = 0; i < {n}; i++) {{")?;
+    writeln!(writer, "        double x = 0.0;")?;
+    writeln!(writer, "        for (int j = 0; j < 7; j++) {{")?;
+    writeln!(writer, "            x += params[i][j] * basis[j];")?;
+    writeln!(writer, "        }}")?;
+    writeln!(writer, "        rate += exp(x);")?;
+    writeln!(writer, "    }}")?;
+    writeln!(writer, "    return rate;")?;
+    writeln!(writer, "}}")?;
+    Ok(())
+}
+
+fn write_fortran<W: Write>(writer: &mut W, rate: &ReactionRate) -> Result<(), RError> {
+    let name = function_name(&rate.reaction);
+    let n = rate.sets.len();
+
+    writeln!(writer)?;
+    writeln!(writer, "  ! {}", rate.reaction)?;
+    writeln!(writer, "  function {name}(t9) result(rate)")?;
+    writeln!(writer, "    real(dp), intent(in) :: t9")?;
+    writeln!(writer, "    real(dp) :: rate")?;
+    if n == 0 {
+        writeln!(writer, "    rate = 0.0_dp")?;
+        writeln!(writer, "  end function {name}")?;
+        return Ok(());
+    }
+    writeln!(
+        writer,
+        "    real(dp), parameter :: params(7, {n}) = reshape([ &"
+    )?;
+    for (i, set) in rate.sets.iter().enumerate() {
+        let params = set.params.map(|p| format!("{p:e}_dp"));
+        // split like the reaclib format, to keep the lines short
+        writeln!(writer, "      {}, &", params[..4].join(", "))?;
+        let end = if i + 1 == n { " &" } else { ", &" };
+        writeln!(writer, "      {}{end}", params[4..].join(", "))?;
+    }
+    writeln!(writer, "      ], [7, {n}])")?;
+    writeln!(writer, "    real(dp) :: t913, basis(7)")?;
+    writeln!(writer, "    integer :: i")?;
+    writeln!(writer, "    t913 = t9**(1.0_dp / 3.0_dp)")?;
+    writeln!(
+        writer,
+        "    basis = [1.0_dp, 1.0_dp / t9, 1.0_dp / t913, t913, t9, t9 * t913 * t913, log(t9)]"
+    )?;
+    writeln!(writer, "    rate = 0.0_dp")?;
+    writeln!(writer, "    do i = 1, {n}")?;
+    writeln!(
+        writer,
+        "      rate = rate + exp(dot_product(params(:, i), basis))"
+    )?;
+    writeln!(writer, "    end do")?;
+    writeln!(writer, "  end function {name}")?;
+    Ok(())
+}
+
+// a name for the function that evaluates `reaction`, which is a valid identifier in C and Fortran
+fn function_name(reaction: &Reaction) -> String {
+    let join = |nuclides: &[Nuclide]| {
+        nuclides
+            .iter()
+            .map(|n| {
+                n.chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("_")
+    };
+    format!(
+        "rate_{}_to_{}",
+        join(reaction.reactants()),
+        join(reaction.products())
+    )
+}
@@ -0,0 +1,181 @@
+use crate::{error::ReaclibError as RError, Format, Library, LibraryMetadata};
+use sha2::{Digest, Sha256};
+use std::{fmt::Write, io::Read};
+
+/// A reaclib snapshot that can be downloaded and parsed into a [`Library`].
+///
+/// A snapshot has a name, which should identify a version of the data, the URL that it is
+/// downloaded from, and the format that it is in.
+/// It is the download that [`SnapshotCache`][crate::SnapshotCache] keeps local copies of.
+///
+/// Snapshots aren't looked up by name: there is no built-in list of the snapshots that JINA
+/// REACLIB publishes, and no `latest`, so the URL has to be given, for example by copying a
+/// snapshot's download link from the [website](https://reaclib.jinaweb.org/), or by pointing it
+/// at a mirror.
+///
+/// The expected size and SHA-256 checksum of the data can also be given, so that a download that
+/// was cut short or that doesn't match the pinned snapshot is rejected instead of being parsed.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use reaclib::{Format, Snapshot};
+///
+/// // a download link for the snapshot, from the website or a mirror, and the checksum of the
+/// // data that it was pinned to
+/// let url = std::env::var("REACLIB_SNAPSHOT_URL")?;
+/// let sha256 = std::env::var("REACLIB_SNAPSHOT_SHA256")?;
+/// let snapshot = Snapshot::new("default", url, Format::Reaclib2).with_sha256(&sha256)?;
+/// let library = snapshot.fetch()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Snapshot {
+    name: String,
+    url: String,
+    format: Format,
+    size: Option<u64>,
+    sha256: Option<[u8; 32]>,
+}
+
+impl Snapshot {
+    /// Creates a new `Snapshot` called `name`, which is downloaded from `url` and is in `format`.
+    #[must_use]
+    pub fn new(name: impl Into<String>, url: impl Into<String>, format: Format) -> Self {
+        Self {
+            name: name.into(),
+            url: url.into(),
+            format,
+            size: None,
+            sha256: None,
+        }
+    }
+
+    /// Require the downloaded data to be `size` bytes long.
+    #[must_use]
+    pub const fn with_size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Require the downloaded data to have the SHA-256 checksum `sha256`, written in hexadecimal.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `sha256` isn't 64 hexadecimal digits.
+    pub fn with_sha256(mut self, sha256: &str) -> Result<Self, RError> {
+        let invalid = || RError::InvalidChecksum(sha256.to_string());
+        if sha256.len() != 64 || !sha256.is_ascii() {
+            return Err(invalid());
+        }
+        let mut checksum = [0; 32];
+        for (byte, digits) in checksum.iter_mut().zip(sha256.as_bytes().chunks(2)) {
+            let digits = std::str::from_utf8(digits).map_err(|_| invalid())?;
+            *byte = u8::from_str_radix(digits, 16).map_err(|_| invalid())?;
+        }
+        self.sha256 = Some(checksum);
+        Ok(self)
+    }
+
+    /// The name of the snapshot.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The URL that the snapshot is downloaded from.
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The format that the snapshot is in.
+    #[must_use]
+    pub const fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Check that `data` has the expected size and checksum, if they were given.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the size or the checksum doesn't match.
+    pub fn verify(&self, data: &[u8]) -> Result<(), RError> {
+        let size = data.len() as u64;
+        if let Some(expected) = self.size {
+            if size != expected {
+                return Err(RError::SnapshotSize(expected, size));
+            }
+        }
+        if let Some(expected) = self.sha256 {
+            if Sha256::digest(data)[..] != expected {
+                return Err(RError::SnapshotChecksum);
+            }
+        }
+        Ok(())
+    }
+
+    /// Download the snapshot and check it with [`verify`][Self::verify].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the download fails, or if the data doesn't match the expected size or
+    /// checksum.
+    pub fn download(&self) -> Result<Vec<u8>, RError> {
+        let data = get(&self.url)?;
+        self.verify(&data)?;
+        Ok(data)
+    }
+
+    /// Check `data` with [`verify`][Self::verify] and parse it.
+    ///
+    /// The [metadata][Library::metadata] of the library records the name and URL of the
+    /// snapshot, and the checksum of `data`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the data doesn't match the expected size or checksum, or if there is
+    /// a parsing error.
+    pub fn parse(&self, data: &[u8]) -> Result<Library, RError> {
+        self.verify(data)?;
+        Ok(Library::from_reader(data, self.format)?.with_metadata(self.metadata(data)))
+    }
+
+    /// Download the snapshot, check it, and parse it.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the download fails, if the data doesn't match the expected size or
+    /// checksum, or if there is a parsing error.
+    pub fn fetch(&self) -> Result<Library, RError> {
+        let data = self.download()?;
+        Ok(Library::from_reader(&data[..], self.format)?.with_metadata(self.metadata(&data)))
+    }
+
+    // the metadata of a library parsed from `data`
+    fn metadata(&self, data: &[u8]) -> LibraryMetadata {
+        let sha256 = Sha256::digest(data).iter().fold(String::new(), |mut s, b| {
+            // writing to a string can't fail
+            let _ = write!(s, "{b:02x}");
+            s
+        });
+        LibraryMetadata {
+            name: Some(self.name.clone()),
+            source: Some(self.url.clone()),
+            sha256: Some(sha256),
+            ..LibraryMetadata::default()
+        }
+    }
+}
+
+// the body of the response to a GET request for `url`
+fn get(url: &str) -> Result<Vec<u8>, RError> {
+    let response = ureq::get(url)
+        .call()
+        .map_err(|e| RError::Download(e.to_string()))?;
+    let mut data = Vec::new();
+    response.into_body().into_reader().read_to_end(&mut data)?;
+    Ok(data)
+}
@@ -0,0 +1,178 @@
+use crate::{
+    log_grid, rate::solve_temperature, Library, Nuclide, RateCurve, Reaction, ReactionRate,
+};
+use std::collections::HashMap;
+
+/// The total rates of a reaction and its reverse, for comparing them as a diagnostic of
+/// equilibrium.
+///
+/// It can be obtained from a [`Library`] with [`rate_pair`][Library::rate_pair].
+///
+/// The rates are compared as they are by [`ratio`][Self::ratio], so if the two directions have
+/// different numbers of reactants, the density and abundance factors are not included.
+/// [`flux_ratio`][Self::flux_ratio] includes them, for a given density and composition.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Library};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"4
+///        he4  c12  o16                       nac2n     7.16200e+00
+///  6.965260e+01-1.392540e+00 5.891280e+01-1.482730e+02
+///  9.083240e+00-5.410410e-01 7.035540e+01
+/// 2
+///        o16  he4  c12                       nac2nv   -7.16200e+00
+///  9.431310e+01-8.450300e+01 5.891280e+01-1.482730e+02
+///  9.083240e+00-5.410410e-01 7.185540e+01");
+/// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+///
+/// let capture = library.reactions().find(|r| r.reactants().len() == 2).unwrap();
+/// let pair = library.rate_pair(capture).unwrap();
+/// // photodisintegration is negligible at low temperatures, but dominates at high temperatures
+/// assert!(pair.ratio(0.5) < 1.0);
+/// assert!(pair.ratio(5.0) > 1.0);
+/// let t = pair.equality_temperature(0.5, 5.0).unwrap();
+/// assert!((pair.ratio(t) - 1.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct RatePair {
+    /// The total rate of the reaction.
+    pub forward: ReactionRate,
+    /// The total rate of the reverse reaction.
+    pub reverse: ReactionRate,
+}
+
+impl RatePair {
+    /// The ratio of the reverse rate to the forward rate at `temperature`.
+    #[must_use]
+    pub fn ratio(&self, temperature: f64) -> f64 {
+        self.reverse.rate(temperature) / self.forward.rate(temperature)
+    }
+
+    /// Evaluate the [`ratio`][Self::ratio] on a grid of `points` temperatures, logarithmically
+    /// spaced between `min` and `max` (inclusive).
+    ///
+    /// The curve is labelled with the forward reaction, and its `rates` are the ratios.
+    /// See [`log_grid`] for how the grid is made.
+    #[must_use]
+    pub fn ratio_curve(&self, min: f64, max: f64, points: usize) -> RateCurve {
+        let temperatures = log_grid(min, max, points);
+        let rates = temperatures.iter().map(|&t| self.ratio(t)).collect();
+        RateCurve {
+            reaction: self.forward.reaction.clone(),
+            temperatures,
+            rates,
+        }
+    }
+
+    /// Find the temperature between `min` and `max` at which the forward and reverse rates are
+    /// equal.
+    ///
+    /// Returns `None` if the rates aren't equal at exactly one temperature in the range, or if
+    /// either of them can't be evaluated.
+    /// See [`Set::temperature_for_rate`][crate::Set::temperature_for_rate] for details.
+    #[must_use]
+    pub fn equality_temperature(&self, min: f64, max: f64) -> Option<f64> {
+        solve_temperature(|t| self.ratio(t), 1.0, min, max)
+    }
+
+    /// The ratio of the reverse flux to the forward flux at `temperature`, for a composition at
+    /// `density` (in g/cm³) with the molar abundances `abundances`.
+    ///
+    /// The fluxes are the rates of change of abundance, as given by [`ReactionRate::flux`], so
+    /// they include the density, abundance, and symmetry factors that [`ratio`][Self::ratio]
+    /// leaves out.
+    #[must_use]
+    pub fn flux_ratio(
+        &self,
+        density: f64,
+        temperature: f64,
+        abundances: &HashMap<Nuclide, f64>,
+    ) -> f64 {
+        self.reverse.flux(density, temperature, abundances)
+            / self.forward.flux(density, temperature, abundances)
+    }
+
+    /// The forward flux minus the reverse flux at `temperature`, for a composition at `density`
+    /// (in g/cm³) with the molar abundances `abundances`.
+    ///
+    /// This is positive if the forward reaction wins, and 0 in equilibrium.
+    /// See [`ReactionRate::flux`] for how the fluxes are calculated.
+    #[must_use]
+    pub fn net_flux(
+        &self,
+        density: f64,
+        temperature: f64,
+        abundances: &HashMap<Nuclide, f64>,
+    ) -> f64 {
+        self.forward.flux(density, temperature, abundances)
+            - self.reverse.flux(density, temperature, abundances)
+    }
+
+    /// Find the temperature between `min` and `max` at which the forward and reverse fluxes
+    /// balance, for a composition at `density` (in g/cm³) with the molar abundances
+    /// `abundances`.
+    ///
+    /// For a capture and its photodisintegration, this is the temperature above which the
+    /// product is destroyed as fast as it is made, so that the two are in equilibrium, which is
+    /// usually found with equal abundances of the target and the product.
+    /// See [`flux_ratio`][Self::flux_ratio] for how the fluxes are calculated.
+    ///
+    /// Returns `None` if the fluxes don't balance at exactly one temperature in the range, or if
+    /// either of them can't be evaluated (e.g. if an abundance is missing, so a flux is 0).
+    ///
+    /// ```
+    /// use reaclib::{Format, Library, Nuclide};
+    /// use std::{collections::HashMap, io::Cursor};
+    ///
+    /// let reader = Cursor::new(r"4
+    ///        he4  c12  o16                       nac2n     7.16200e+00
+    ///  6.965260e+01-1.392540e+00 5.891280e+01-1.482730e+02
+    ///  9.083240e+00-5.410410e-01 7.035540e+01
+    /// 2
+    ///        o16  he4  c12                       nac2nv   -7.16200e+00
+    ///  9.431310e+01-8.450300e+01 5.891280e+01-1.482730e+02
+    ///  9.083240e+00-5.410410e-01 7.185540e+01");
+    /// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+    /// let capture = "c12(a,g)o16".parse().unwrap();
+    /// let pair = library.rate_pair(&capture).unwrap();
+    ///
+    /// // equal amounts of carbon and oxygen in a helium-rich environment
+    /// let nuclide = |s: &str| s.parse::<Nuclide>().unwrap();
+    /// let abundances = HashMap::from([
+    ///     (nuclide("he4"), 0.2),
+    ///     (nuclide("c12"), 0.001),
+    ///     (nuclide("o16"), 0.001),
+    /// ]);
+    /// let t = pair.equilibrium_temperature(1e4, &abundances, 0.1, 10.0).unwrap();
+    /// assert!((pair.flux_ratio(1e4, t, &abundances) - 1.0).abs() < 1e-9);
+    ///
+    /// // at a higher density, capture is faster, so it takes a higher temperature to balance it
+    /// assert!(pair.equilibrium_temperature(1e6, &abundances, 0.1, 10.0).unwrap() > t);
+    /// ```
+    #[must_use]
+    pub fn equilibrium_temperature(
+        &self,
+        density: f64,
+        abundances: &HashMap<Nuclide, f64>,
+        min: f64,
+        max: f64,
+    ) -> Option<f64> {
+        solve_temperature(|t| self.flux_ratio(density, t, abundances), 1.0, min, max)
+    }
+}
+
+impl Library {
+    /// The total rates of `reaction` and its reverse.
+    ///
+    /// Returns `None` if either of the reactions isn't in the library.
+    #[must_use]
+    pub fn rate_pair(&self, reaction: &Reaction) -> Option<RatePair> {
+        Some(RatePair {
+            forward: self.reaction_rate(reaction)?,
+            reverse: self.reaction_rate(&reaction.reversed())?,
+        })
+    }
+}
@@ -1,17 +1,81 @@
+use crate::{Reaction, Span};
 use std::{
     convert::Infallible,
-    io,
+    error::Error as StdError,
+    fmt, io,
     num::{ParseFloatError, ParseIntError},
+    ops::Deref,
+    sync::Arc,
 };
 use thiserror::Error;
 
 /// A reading or parsing error.
+///
+/// The errors are grouped by what went wrong: reading the source ([`Io`][Self::Io]), parsing what
+/// was read ([`Parse`][Self::Parse] and [`Invalid`][Self::Invalid], with a [`ParseError`]), or
+/// using a set that was read ([`Set`][Self::Set], with a [`SetError`]).
+/// The rest are for the optional storage and download features.
 #[derive(Error, Debug, Clone, Eq, PartialEq)]
 #[non_exhaustive]
 #[allow(clippy::module_name_repetitions)]
 pub enum ReaclibError {
-    #[error("read error")]
-    Io(io::ErrorKind),
+    #[error("read error: {0}")]
+    Io(#[source] IoError),
+    #[error("line {line}, {field}: {kind}")]
+    Parse {
+        /// The line that the field is on.
+        line: usize,
+        /// The field that was being parsed.
+        field: Field,
+        /// The error.
+        #[source]
+        kind: ParseError,
+    },
+    /// A parsing error that isn't in a particular field, such as a set that is missing a line, or
+    /// a nuclide name that was parsed on its own.
+    #[error(transparent)]
+    Invalid(#[from] ParseError),
+    #[error(transparent)]
+    Set(#[from] SetError),
+    #[error("{}: {source}", location(*span, reaction.as_deref()))]
+    InSet {
+        /// The lines of the set.
+        span: Span,
+        /// The reaction of the set, if its nuclides could be read.
+        reaction: Option<Box<Reaction>>,
+        /// The error.
+        source: Box<ReaclibError>,
+    },
+    #[error("invalid cache")]
+    InvalidCache,
+    #[error("unsupported cache version: {0}")]
+    CacheVersion(u32),
+    #[error("invalid packed library")]
+    InvalidPacked,
+    #[error("unsupported packed library version: {0}")]
+    PackedVersion(u32),
+    #[cfg(feature = "sqlite")]
+    #[error("sqlite error: {0}")]
+    Sqlite(String),
+    #[cfg(feature = "download")]
+    #[error("download error: {0}")]
+    Download(String),
+    #[cfg(feature = "download")]
+    #[error("invalid checksum: {0}")]
+    InvalidChecksum(String),
+    #[cfg(feature = "download")]
+    #[error("snapshot size is {1} bytes, expected {0}")]
+    SnapshotSize(u64, u64),
+    #[cfg(feature = "download")]
+    #[error("snapshot checksum doesn't match")]
+    SnapshotChecksum,
+}
+
+/// What went wrong while parsing, in a [`ReaclibError::Parse`] or [`ReaclibError::Invalid`].
+#[derive(Error, Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+#[allow(clippy::module_name_repetitions)]
+pub enum ParseError {
     #[error("int parsing error")]
     ParseInt(#[from] ParseIntError),
     #[error("float parsing error")]
@@ -28,17 +92,260 @@ pub enum ReaclibError {
     TooFewLines,
     #[error("string indexing error")]
     StrIndex,
+    #[error("unknown nuclide: {0}")]
+    UnknownNuclide(String),
+    #[error("nuclide name too long: {0}")]
+    NuclideTooLong(String),
+    #[error("malformed nuclide: {0}")]
+    MalformedNuclide(String),
+    #[error("invalid reaction: {0}")]
+    InvalidReaction(String),
+    #[error("invalid rate table: {0}")]
+    InvalidTable(String),
+}
+
+impl From<reaclib_core::Error> for ParseError {
+    fn from(e: reaclib_core::Error) -> Self {
+        match e {
+            reaclib_core::Error::ParseInt(e) => Self::ParseInt(e),
+            reaclib_core::Error::ParseFloat(e) => Self::ParseFloat(e),
+            reaclib_core::Error::UnknownResonance(s) => Self::UnknownResonance(s),
+            reaclib_core::Error::TooShortLine => Self::TooShortLine,
+            reaclib_core::Error::StrIndex => Self::StrIndex,
+        }
+    }
+}
+
+/// What went wrong while using a set, in a [`ReaclibError::Set`].
+#[derive(Error, Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum SetError {
+    #[error("no chapter with {0} reactants and {1} products")]
+    NoMatchingChapter(usize, usize),
+    #[error("not a decay")]
+    NotDecay,
+    #[error("rate depends on temperature")]
+    TemperatureDependent,
+    #[error("temperature outside of the validity range")]
+    TemperatureOutOfRange,
+    #[error("validity range is empty")]
+    InvalidValidityRange,
+    #[error("temperature is not positive")]
+    NonPositiveTemperature,
+    #[error("rate is not finite")]
+    NonFiniteRate,
+}
+
+impl ReaclibError {
+    /// The error underneath any location that it has been wrapped in with
+    /// [`InSet`][Self::InSet].
+    #[must_use]
+    pub fn innermost(&self) -> &Self {
+        match self {
+            Self::InSet { source, .. } => source.innermost(),
+            e => e,
+        }
+    }
+
+    /// What went wrong, if this is a parsing error, no matter where it was.
+    ///
+    /// This is useful for matching on the kind of parsing error.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter, ParseError};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"1
+    ///          n    p                            wc12x     7.82300e-01
+    /// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00");
+    /// let error = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap_err();
+    /// assert_eq!(error.to_string(), "line 2, resonance flag: unknown resonance: x");
+    /// assert!(matches!(error.parse_error(), Some(ParseError::UnknownResonance(_))));
+    /// ```
+    #[must_use]
+    pub fn parse_error(&self) -> Option<&ParseError> {
+        match self.innermost() {
+            Self::Parse { kind, .. } | Self::Invalid(kind) => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// Whether this is an error from reading the source, rather than from parsing what was
+    /// read.
+    #[must_use]
+    pub fn is_io(&self) -> bool {
+        matches!(self.innermost(), Self::Io(_))
+    }
+
+    /// The line and field of a parsing error, if it has them.
+    #[must_use]
+    pub fn field(&self) -> Option<(usize, Field)> {
+        match self.innermost() {
+            Self::Parse { line, field, .. } => Some((*line, *field)),
+            _ => None,
+        }
+    }
+}
+
+/// The part of a set that was being parsed when a [`ReaclibError::Parse`] happened.
+///
+/// Indices are from 0, in the order that the fields appear in the set, and are written from 1
+/// by [`Display`][fmt::Display], except for parameters, which are written as `a0` to `a6` like
+/// in the reaclib documentation.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum Field {
+    /// The chapter number.
+    Chapter,
+    /// A reactant, by its index.
+    Reactant(usize),
+    /// A product, by its index.
+    Product(usize),
+    /// The label.
+    Label,
+    /// The resonance flag.
+    Resonance,
+    /// The reverse flag.
+    Reverse,
+    /// The Q-value.
+    QValue,
+    /// A rate parameter, by its index.
+    Param(usize),
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Chapter => write!(f, "chapter"),
+            Self::Reactant(i) => write!(f, "reactant {}", i + 1),
+            Self::Product(i) => write!(f, "product {}", i + 1),
+            Self::Label => write!(f, "label"),
+            Self::Resonance => write!(f, "resonance flag"),
+            Self::Reverse => write!(f, "reverse flag"),
+            Self::QValue => write!(f, "Q-value"),
+            Self::Param(i) => write!(f, "parameter a{i}"),
+        }
+    }
+}
+
+fn location(span: Span, reaction: Option<&Reaction>) -> String {
+    match reaction {
+        Some(reaction) => format!("{span} ({reaction})"),
+        None => span.to_string(),
+    }
+}
+
+/// An [`io::Error`], shared so that a [`ReaclibError`] can be cloned without losing the
+/// underlying failure.
+///
+/// It dereferences to the `io::Error`, so the OS message, [`kind`][io::Error::kind], and
+/// [`source`][StdError::source] are all available.
+/// Two `IoError`s are equal if they have the same kind.
+///
+/// ```
+/// use reaclib::ReaclibError;
+/// use std::io;
+///
+/// let error = ReaclibError::from(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+/// let ReaclibError::Io(io_error) = &error else {
+///     panic!("this is an io error");
+/// };
+/// assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+/// assert_eq!(error.to_string(), "read error: no such file");
+/// ```
+#[derive(Clone, Debug)]
+pub struct IoError(Arc<io::Error>);
+
+impl IoError {
+    /// The underlying `io::Error`.
+    #[must_use]
+    pub fn get_ref(&self) -> &io::Error {
+        &self.0
+    }
+}
+
+impl Deref for IoError {
+    type Target = io::Error;
+
+    fn deref(&self) -> &io::Error {
+        &self.0
+    }
+}
+
+impl PartialEq for IoError {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.kind() == other.0.kind()
+    }
+}
+
+impl Eq for IoError {}
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl StdError for IoError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.source()
+    }
+}
+
+impl From<io::Error> for IoError {
+    fn from(e: io::Error) -> Self {
+        Self(Arc::new(e))
+    }
+}
+
+impl From<io::ErrorKind> for IoError {
+    fn from(k: io::ErrorKind) -> Self {
+        io::Error::from(k).into()
+    }
 }
 
 impl From<io::Error> for ReaclibError {
     fn from(e: io::Error) -> Self {
-        Self::Io(e.kind())
+        Self::Io(e.into())
     }
 }
 
 impl From<io::ErrorKind> for ReaclibError {
     fn from(k: io::ErrorKind) -> Self {
-        Self::Io(k)
+        Self::Io(k.into())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for ReaclibError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Sqlite(e.to_string())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::types::FromSqlError> for ReaclibError {
+    fn from(e: rusqlite::types::FromSqlError) -> Self {
+        Self::Sqlite(e.to_string())
+    }
+}
+
+impl From<ParseIntError> for ReaclibError {
+    fn from(e: ParseIntError) -> Self {
+        Self::Invalid(e.into())
+    }
+}
+
+impl From<ParseFloatError> for ReaclibError {
+    fn from(e: ParseFloatError) -> Self {
+        Self::Invalid(e.into())
+    }
+}
+
+impl From<Infallible> for ParseError {
+    fn from(_: Infallible) -> Self {
+        unreachable!()
     }
 }
 
@@ -63,4 +370,13 @@ mod tests {
         const fn assert_sync<T: Sync>() {}
         assert_sync::<ReaclibError>();
     }
+
+    #[test]
+    fn io_source() {
+        let error = ReaclibError::from(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+        let source = error.source().unwrap();
+        let io_error = source.downcast_ref::<IoError>().unwrap();
+        assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+        assert_eq!(source.to_string(), "no such file");
+    }
 }
@@ -0,0 +1,204 @@
+use crate::{
+    error::ReaclibError as RError, parse_float, Header, ParseError, RateBasis, Set, TabulatedRate,
+};
+use std::io::BufRead;
+
+/// Fit the 7 reaclib parameters to `rates` at `temperatures` (in GK), by least squares in the
+/// natural logarithm of the rate.
+///
+/// Since the logarithm of a reaclib rate is linear in its parameters (see [`RateBasis`]), this
+/// finds the best fit directly, without iterating or an initial guess.
+/// This is useful for converting a table of rates, such as one from a statistical-model code
+/// (see [`TabulatedRate::from_reader`]), into a [`Set`].
+/// Rates that aren't positive (or are smaller than [`f64::MIN_POSITIVE`]) are left out, since
+/// their logarithm can't be fit.
+///
+/// Returns `None` if the lengths are different, or if there aren't enough distinct positive
+/// temperatures with positive rates to determine the parameters.
+///
+/// ```
+/// use reaclib::{fit_params, log_grid, RateBasis};
+///
+/// let params = [17.1482, 0.0, -13.692, -0.230881, 4.44362, -3.15898, -0.666667];
+/// let rate = |t: f64| {
+///     let basis = RateBasis::new(t);
+///     basis.values().iter().zip(&params).map(|(b, p)| b * p).sum::<f64>().exp()
+/// };
+/// let temperatures = log_grid(0.1, 10.0, 24);
+/// let rates = temperatures.iter().map(|&t| rate(t)).collect::<Vec<_>>();
+///
+/// let fit = fit_params(&temperatures, &rates).unwrap();
+/// for (a, b) in fit.iter().zip(&params) {
+///     assert!((a - b).abs() < 1e-6);
+/// }
+/// ```
+#[must_use]
+pub fn fit_params(temperatures: &[f64], rates: &[f64]) -> Option<[f64; 7]> {
+    if temperatures.len() != rates.len() {
+        return None;
+    }
+    let (mut rows, mut ln_rates): (Vec<_>, Vec<_>) = temperatures
+        .iter()
+        .zip(rates)
+        .filter(|&(&t, &r)| t > 0.0 && r >= f64::MIN_POSITIVE)
+        .map(|(&t, &r)| (*RateBasis::new(t).values(), r.ln()))
+        .unzip();
+    least_squares(&mut rows, &mut ln_rates)
+}
+
+// solve the least squares problem `rows · x ≈ values` with Householder QR, which is better
+// conditioned than the normal equations
+// `rows` and `values` are overwritten
+fn least_squares(rows: &mut [[f64; 7]], values: &mut [f64]) -> Option<[f64; 7]> {
+    if rows.len() < 7 {
+        return None;
+    }
+
+    for k in 0..7 {
+        let norm = rows[k..].iter().map(|r| r[k] * r[k]).sum::<f64>().sqrt();
+        // the scale of the column, so that a column of rounding errors is treated as singular
+        let scale = rows.iter().map(|r| r[k].abs()).fold(0.0, f64::max);
+        if norm <= scale * 1e-12 {
+            return None;
+        }
+        // reflect the column onto `alpha` times the `k`th unit vector
+        let alpha = if rows[k][k] > 0.0 { -norm } else { norm };
+        let mut v = rows[k..].iter().map(|r| r[k]).collect::<Vec<_>>();
+        v[0] -= alpha;
+        let v_norm_sq = v.iter().map(|x| x * x).sum::<f64>();
+
+        // the factor to subtract `v` with, for the column given by `value`
+        let factor = |value: &dyn Fn(usize) -> f64| {
+            2.0 * v
+                .iter()
+                .enumerate()
+                .map(|(i, vi)| vi * value(k + i))
+                .sum::<f64>()
+                / v_norm_sq
+        };
+        for j in k..7 {
+            let factor = factor(&|i| rows[i][j]);
+            for (row, vi) in rows[k..].iter_mut().zip(&v) {
+                row[j] -= factor * vi;
+            }
+        }
+        let factor = factor(&|i| values[i]);
+        for (value, vi) in values[k..].iter_mut().zip(&v) {
+            *value -= factor * vi;
+        }
+    }
+
+    // back substitution with the upper triangle
+    let mut x = [0.0; 7];
+    for k in (0..7).rev() {
+        let sum = (k + 1..7).map(|j| rows[k][j] * x[j]).sum::<f64>();
+        x[k] = (values[k] - sum) / rows[k][k];
+    }
+    x.iter().all(|x| x.is_finite()).then_some(x)
+}
+
+impl TabulatedRate {
+    /// Reads a table of rates, such as the output of a statistical-model code like NON-SMOKER or
+    /// TALYS, with the rates from `column`, counting from 0 after the temperature.
+    ///
+    /// Each line has whitespace-separated columns, starting with the temperature (T9, in GK)
+    /// and followed by one or more columns of rates, e.g. for different reactions of the same
+    /// target.
+    /// Lines that don't start with a number, such as comments or a header of column names, are
+    /// skipped.
+    /// The temperatures must be strictly increasing.
+    /// Together with [`fit`][Self::fit] and [`Set::from_header`], this converts a table from
+    /// theory into a reaclib set.
+    ///
+    /// ```
+    /// use reaclib::{Header, Resonance, Set, TabulatedRate};
+    /// use arrayvec::ArrayString;
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"# T9   (n,g)       (n,p)
+    ///   0.1   1.000e+04   1.000e-03
+    ///   0.3   2.000e+04   1.000e-01
+    ///   0.5   3.000e+04   1.000e+00
+    ///   1.0   4.000e+04   1.000e+01
+    ///   2.0   5.000e+04   1.000e+02
+    ///   5.0   6.000e+04   1.000e+03
+    ///  10.0   7.000e+04   1.000e+04
+    /// ");
+    /// let table = TabulatedRate::from_reader(reader, 0).unwrap();
+    /// let params = table.fit().unwrap();
+    ///
+    /// let header = Header {
+    ///     reactants: ["n".parse().unwrap(), "fe56".parse().unwrap()].into_iter().collect(),
+    ///     products: ["fe57".parse().unwrap()].into_iter().collect(),
+    ///     label: ArrayString::from("ths8").unwrap(),
+    ///     resonance: Resonance::NonResonant,
+    ///     reverse: false,
+    ///     q_value: 7.646,
+    /// };
+    /// let set = Set::from_header(header, params);
+    /// assert!((set.rate(1.0) / 4e4 - 1.0).abs() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error, a number can't be parsed, a line doesn't have
+    /// `column`, or there are no lines, or the temperatures aren't positive and strictly
+    /// increasing.
+    pub fn from_reader<R: BufRead>(reader: R, column: usize) -> Result<Self, RError> {
+        let (mut temperatures, mut rates) = (Vec::new(), Vec::new());
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let mut fields = line.split_whitespace();
+            let Some(temperature) = fields.next().and_then(|t| parse_float(t).ok()) else {
+                continue;
+            };
+            let rate = fields.nth(column).ok_or_else(|| {
+                ParseError::InvalidTable(format!("line {} has too few columns", i + 1))
+            })?;
+            temperatures.push(temperature);
+            rates.push(parse_float(rate)?);
+        }
+        Self::new(&temperatures, &rates).ok_or_else(|| {
+            ParseError::InvalidTable("the temperatures aren't strictly increasing".to_string())
+                .into()
+        })
+    }
+
+    /// Fit the 7 reaclib parameters to the tabulated points.
+    ///
+    /// See [`fit_params`].
+    #[must_use]
+    pub fn fit(&self) -> Option<[f64; 7]> {
+        fit_params(
+            &self.temperatures().collect::<Vec<_>>(),
+            &self.rates().collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl Set {
+    /// Creates a `Set` with everything from `header`, and `params`, such as from [`fit_params`].
+    #[must_use]
+    pub fn from_header(header: Header, params: [f64; 7]) -> Self {
+        let Header {
+            reactants,
+            products,
+            label,
+            resonance,
+            reverse,
+            q_value,
+        } = header;
+        Self {
+            reactants,
+            products,
+            label,
+            resonance,
+            reverse,
+            q_value,
+            params,
+            raw_params: None,
+            raw_lines: None,
+            validity: None,
+        }
+    }
+}
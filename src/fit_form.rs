@@ -0,0 +1,298 @@
+use crate::{
+    error::ReaclibError as RError, field_error, parse_float, range_err, Chapter, Field, Format,
+    Header, LineSource, ParseError, RateBasis,
+};
+
+// the width of a parameter field in the reaclib format
+const FIELD_WIDTH: usize = 13;
+
+/// The functional form of a rate fit, and how its parameters are laid out in a file.
+///
+/// Reaclib rates are fit with 7 parameters on 2 lines (see [`ReaclibFit`]), but some groups
+/// distribute "reaclib-like" files with more parameters, e.g. on an extra line.
+/// Implementing `FitForm` for such a dialect allows it to be read with [`FitIter`] and evaluated,
+/// while the first line of each set is read the same as in reaclib.
+///
+/// Only [`param_lines`][Self::param_lines] and [`ln_rate`][Self::ln_rate] need to be implemented.
+/// By default, [`parse_params`][Self::parse_params] reads every 13-column field of each
+/// parameter line, which is how reaclib lays out its parameters.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{FitForm, Format, Iter, RateBasis};
+/// use std::io::Cursor;
+///
+/// // the reaclib form, with an extra T² term on a third line
+/// struct Quadratic;
+///
+/// impl FitForm for Quadratic {
+///     fn param_lines(&self) -> usize {
+///         3
+///     }
+///
+///     fn ln_rate(&self, params: &[f64], temperature: f64) -> f64 {
+///         let basis = RateBasis::new(temperature);
+///         let reaclib = basis.values().iter().zip(params).map(|(b, p)| b * p).sum::<f64>();
+///         reaclib + params[7] * temperature.powi(2)
+///     }
+/// }
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00
+///  1.000000e-02");
+/// let mut iter = Iter::builder(reader).format(Format::Reaclib2).fit_form(Quadratic);
+/// let set = iter.next().unwrap().unwrap();
+/// assert_eq!(set.params.len(), 8);
+/// assert_eq!(set.ln_rate(iter.form(), 2.0), -6.781610 + 0.01 * 4.0);
+/// ```
+pub trait FitForm {
+    /// The number of lines of parameters after the first line of each set.
+    fn param_lines(&self) -> usize;
+
+    /// Read the parameters of a set from its parameter lines, of which there are
+    /// [`param_lines`][Self::param_lines].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the parameters can't be read.
+    fn parse_params(&self, lines: &[String]) -> Result<Vec<f64>, RError> {
+        let mut params = Vec::new();
+        for line in lines {
+            for start in (0..line.len()).step_by(FIELD_WIDTH) {
+                let field = line
+                    .get(start..line.len().min(start + FIELD_WIDTH))
+                    .ok_or(ParseError::StrIndex)?
+                    .trim();
+                if !field.is_empty() {
+                    params.push(parse_float(field)?);
+                }
+            }
+        }
+        Ok(params)
+    }
+
+    /// Calculate the natural logarithm of the rate with `params` at `temperature` (in GK).
+    fn ln_rate(&self, params: &[f64], temperature: f64) -> f64;
+
+    /// Calculate the rate with `params` at `temperature` (in GK).
+    fn rate(&self, params: &[f64], temperature: f64) -> f64 {
+        self.ln_rate(params, temperature).exp()
+    }
+}
+
+impl<T: FitForm + ?Sized> FitForm for &T {
+    fn param_lines(&self) -> usize {
+        (**self).param_lines()
+    }
+
+    fn parse_params(&self, lines: &[String]) -> Result<Vec<f64>, RError> {
+        (**self).parse_params(lines)
+    }
+
+    fn ln_rate(&self, params: &[f64], temperature: f64) -> f64 {
+        (**self).ln_rate(params, temperature)
+    }
+}
+
+impl<T: FitForm + ?Sized> FitForm for Box<T> {
+    fn param_lines(&self) -> usize {
+        (**self).param_lines()
+    }
+
+    fn parse_params(&self, lines: &[String]) -> Result<Vec<f64>, RError> {
+        (**self).parse_params(lines)
+    }
+
+    fn ln_rate(&self, params: &[f64], temperature: f64) -> f64 {
+        (**self).ln_rate(params, temperature)
+    }
+}
+
+/// The standard reaclib fit, with 7 parameters: 4 on the first line and 3 on the second.
+///
+/// This reads and evaluates sets the same as [`Set`][crate::Set], and [`ln_rate`][Self::ln_rate]
+/// is NaN if there aren't exactly 7 parameters.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct ReaclibFit;
+
+impl FitForm for ReaclibFit {
+    fn param_lines(&self) -> usize {
+        2
+    }
+
+    fn parse_params(&self, lines: &[String]) -> Result<Vec<f64>, RError> {
+        let [line_1, line_2] = lines else {
+            return Err(ParseError::TooFewLines.into());
+        };
+        [(line_1, 4), (line_2, 3)]
+            .into_iter()
+            .flat_map(|(line, n)| (0..n).map(move |i| (line, i * FIELD_WIDTH)))
+            .map(|(line, start)| parse_float(range_err(line, start..start + FIELD_WIDTH)?))
+            .collect::<Result<_, _>>()
+            .map_err(RError::from)
+    }
+
+    fn ln_rate(&self, params: &[f64], temperature: f64) -> f64 {
+        params
+            .try_into()
+            .map_or(f64::NAN, |params| RateBasis::new(temperature).dot(params))
+    }
+}
+
+/// A set read by [`FitIter`], with the parameters of its [`FitForm`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct FitSet {
+    /// Everything from the first line of the set.
+    pub header: Header,
+    /// The parameters, as read by [`FitForm::parse_params`].
+    pub params: Vec<f64>,
+}
+
+impl FitSet {
+    /// Calculate the natural logarithm of the rate at `temperature` (in GK) with `form`, which
+    /// should be the one that the set was read with.
+    #[must_use]
+    pub fn ln_rate(&self, form: &impl FitForm, temperature: f64) -> f64 {
+        form.ln_rate(&self.params, temperature)
+    }
+
+    /// Calculate the rate at `temperature` (in GK) with `form`, which should be the one that the
+    /// set was read with.
+    #[must_use]
+    pub fn rate(&self, form: &impl FitForm, temperature: f64) -> f64 {
+        form.rate(&self.params, temperature)
+    }
+}
+
+/// An iterator that reads sets with the parameter lines of a [`FitForm`], created by
+/// [`IterBuilder::fit_form`][crate::IterBuilder::fit_form].
+///
+/// Chapter headers and the first line of each set are read the same as by [`Iter`][crate::Iter],
+/// and each set is followed by [`FitForm::param_lines`] lines of parameters.
+///
+/// # Errors
+///
+/// If a set fails to parse or there is a reading error, [`next`][Self::next] will return `Some(Err)`.
+/// Calling `next` again may return `Some`, but the validity of the data is not guaranteed.
+#[derive(Debug)]
+pub struct FitIter<R: LineSource, F> {
+    reader: R,
+    form: F,
+    format: Format,
+    species_width: usize,
+    extended_chapters: Vec<Chapter>,
+    chapter: Option<Chapter>,
+    // the first line of the current set, and its parameter lines
+    header: String,
+    lines: Vec<String>,
+    // the number of lines that have been read
+    line: usize,
+}
+
+impl<R: LineSource, F: FitForm> FitIter<R, F> {
+    pub(crate) fn new(
+        reader: R,
+        form: F,
+        format: Format,
+        species_width: usize,
+        extended_chapters: Vec<Chapter>,
+    ) -> Self {
+        let lines = vec![String::new(); form.param_lines()];
+        Self {
+            reader,
+            form,
+            format,
+            species_width,
+            extended_chapters,
+            chapter: None,
+            header: String::new(),
+            lines,
+            line: 0,
+        }
+    }
+
+    /// The fit form that the sets are read with.
+    pub const fn form(&self) -> &F {
+        &self.form
+    }
+
+    // Read the next line into `buf`, or return `Err(None)` at the end of the input.
+    fn read_line(reader: &mut R, line: &mut usize, buf: &mut String) -> Result<(), Option<RError>> {
+        let r = reader.next_line(buf).ok_or(None)?;
+        *line += 1;
+        r.map_err(Some)
+    }
+
+    // Read the chapter header (if any) and the first line of the next set into `self.header`,
+    // and find the chapter that the set belongs to.
+    fn next_header(&mut self) -> Result<Chapter, Option<RError>> {
+        loop {
+            Self::read_line(&mut self.reader, &mut self.line, &mut self.header)?;
+            let number = self.header.trim().parse::<u8>();
+            match (self.format, number) {
+                (Format::Reaclib2, number) => {
+                    let chapter = number
+                        .map_err(ParseError::from)
+                        .and_then(|n| Chapter::from_number(n, &self.extended_chapters))
+                        .map_err(|e| field_error(self.line, Field::Chapter, e))?;
+                    self.chapter = Some(chapter);
+                    Self::read_line(&mut self.reader, &mut self.line, &mut self.header)
+                        .map_err(|e| e.or(Some(ParseError::TooFewLines.into())))?;
+                    return Ok(chapter);
+                }
+                // a chapter header is a number and two blank lines
+                (Format::Reaclib1, Ok(number)) => {
+                    let chapter = Chapter::from_number(number, &self.extended_chapters)
+                        .map_err(|e| field_error(self.line, Field::Chapter, e))?;
+                    self.chapter = Some(chapter);
+                    for _ in 0..2 {
+                        Self::read_line(&mut self.reader, &mut self.line, &mut self.header)
+                            .map_err(|e| e.or(Some(ParseError::TooFewLines.into())))?;
+                    }
+                }
+                (Format::Reaclib1, Err(_)) => {
+                    return self.chapter.ok_or(Some(ParseError::ChapterUnset.into()));
+                }
+            }
+        }
+    }
+
+    fn next_set(&mut self) -> Result<FitSet, Option<RError>> {
+        let chapter = self.next_header();
+        if let Err(None) = chapter {
+            return Err(None);
+        }
+        let header_line = self.line;
+        // every parameter line is read, even after an error, so that the next set starts in the
+        // same place
+        let mut result = Ok(());
+        for line in &mut self.lines {
+            match Self::read_line(&mut self.reader, &mut self.line, line) {
+                Err(None) => return Err(Some(ParseError::TooFewLines.into())),
+                Err(Some(e)) if result.is_ok() => result = Err(e),
+                _ => {}
+            }
+        }
+        let chapter = chapter?;
+        result?;
+        Ok(FitSet {
+            header: Header::from_line(chapter, &self.header, self.species_width)
+                .map_err(|(field, e)| field_error(header_line, field, e))?,
+            params: self.form.parse_params(&self.lines)?,
+        })
+    }
+}
+
+impl<R: LineSource, F: FitForm> Iterator for FitIter<R, F> {
+    type Item = Result<FitSet, RError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_set() {
+            Ok(set) => Some(Ok(set)),
+            Err(e) => e.map(Err),
+        }
+    }
+}
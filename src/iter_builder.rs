@@ -0,0 +1,316 @@
+use crate::{
+    error::ReaclibError as RError, sized_iter::Layout, Chapter, FitForm, FitIter, Format, Iter,
+    LineSource, SizedIter,
+};
+use std::io::{BufRead, Seek};
+
+/// A builder for an [`Iter`] with parsing options, created by [`Iter::builder`].
+///
+/// Every option has a default, so only the ones that matter need to be set:
+///
+/// * [`format`][Self::format]: [`Format::Reaclib2`]
+/// * [`raw_params`][Self::raw_params]: `false`
+/// * [`raw_lines`][Self::raw_lines]: `false`
+/// * [`lenient`][Self::lenient]: `false`
+/// * [`validate_nuclides`][Self::validate_nuclides]: `false`
+/// * [`with_spans`][Self::with_spans]: `false`
+/// * [`species_width`][Self::species_width]: 5
+/// * [`chapter`][Self::chapter]: only the standard chapters
+/// * [`skip_preamble`][Self::skip_preamble]: `false`
+#[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct IterBuilder<R: LineSource> {
+    reader: R,
+    format: Format,
+    raw_params: bool,
+    raw_lines: bool,
+    lenient: bool,
+    validate_nuclides: bool,
+    spans: bool,
+    species_width: usize,
+    chapters: Vec<Chapter>,
+    skip_preamble: bool,
+}
+
+impl<R: LineSource> IterBuilder<R> {
+    pub(crate) const fn new(reader: R) -> Self {
+        Self {
+            reader,
+            format: Format::Reaclib2,
+            raw_params: false,
+            raw_lines: false,
+            lenient: false,
+            validate_nuclides: false,
+            spans: false,
+            species_width: 5,
+            chapters: Vec::new(),
+            skip_preamble: false,
+        }
+    }
+
+    /// Parse according to the rules of `format`.
+    #[must_use]
+    pub const fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Keep the parameters exactly as they appear in the source in
+    /// [`Set::raw_params`][crate::Set::raw_params], like [`Iter::with_raw_params`].
+    #[must_use]
+    pub const fn raw_params(mut self, raw_params: bool) -> Self {
+        self.raw_params = raw_params;
+        self
+    }
+
+    /// Keep the lines of each set exactly as they appear in the source in
+    /// [`Set::raw_lines`][crate::Set::raw_lines].
+    ///
+    /// With [`lenient`][Self::lenient], the lines are kept as they were before any deviations
+    /// were fixed.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"1
+    ///          n    p                            wc12w     7.82300e-01
+    /// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00");
+    /// let mut iter = Iter::builder(reader).format(Format::Reaclib2).raw_lines(true).build();
+    /// let set = iter.next().unwrap().unwrap();
+    /// let lines = set.raw_lines.unwrap();
+    /// assert_eq!(lines[2], " 0.000000e+00 0.000000e+00 0.000000e+00");
+    /// ```
+    #[must_use]
+    pub const fn raw_lines(mut self, raw_lines: bool) -> Self {
+        self.raw_lines = raw_lines;
+        self
+    }
+
+    /// Accept small deviations from the format, instead of returning an error:
+    ///
+    /// * lines that are too short are read as if they were padded with spaces
+    /// * a blank Q-value or parameter is read as 0
+    /// * an unknown resonance flag is read as [`Resonance::NonResonant`][crate::Resonance::NonResonant]
+    /// * lines starting with `#` or `!` are skipped as comments, as are blank lines between sets
+    ///
+    /// With [`raw_params`][Self::raw_params], a blank parameter is kept as `0`.
+    #[must_use]
+    pub const fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Return an error for a set with a nuclide that isn't
+    /// [well-formed][crate::Nuclide::is_well_formed], instead of the set.
+    ///
+    /// The error is a [`ReaclibError::Parse`][RError::Parse] with the field of the first such
+    /// nuclide, and a [`ParseError::MalformedNuclide`][crate::ParseError::MalformedNuclide].
+    /// See [`Set::malformed_nuclides`][crate::Set::malformed_nuclides].
+    ///
+    /// ```
+    /// use reaclib::{Field, Format, Iter, ParseError};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///        he4   c12 o16                       nac2n     7.16200e+00
+    ///  6.965260e+01-1.392540e+00 5.891280e+01-1.482730e+02
+    ///  9.083240e+00-5.410410e-01 7.035540e+01");
+    /// let mut iter = Iter::builder(reader).format(Format::Reaclib2).validate_nuclides(true).build();
+    /// let error = iter.next().unwrap().unwrap_err();
+    /// assert_eq!(error.field(), Some((2, Field::Reactant(1))));
+    /// assert_eq!(
+    ///     error.parse_error(),
+    ///     Some(&ParseError::MalformedNuclide("c1".to_string()))
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn validate_nuclides(mut self, validate_nuclides: bool) -> Self {
+        self.validate_nuclides = validate_nuclides;
+        self
+    }
+
+    /// Keep track of the lines that each set came from, so that they are available from
+    /// [`Iter::span`].
+    #[must_use]
+    pub const fn with_spans(mut self, spans: bool) -> Self {
+        self.spans = spans;
+        self
+    }
+
+    /// Read the nuclides from fields that are `width` columns wide, instead of the usual 5, as
+    /// some extended libraries do to fit longer names.
+    ///
+    /// The first nuclide still starts at column 5, and everything after the nuclides is in the
+    /// same columns as usual.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `width` is not 5 or 6, since a [`Nuclide`][crate::Nuclide] holds at most 6
+    /// bytes and 6 nuclides with wider fields would overlap the label.
+    #[must_use]
+    pub const fn species_width(mut self, width: usize) -> Self {
+        assert!(width == 5 || width == 6, "the species width must be 5 or 6");
+        self.species_width = width;
+        self
+    }
+
+    /// Read sets in chapter `number` as reactions with `reactants` reactants and `products`
+    /// products, for chapters beyond the standard ones, such as those proposed for reactions with
+    /// more species.
+    ///
+    /// The nuclides are read from consecutive fields, as in the standard chapters, so they have to
+    /// fit before the label at column 43: 7 nuclides at most, or 6 with a
+    /// [`species_width`][Self::species_width] of 6.
+    /// The sets are read with [`Chapter::Extended`], and their reactants and products are
+    /// [`Species`][crate::Species] that can hold any number of nuclides.
+    /// They can't be written in the reaclib formats, since the writer only knows the standard
+    /// chapters.
+    ///
+    /// ```
+    /// use reaclib::{Chapter, Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"12
+    ///          n  c12    n    n    n    p   b9   ex12w     0.00000e+00
+    /// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00");
+    /// let set = Iter::builder(reader)
+    ///     .format(Format::Reaclib2)
+    ///     .chapter(12, 2, 5)
+    ///     .build()
+    ///     .next()
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(set.reactants.len(), 2);
+    /// assert_eq!(set.products.len(), 5);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `number` is a standard chapter, or if there are no reactants or products.
+    #[must_use]
+    pub fn chapter(mut self, number: u8, reactants: u8, products: u8) -> Self {
+        assert!(
+            Chapter::try_from(number).is_err(),
+            "chapter {number} is a standard chapter"
+        );
+        assert!(
+            reactants > 0 && products > 0,
+            "a chapter must have reactants and products"
+        );
+        self.chapters.retain(|c| c.number() != number);
+        self.chapters.push(Chapter::Extended {
+            number,
+            reactants,
+            products,
+        });
+        self
+    }
+
+    /// Skip the lines before the first chapter number, such as the title of a single reaction
+    /// downloaded from the reaclib website, so that these files can be read like a full library.
+    ///
+    /// The skipped lines are available from [`Iter::preamble`].
+    /// If there is no chapter number, every line is skipped and there are no sets.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"c12(p,g)n13
+    ///
+    /// 4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01");
+    /// let mut iter = Iter::builder(reader)
+    ///     .format(Format::Reaclib2)
+    ///     .skip_preamble(true)
+    ///     .build();
+    /// assert_eq!(iter.next().unwrap().unwrap().label.as_str(), "nacr");
+    /// assert_eq!(iter.preamble(), ["c12(p,g)n13", ""]);
+    /// ```
+    #[must_use]
+    pub const fn skip_preamble(mut self, skip_preamble: bool) -> Self {
+        self.skip_preamble = skip_preamble;
+        self
+    }
+
+    /// Creates the `Iter`.
+    pub fn build(self) -> Iter<R> {
+        Iter {
+            raw_params: self.raw_params,
+            raw_lines: self.raw_lines,
+            lenient: self.lenient,
+            validate_nuclides: self.validate_nuclides,
+            spans: self.spans,
+            species_width: self.species_width,
+            extended_chapters: self.chapters,
+            in_preamble: self.skip_preamble,
+            ..Iter::new(self.reader, self.format)
+        }
+    }
+
+    /// Creates a [`FitIter`], which reads sets with the parameter lines of `form`, for files in
+    /// a "reaclib-like" dialect with a different fit.
+    ///
+    /// Only the [`format`][Self::format], [`species_width`][Self::species_width], and
+    /// [`chapter`][Self::chapter] options apply to it.
+    /// See [`FitForm`] for an example.
+    pub fn fit_form<F: FitForm>(self, form: F) -> FitIter<R, F> {
+        FitIter::new(
+            self.reader,
+            form,
+            self.format,
+            self.species_width,
+            self.chapters,
+        )
+    }
+
+    /// Creates a [`SizedIter`], which counts the sets before they are parsed.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error while counting the sets.
+    pub fn build_sized(self) -> Result<SizedIter<R>, RError>
+    where
+        R: BufRead + Seek,
+    {
+        let Self {
+            reader,
+            format,
+            raw_params,
+            raw_lines,
+            lenient,
+            validate_nuclides,
+            spans,
+            species_width,
+            chapters,
+            skip_preamble,
+        } = self;
+        let numbers = chapters.iter().map(Chapter::number).collect::<Vec<_>>();
+        let layout = Layout {
+            format,
+            lenient,
+            chapters: &numbers,
+            skip_preamble,
+        };
+        SizedIter::with_count(reader, &layout, |reader| {
+            Self {
+                reader,
+                format,
+                raw_params,
+                raw_lines,
+                lenient,
+                validate_nuclides,
+                spans,
+                species_width,
+                chapters,
+                skip_preamble,
+            }
+            .build()
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{Nuclide, Set};
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A broad classification of what a set describes, from [`Set::kind`].
+///
+/// This is derived from the number of reactants and products, the species involved, and the
+/// reverse flag.
+/// Light particles are those with a mass number of at most 4 (`n`, `p`, `d`, `t`, `he3`, `he4`).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[non_exhaustive]
+pub enum RateKind {
+    /// A single nuclide decays, e.g. `n13 -> c13`.
+    Decay,
+    /// A single nuclide is broken up by a photon, e.g. `o16 -> he4 c12` with the reverse flag.
+    Photodisintegration,
+    /// A light particle is captured, producing a single nuclide, e.g. `p c12 -> n13`.
+    Capture,
+    /// Two heavier nuclides fuse, e.g. `c12 c12 -> he4 ne20`.
+    Fusion,
+    /// A light particle goes in and a different light particle comes out, e.g.
+    /// `p o18 -> he4 n15`.
+    Transfer,
+    /// Three or more nuclides react, e.g. `he4 he4 he4 -> c12`.
+    MultiBody,
+}
+
+impl RateKind {
+    /// Classify `set`.
+    ///
+    /// This is the same as [`Set::kind`].
+    #[must_use]
+    pub fn of(set: &Set) -> Self {
+        // unknown nuclides are treated as heavy
+        fn is_light(n: &Nuclide) -> bool {
+            n.a().is_some_and(|a| a <= 4)
+        }
+
+        match (set.reactants.as_slice(), set.products.len()) {
+            ([_], _) if set.reverse => Self::Photodisintegration,
+            ([_], _) => Self::Decay,
+            ([a, b], _) if !is_light(a) && !is_light(b) => Self::Fusion,
+            ([_, _], 1) => Self::Capture,
+            ([_, _], _) => Self::Transfer,
+            _ => Self::MultiBody,
+        }
+    }
+}
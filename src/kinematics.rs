@@ -0,0 +1,168 @@
+use crate::{nuclide_z_a, Nuclide};
+use std::f64::consts::PI;
+
+// Boltzmann constant in MeV / GK
+const K_B: f64 = 0.086_173_33;
+// atomic mass unit in MeV / c^2
+const AMU: f64 = 931.494_10;
+// fine structure constant
+const ALPHA: f64 = 1.0 / 137.035_999;
+// reduced Planck constant times the speed of light in MeV fm
+const HBAR_C: f64 = 197.326_980;
+// the nuclear radius parameter in fm, so that a nucleus has a radius of `R_0 A^(1/3)`
+const R_0: f64 = 1.2;
+
+/// The Gamow window of a two-body reaction between charged particles.
+///
+/// This is the range of energies that contributes most to a non-resonant charged-particle
+/// reaction rate at a given temperature.
+/// Energies are in MeV, in the center-of-mass frame.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GamowWindow {
+    /// The energy at which the Gamow peak is located.
+    pub peak: f64,
+    /// The full width of the Gamow peak, at 1/e of its maximum.
+    pub width: f64,
+}
+
+impl GamowWindow {
+    /// Calculate the Gamow window for particles with charges `z1`, `z2` and masses `a1`, `a2`
+    /// (in atomic mass units) at `temperature` (in GK).
+    ///
+    /// ```
+    /// use reaclib::GamowWindow;
+    ///
+    /// // 12C + alpha at 0.2 GK
+    /// let window = GamowWindow::new(2, 4.0, 6, 12.0, 0.2);
+    /// assert!((window.peak - 0.315).abs() < 0.005);
+    /// ```
+    #[must_use]
+    pub fn new(z1: u8, a1: f64, z2: u8, a2: f64, temperature: f64) -> Self {
+        let mu = a1 * a2 / (a1 + a2) * AMU;
+        let kt = K_B * temperature;
+        // the Gamow energy
+        let e_g = 2.0 * mu * (PI * ALPHA * f64::from(z1) * f64::from(z2)).powi(2);
+        let peak = (e_g.sqrt() * kt / 2.0).powf(2.0 / 3.0);
+        let width = 4.0 / f64::sqrt(3.0) * f64::sqrt(peak * kt);
+        Self { peak, width }
+    }
+
+    /// Calculate the Gamow window for the entrance channel made up of `reactants`.
+    ///
+    /// The mass number is used as the mass of each reactant.
+    /// Returns `None` if there aren't exactly two reactants, either of them is neutral, or the
+    /// charge and mass can't be determined from their names.
+    #[must_use]
+    pub fn for_reactants(reactants: &[Nuclide], temperature: f64) -> Option<Self> {
+        let [r1, r2] = reactants else {
+            return None;
+        };
+        let (z1, a1) = nuclide_z_a(r1)?;
+        let (z2, a2) = nuclide_z_a(r2)?;
+        (z1 != 0 && z2 != 0).then(|| Self::new(z1, f64::from(a1), z2, f64::from(a2), temperature))
+    }
+
+    /// The lower end of the window, at `peak - width / 2`.
+    #[must_use]
+    pub fn lower(&self) -> f64 {
+        self.peak - self.width / 2.0
+    }
+
+    /// The upper end of the window, at `peak + width / 2`.
+    #[must_use]
+    pub fn upper(&self) -> f64 {
+        self.peak + self.width / 2.0
+    }
+}
+
+/// The entrance channel of a two-body reaction, for estimating its Coulomb barrier.
+///
+/// Masses are in atomic mass units, and energies are in MeV, in the center-of-mass frame.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::EntranceChannel;
+///
+/// let channel = EntranceChannel::for_reactants(&["p".parse()?, "c12".parse()?]).unwrap();
+/// assert!((channel.coulomb_barrier() - 2.19).abs() < 0.01);
+/// assert!((channel.sommerfeld_parameter(0.1) - 2.87).abs() < 0.01);
+/// # Ok::<(), reaclib::ReaclibError>(())
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EntranceChannel {
+    /// The charge of the first particle.
+    pub z1: u8,
+    /// The mass of the first particle.
+    pub a1: f64,
+    /// The charge of the second particle.
+    pub z2: u8,
+    /// The mass of the second particle.
+    pub a2: f64,
+}
+
+impl EntranceChannel {
+    /// Creates the entrance channel of particles with charges `z1`, `z2` and masses `a1`, `a2`.
+    #[must_use]
+    pub const fn new(z1: u8, a1: f64, z2: u8, a2: f64) -> Self {
+        Self { z1, a1, z2, a2 }
+    }
+
+    /// Creates the entrance channel made up of `reactants`.
+    ///
+    /// The mass number is used as the mass of each reactant.
+    /// Returns `None` if there aren't exactly two reactants, or the charge and mass can't be
+    /// determined from their names.
+    #[must_use]
+    pub fn for_reactants(reactants: &[Nuclide]) -> Option<Self> {
+        let [r1, r2] = reactants else {
+            return None;
+        };
+        let (z1, a1) = nuclide_z_a(r1)?;
+        let (z2, a2) = nuclide_z_a(r2)?;
+        Some(Self::new(z1, f64::from(a1), z2, f64::from(a2)))
+    }
+
+    /// The reduced mass of the particles, in atomic mass units.
+    #[must_use]
+    pub fn reduced_mass(&self) -> f64 {
+        self.a1 * self.a2 / (self.a1 + self.a2)
+    }
+
+    /// The distance between the centers of the particles when they touch, in fm.
+    ///
+    /// Each particle has a radius of `1.2 A^(1/3)` fm.
+    #[must_use]
+    pub fn radius(&self) -> f64 {
+        R_0 * (self.a1.cbrt() + self.a2.cbrt())
+    }
+
+    /// The height of the Coulomb barrier, which is the Coulomb potential at
+    /// [`radius`][Self::radius].
+    ///
+    /// This is 0 if either particle is neutral.
+    #[must_use]
+    pub fn coulomb_barrier(&self) -> f64 {
+        f64::from(self.z1) * f64::from(self.z2) * ALPHA * HBAR_C / self.radius()
+    }
+
+    /// The Sommerfeld parameter `η` at `energy`, which is the ratio of the Coulomb potential to
+    /// the kinetic energy, and sets how strongly tunnelling through the barrier is suppressed.
+    ///
+    /// This is 0 if either particle is neutral.
+    #[must_use]
+    pub fn sommerfeld_parameter(&self, energy: f64) -> f64 {
+        f64::from(self.z1)
+            * f64::from(self.z2)
+            * ALPHA
+            * (self.reduced_mass() * AMU / (2.0 * energy)).sqrt()
+    }
+
+    /// The Gamow window of the channel at `temperature` (in GK).
+    ///
+    /// See [`GamowWindow::new`].
+    #[must_use]
+    pub fn gamow_window(&self, temperature: f64) -> GamowWindow {
+        GamowWindow::new(self.z1, self.a1, self.z2, self.a2, temperature)
+    }
+}
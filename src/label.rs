@@ -0,0 +1,88 @@
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Where the rates with a label come from, from [`LabelInfo::source`] or [`Set::source_kind`].
+///
+/// [`Set::source_kind`]: crate::Set::source_kind
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[non_exhaustive]
+pub enum SourceKind {
+    /// Based directly on measurements, e.g. the experimental half-lives of `wc12`.
+    Experimental,
+    /// Calculated from a model, e.g. the Hauser-Feshbach rates of `ths8`.
+    Theoretical,
+    /// A compilation or evaluation of other work, e.g. `nacr` (NACRE).
+    Evaluation,
+}
+
+/// Information about a reaclib label, from [`label_info`].
+///
+/// Here is a [list of all labels](https://reaclib.jinaweb.org/labels.php).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub struct LabelInfo {
+    /// The label, as it appears in a [`Set`][crate::Set].
+    pub label: &'static str,
+    /// Whether the label is only used for weak rates.
+    pub weak: bool,
+    /// Where the rates with the label come from, if it is known.
+    pub source: Option<SourceKind>,
+}
+
+const fn info(label: &'static str, weak: bool, source: Option<SourceKind>) -> LabelInfo {
+    LabelInfo {
+        label,
+        weak,
+        source,
+    }
+}
+
+const EXPERIMENTAL: Option<SourceKind> = Some(SourceKind::Experimental);
+const THEORETICAL: Option<SourceKind> = Some(SourceKind::Theoretical);
+const EVALUATION: Option<SourceKind> = Some(SourceKind::Evaluation);
+
+// this isn't exhaustive, but covers the labels that are needed for classification
+const LABELS: &[LabelInfo] = &[
+    info("bec", true, None),
+    info("bet+", true, None),
+    info("bet-", true, None),
+    info("bkmo", true, None),
+    info("btyk", true, THEORETICAL),
+    info("cf88", false, EVALUATION),
+    info("ec", true, None),
+    info("ffn", true, THEORETICAL),
+    info("ffnu", true, THEORETICAL),
+    info("il10", false, EVALUATION),
+    info("ka02", false, EVALUATION),
+    info("lmp", true, THEORETICAL),
+    info("mo03", true, THEORETICAL),
+    info("mo92", true, THEORETICAL),
+    info("nac2", false, EVALUATION),
+    info("nacr", false, EVALUATION),
+    info("rath", false, THEORETICAL),
+    info("ths8", false, THEORETICAL),
+    info("wc07", true, EXPERIMENTAL),
+    info("wc12", true, EXPERIMENTAL),
+    info("wc17", true, EXPERIMENTAL),
+];
+
+/// Look up information about a label.
+///
+/// Returns `None` if the label isn't known to this library.
+///
+/// ```
+/// use reaclib::{label_info, SourceKind};
+///
+/// assert!(label_info("wc12").unwrap().weak);
+/// assert_eq!(label_info("ths8").unwrap().source, Some(SourceKind::Theoretical));
+/// assert_eq!(label_info("xxxx"), None);
+/// ```
+#[must_use]
+pub fn label_info(label: &str) -> Option<&'static LabelInfo> {
+    let label = label.trim();
+    LABELS.iter().find(|l| l.label == label)
+}
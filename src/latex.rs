@@ -0,0 +1,174 @@
+use crate::{nuclide::ELEMENTS, Nuclide, Reaction, Set};
+use std::fmt;
+
+/// A LaTeX table of sets, for including in papers.
+///
+/// Each set is a row with its reaction, label, and Q-value (in MeV), and optionally its rate at
+/// some temperatures (see [`with_rates`][Self::with_rates]).
+/// The table is written by [`Display`][fmt::Display], as a `tabular` environment that only needs
+/// standard LaTeX, so it can be put in a `table` with a caption, or written to a file with
+/// `write!` and `\input`.
+///
+/// Reactions are written in math mode, with nuclides as e.g. `{}^{12}\mathrm{C}`, and `n`, `p`,
+/// `d`, `t`, and `α` for the light particles.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, LatexTable, Library};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"4
+///          p  c12  n13                       nacrn     1.94400e+00
+///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+///  4.443620e+00-3.158980e+00-6.666670e-01");
+/// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+///
+/// let table = LatexTable::new(library.sets()).with_rates(&[0.1, 1.0]).to_string();
+/// assert!(table.contains(
+///     r"$\mathrm{p} + {}^{12}\mathrm{C} \rightarrow {}^{13}\mathrm{N}$ & nacr & 1.944 &"
+/// ));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LatexTable<'a> {
+    sets: Vec<&'a Set>,
+    temperatures: Vec<f64>,
+}
+
+impl<'a> LatexTable<'a> {
+    /// Creates a new `LatexTable` with a row for each of `sets`, in order.
+    pub fn new(sets: impl IntoIterator<Item = &'a Set>) -> Self {
+        Self {
+            sets: sets.into_iter().collect(),
+            temperatures: Vec::new(),
+        }
+    }
+
+    /// Add a column with the rate of each set at each of `temperatures` (in GK).
+    #[must_use]
+    pub fn with_rates(mut self, temperatures: &[f64]) -> Self {
+        self.temperatures = temperatures.to_vec();
+        self
+    }
+}
+
+impl fmt::Display for LatexTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "\\begin{{tabular}}{{llr{}}}",
+            "r".repeat(self.temperatures.len())
+        )?;
+        writeln!(f, "\\hline")?;
+        write!(f, "Reaction & Label & $Q$ (MeV)")?;
+        for t in &self.temperatures {
+            write!(f, " & $T_9 = {t}$")?;
+        }
+        writeln!(f, " \\\\")?;
+        writeln!(f, "\\hline")?;
+        for set in &self.sets {
+            write!(
+                f,
+                "${}$ & {} & {:.3}",
+                LatexReaction(&set.reaction()),
+                Escaped(&set.label),
+                set.q_value
+            )?;
+            for &t in &self.temperatures {
+                write!(f, " & {}", Scientific(set.rate(t)))?;
+            }
+            writeln!(f, " \\\\")?;
+        }
+        writeln!(f, "\\hline")?;
+        writeln!(f, "\\end{{tabular}}")
+    }
+}
+
+// a reaction in math mode, e.g. `\mathrm{p} + {}^{12}\mathrm{C} \rightarrow {}^{13}\mathrm{N}`
+struct LatexReaction<'a>(&'a Reaction);
+
+impl fmt::Display for LatexReaction<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let side = |f: &mut fmt::Formatter<'_>, nuclides: &[Nuclide]| {
+            for (i, nuclide) in nuclides.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " + ")?;
+                }
+                write!(f, "{}", LatexNuclide(nuclide))?;
+            }
+            Ok(())
+        };
+        side(f, self.0.reactants())?;
+        write!(f, " \\rightarrow ")?;
+        side(f, self.0.products())
+    }
+}
+
+// a nuclide in LaTeX math mode
+// names that aren't understood are written as they are
+struct LatexNuclide<'a>(&'a Nuclide);
+
+impl fmt::Display for LatexNuclide<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.0.as_str();
+        match name {
+            "n" | "p" | "d" | "t" => return write!(f, "\\mathrm{{{name}}}"),
+            _ if self.0.is_alpha() => return write!(f, "\\alpha"),
+            _ => {}
+        }
+        let Some((z, a)) = self.0.z_a() else {
+            return write!(f, "\\mathrm{{{}}}", Escaped(name));
+        };
+        let symbol = ELEMENTS[usize::from(z)];
+        let mut chars = symbol.chars();
+        let first = chars.next().map(|c| c.to_ascii_uppercase());
+        write!(
+            f,
+            "{{}}^{{{a}}}\\mathrm{{{}{}}}",
+            first.unwrap_or_default(),
+            chars.as_str()
+        )?;
+        // the aluminium-26 ground and isomeric states
+        match name {
+            "al-6" => write!(f, "^{{g}}"),
+            "al*6" => write!(f, "^{{m}}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+// text with the characters that are special in LaTeX escaped
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' | '%' | '$' | '#' | '_' | '{' | '}' => write!(f, "\\{c}")?,
+                '~' => write!(f, "\\textasciitilde{{}}")?,
+                '^' => write!(f, "\\textasciicircum{{}}")?,
+                '\\' => write!(f, "\\textbackslash{{}}")?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// a number like `$1.234 \times 10^{-5}$`, or `---` if it isn't finite
+struct Scientific(f64);
+
+impl fmt::Display for Scientific {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.0.is_finite() {
+            return write!(f, "---");
+        }
+        let s = format!("{:.3e}", self.0);
+        let (mantissa, exponent) = s.split_once('e').unwrap_or((&s, "0"));
+        if exponent == "0" {
+            write!(f, "${mantissa}$")
+        } else {
+            write!(f, "${mantissa} \\times 10^{{{exponent}}}$")
+        }
+    }
+}
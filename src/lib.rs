@@ -1,9 +1,22 @@
 //! A parsing library for the [reaclib] data format.
 //!
-//! The data is represented by [`Set`], and the parsing is mostly done by [`Iter`].
+//! The data is represented by [`Set`], and the parsing is mostly done by [`Iter`], with options
+//! set by [`Iter::builder`].
+//! `Iter` reads from a [`BufRead`], or from any other [`LineSource`], such as an
+//! iterator of lines wrapped in [`Lines`].
 //! The data can be collected into a type that implements [`FromIterator`], such as [`Vec`].
 //! A convenience function [`to_hash_map`] is provided for the case that you want a `Vec` of all
-//! `Set`s for each reaction.
+//! `Set`s for each reaction, and [`to_canonical_hash_map`] does the same with the nuclides in each
+//! reaction sorted into a canonical order.
+//! Other ways of grouping, such as with only the products sorted, are chosen with [`Grouping`] in
+//! [`to_grouped_hash_map`].
+//! [`Iter::chapter_map`] also keys by chapter, for libraries with the same reaction in several
+//! chapters.
+//! If only the reactions are needed, [`HeaderIter`] skips parsing the rate parameters, and if the
+//! number of sets is needed up front, [`SizedIter`] counts them first.
+//! [`Library`] holds a collection of `Set`s along with an index by reaction, and provides ways to
+//! query and reduce it.
+//! Sets can be written back out with [`Writer`].
 //!
 //! [reaclib]: https://reaclib.jinaweb.org/
 //!
@@ -12,6 +25,10 @@
 //! The format is documented on the [reaclib format help page][reaclib_format].
 //! There are two formats, both supported by this library.
 //! [`Format`] is used to indicate which version to expect.
+//! In either format, numbers may also be written with a Fortran-style `D` exponent, like
+//! `1.234560D+00`, as some locally produced files are.
+//! Lines may end with `\n`, `\r\n`, or `\r`, and control characters at the end of a line are
+//! ignored, so files that have passed through Windows or old Mac tools are read the same.
 //!
 //! [reaclib_format]: https://reaclib.jinaweb.org/help.php?topic=reaclib_format
 //!
@@ -45,38 +62,186 @@
 //!
 //! * `serde`: Provide `Serialize` and `Deserialize` implementations for [serde](https://serde.rs).
 //! * `arbitrary`: Provide `Arbitrary` implementations for [arbitrary](https://crates.io/crates/arbitrary), useful for fuzzing.
+//! * `rayon`: Provide parallel rate evaluation, and convert files in parallel with [`convert_dir`], using [rayon](https://crates.io/crates/rayon).
+//! * `num-traits`: Provide rate evaluation that is generic over [num-traits](https://crates.io/crates/num-traits) floats, such as `f32` or dual numbers.
+//! * `cache`: Provide a compact binary cache for a [`Library`] using [postcard](https://crates.io/crates/postcard), for fast reloading. This enables `serde`.
+//! * `arrow`: Provide conversion of a [`Library`] into [Arrow](https://arrow.apache.org) record batches.
+//! * `sqlite`: Provide writing a [`Library`] into an [SQLite](https://sqlite.org) database and reading it back, using [rusqlite](https://crates.io/crates/rusqlite).
+//! * `schemars`: Provide `JsonSchema` implementations for [schemars](https://crates.io/crates/schemars), describing the serialized form of [`Set`], [`Library`], and related types. This enables `serde`.
+//! * `download`: Provide downloading, verifying, and locally caching reaclib snapshots from a given URL, using [ureq](https://crates.io/crates/ureq).
+//! * `indexmap`: Provide [`to_index_map`], which groups sets by reaction like [`to_hash_map`], while keeping the order of the input, using [indexmap](https://crates.io/crates/indexmap).
+//! * `tracing`: Emit [tracing](https://crates.io/crates/tracing) events while parsing, for chapter changes, sets that fail to parse, and progress every 10,000 sets, inside of a `reaclib::parse` span.
+//! * `uom`: Provide rates as typed quantities from [uom](https://crates.io/crates/uom), with [`Set::typed_rate`] taking a temperature and returning a [`TypedRate`] with the dimensions for the number of reactants, so that dimensional errors are caught by the compiler.
+//! * `weak-tables`: Provide [`TabulatedWeakRate`], which reads the temperature- and density-dependent weak rate tables of FFN and LMP, for the weak rates that aren't in reaclib.
+//! * `macros`: Provide [`include_reaclib`], which parses a file at compile time into a static table of [`StaticSet`]s.
+//! * `cli`: Build the `reaclib` binary, with subcommands for converting, filtering, summarizing, and comparing files, converting whole directories, and evaluating rates. It can be installed with `cargo install reaclib --features cli`. This enables `serde`.
 use crate::error::ReaclibError as RError;
 #[cfg(feature = "arbitrary")]
 use arbitrary::{Arbitrary, Unstructured};
-use arrayvec::{ArrayString, ArrayVec};
+use arrayvec::ArrayString;
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
-use std::{
-    collections::HashMap,
-    hash::Hash,
-    io::{BufRead, Lines},
-    ops::Range,
-    str::FromStr,
-};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::{collections::HashMap, hash::Hash, io::BufRead, ops::Range, str::FromStr};
+
+// lets the code generated by `include_reaclib` refer to `::reaclib` in the tests
+#[cfg(all(test, feature = "macros"))]
+extern crate self as reaclib;
 
-pub use crate::error::ReaclibError;
+#[cfg(feature = "rayon")]
+pub use crate::rate::rates_par;
+#[cfg(feature = "serde")]
+pub use crate::set_list::SetList;
+#[cfg(feature = "macros")]
+pub use crate::static_set::StaticSet;
+#[cfg(feature = "uom")]
+pub use crate::units::{FourBodyRate, ThreeBodyRate, TwoBodyRate, TypedRate};
+#[cfg(feature = "weak-tables")]
+pub use crate::weak_table::TabulatedWeakRate;
+pub use crate::{
+    batch::{convert_dir, convert_dir_with, Conversion, Manifest},
+    bruslib::Bruslib,
+    changelog::Changelog,
+    codegen::{write_code, Language},
+    equilibrium::RatePair,
+    error::{Field, IoError, ParseError, ReaclibError, SetError},
+    fit::fit_params,
+    fit_form::{FitForm, FitIter, FitSet, ReaclibFit},
+    iter_builder::IterBuilder,
+    kind::RateKind,
+    kinematics::{EntranceChannel, GamowWindow},
+    label::{label_info, LabelInfo, SourceKind},
+    latex::LatexTable,
+    library::{Library, LibraryDiff, RateComparison, ReversePairs},
+    line_source::{LineSource, Lines},
+    mesa::{mesa_rate_name, write_mesa_table},
+    metadata::LibraryMetadata,
+    names::NameMap,
+    network::Contributions,
+    nuclide::{nuclide_z_a, Nuclide},
+    packed::PackedLibrary,
+    rate::{
+        log_grid, Rate, RateBasis, RateComponent, RateCurve, RateEvaluator, ReactionRate, Screening,
+    },
+    rate_table::write_rate_table,
+    reaction::Reaction,
+    round_trip::assert_round_trip,
+    set_key::SetKey,
+    sized_iter::SizedIter,
+    span::Span,
+    species::Species,
+    stats::{Stats, StatsAccumulator},
+    tabulated::TabulatedRate,
+    validity::{Extrapolation, ValidityRange},
+    winvn::{NuclideInfo, Winvn},
+    writer::{write_sets, Writer},
+};
+#[cfg(feature = "download")]
+pub use crate::{download::Snapshot, snapshot_cache::SnapshotCache};
+/// Parse a reaclib file at compile time into a `&'static [StaticSet]`.
+///
+/// The path is relative to the directory containing the crate's `Cargo.toml` (not the current
+/// file, unlike [`include_str`]), and it can be followed by the format, `Reaclib1` or `Reaclib2`
+/// (the default).
+/// A parsing error is a compile error, and the crate is rebuilt when the file changes.
+///
+/// This is meant for small networks that are built into a simulation, so that there is no file
+/// to ship and nothing to parse at startup.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{include_reaclib, Library, Set, StaticSet};
+///
+/// static NETWORK: &[StaticSet] = include_reaclib!("src/tests/library/network", Reaclib2);
+///
+/// assert_eq!(NETWORK.len(), 7);
+/// assert_eq!(NETWORK[0].reaction().to_string(), "p c12 -> n13");
+/// let rate = NETWORK[0].rate(1.0);
+///
+/// let library = NETWORK.iter().map(Set::from).collect::<Library>();
+/// assert_eq!(library.sets().len(), 7);
+/// ```
+///
+/// A file that doesn't parse doesn't compile:
+///
+/// ```compile_fail
+/// static SETS: &[reaclib::StaticSet] = reaclib::include_reaclib!("src/tests/v2/unknown_chapter");
+/// ```
+#[cfg(feature = "macros")]
+pub use reaclib_macros::include_reaclib;
 
+#[cfg(feature = "arrow")]
+mod arrow;
+mod batch;
+mod bruslib;
+#[cfg(feature = "cache")]
+mod cache;
+mod changelog;
+mod codegen;
+#[cfg(feature = "download")]
+mod download;
+mod equilibrium;
 mod error;
+mod fit;
+mod fit_form;
+mod iter_builder;
+mod kind;
+mod kinematics;
+mod label;
+mod latex;
+mod library;
+mod line_source;
+mod mesa;
+mod metadata;
+mod names;
+mod network;
+mod nuclide;
+mod packed;
+mod rate;
+mod rate_table;
+mod reaction;
+#[cfg(feature = "serde")]
+pub mod reaction_map;
+mod round_trip;
+mod set_key;
+#[cfg(feature = "serde")]
+mod set_list;
+mod sized_iter;
+#[cfg(feature = "download")]
+mod snapshot_cache;
+mod span;
+mod species;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(feature = "macros")]
+mod static_set;
+mod stats;
+mod tabulated;
 #[cfg(test)]
+// the tests check for exact results, so comparing floats exactly is intended
+#[allow(clippy::float_cmp)]
 mod tests;
-
-/// A type that represents a nuclide.
-pub type Nuclide = ArrayString<5>;
-
-/// A type that represents a reaction.
-///
-/// The first element represents the reactants and the second element represents the products.
-pub type Reaction = (ArrayVec<Nuclide, 4>, ArrayVec<Nuclide, 4>);
+#[cfg(feature = "uom")]
+mod units;
+mod validity;
+#[cfg(feature = "weak-tables")]
+mod weak_table;
+mod winvn;
+mod writer;
 
 /// A type holding a single set of reaclib data.
 ///
 /// A reaction may be made up of multiple sets.
 ///
+/// With the `serde` feature, every field is always written, so that formats that aren't
+/// self-describing (such as postcard or bincode) can read it back, but the optional fields may be
+/// left out when reading.
+///
+/// Two sets are equal if they hold the same data, whether or not the raw text they were parsed
+/// from ([`raw_params`][Self::raw_params] and [`raw_lines`][Self::raw_lines]) was kept.
+///
 /// ```
 /// use reaclib::{Format, Iter};
 /// use std::io::Cursor;
@@ -90,16 +255,19 @@ pub type Reaction = (ArrayVec<Nuclide, 4>, ArrayVec<Nuclide, 4>);
 /// let data = iter.next().unwrap().unwrap();
 /// assert_eq!(data.q_value, 7.82300e-01);
 /// ```
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
 pub struct Set {
     /// The nuclides going into a reaction.
-    pub reactants: ArrayVec<Nuclide, 4>,
+    pub reactants: Species,
     /// The nuclides resulting from a reaction.
-    pub products: ArrayVec<Nuclide, 4>,
+    pub products: Species,
     /// A label denoting the source of the reaction.
     ///
     /// Here is a [list of all labels](https://reaclib.jinaweb.org/labels.php).
+    /// With the `serde` feature, it is trimmed when deserialized.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_label"))]
     pub label: ArrayString<4>,
     /// The resonance flag for the reaction.
     pub resonance: Resonance,
@@ -116,47 +284,209 @@ pub struct Set {
     /// for how to interpret these parameters, and [`rate`][Self::rate] for an implementation of
     /// that.
     pub params: [f64; 7],
+    /// The parameters exactly as they appeared in the source, if they were kept.
+    ///
+    /// These are only filled in when parsing with [`Iter::with_raw_params`] or
+    /// [`IterBuilder::raw_params`], and are used by [`Writer`] to reproduce the source
+    /// byte-for-byte when a parameter's value hasn't changed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub raw_params: Option<Box<[ArrayString<13>; 7]>>,
+    /// The lines of the set exactly as they appeared in the source, if they were kept: the first
+    /// line and the two parameter lines, without the chapter header or line endings.
+    ///
+    /// These are only filled in with [`IterBuilder::raw_lines`], so that error reports, diffs,
+    /// and patch tools can show exactly what the file said.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub raw_lines: Option<Box<[String; 3]>>,
+    /// The range of temperatures that the rate is valid over, if it isn't the usual
+    /// [`ValidityRange::REACLIB`].
+    ///
+    /// This isn't part of the reaclib format, so it is never filled in when parsing.
+    /// See [`validity_range`][Self::validity_range] and [`rate_with_policy`][Self::rate_with_policy].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub validity: Option<ValidityRange>,
 }
 
-impl Set {
-    fn from_lines(chapter: Chapter, lines: &[String; 3]) -> Result<Self, RError> {
-        fn range_err(line: &str, range: Range<usize>) -> Result<&str, RError> {
-            if line.len() < range.end {
-                Err(RError::TooShortLine)
-            } else {
-                Ok(line.get(range).ok_or(RError::StrIndex)?.trim())
-            }
-        }
+/// A type holding the first line of a set of reaclib data.
+///
+/// This is everything in a [`Set`] except for the rate parameters, and is produced by
+/// [`HeaderIter`], which skips parsing the parameter lines entirely.
+///
+/// ```
+/// use reaclib::{Format, HeaderIter};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01          
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00                      
+///  0.000000e+00 0.000000e+00 0.000000e+00                                   ");
+///
+/// let mut iter = HeaderIter::new(reader, Format::Reaclib2);
+/// let header = iter.next().unwrap().unwrap();
+/// assert_eq!(header.q_value, 7.82300e-01);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Header {
+    /// The nuclides going into a reaction.
+    pub reactants: Species,
+    /// The nuclides resulting from a reaction.
+    pub products: Species,
+    /// A label denoting the source of the reaction.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_label"))]
+    pub label: ArrayString<4>,
+    /// The resonance flag for the reaction.
+    pub resonance: Resonance,
+    /// A flag denoting whether the reaction rate was derived from the reverse rate using detailed
+    /// balance.
+    pub reverse: bool,
+    /// The Q-value of the reaction.
+    pub q_value: f64,
+}
 
+impl Header {
+    // errors come with the field that they were found in
+    fn from_line(
+        chapter: Chapter,
+        line: &str,
+        species_width: usize,
+    ) -> Result<Self, (Field, ParseError)> {
+        let nuclide = |i, field| {
+            species_field(line, i, species_width)
+                .map(Nuclide::from_field)
+                .map_err(|e| (field, e))
+        };
         let reactants = (0..chapter.num_reactants())
-            .map(|i| {
-                let r = (5 + 5 * i)..(5 + 5 * (i + 1));
-                Ok(Nuclide::from(range_err(&lines[0], r)?)
-                    .expect("the range is 5 and the capacity is 5"))
-            })
-            .collect::<Result<_, RError>>()?;
-        let products = (chapter.num_reactants()
-            ..(chapter.num_reactants() + chapter.num_products()))
-            .map(|i| {
-                let r = (5 + 5 * i)..(5 + 5 * (i + 1));
-                Ok(Nuclide::from(range_err(&lines[0], r)?)
-                    .expect("the range is 5 and the capacity is 5"))
-            })
-            .collect::<Result<_, RError>>()?;
-        let label = ArrayString::from(range_err(&lines[0], 43..47)?)
-            .expect("the range is 4 and the capacity is 4");
-        let resonance = range_err(&lines[0], 47..48)?.parse()?;
-        let reverse = range_err(&lines[0], 48..49)? == "v";
-        let q_value = range_err(&lines[0], 52..64)?.parse()?;
+            .map(|i| nuclide(i, Field::Reactant(i)))
+            .collect::<Result<_, _>>()?;
+        let products = (0..chapter.num_products())
+            .map(|i| nuclide(chapter.num_reactants() + i, Field::Product(i)))
+            .collect::<Result<_, _>>()?;
+        let label = range_err(line, reaclib_core::LABEL).map_err(|e| (Field::Label, e))?;
+        let label = ArrayString::from(label).expect("the range is 4 and the capacity is 4");
+        let resonance = range_err(line, reaclib_core::RESONANCE)
+            .and_then(str::parse)
+            .map_err(|e| (Field::Resonance, e))?;
+        let reverse = range_err(line, reaclib_core::REVERSE).map_err(|e| (Field::Reverse, e))?;
+        let reverse = reaclib_core::reverse(reverse);
+        let q_value = range_err(line, reaclib_core::Q_VALUE)
+            .and_then(parse_float)
+            .map_err(|e| (Field::QValue, e))?;
+
+        Ok(Self {
+            reactants,
+            products,
+            label,
+            resonance,
+            reverse,
+            q_value,
+        })
+    }
+}
+
+impl From<Set> for Header {
+    fn from(set: Set) -> Self {
+        Self {
+            reactants: set.reactants,
+            products: set.products,
+            label: set.label,
+            resonance: set.resonance,
+            reverse: set.reverse,
+            q_value: set.q_value,
+        }
+    }
+}
+
+// the columns of each field, and how they are read, are in `reaclib_core`, so that
+// `include_reaclib` follows the same rules
+fn range_err(line: &str, range: Range<usize>) -> Result<&str, ParseError> {
+    Ok(reaclib_core::field(line, range)?)
+}
+
+// Parse a number, which may also use a Fortran-style `D` exponent, like `1.234560D+00`.
+fn parse_float(s: &str) -> Result<f64, ParseError> {
+    Ok(reaclib_core::parse_float(s)?)
+}
+
+// an error in `field`, on `line`
+fn field_error(line: usize, field: Field, kind: ParseError) -> RError {
+    RError::Parse { line, field, kind }
+}
+
+// the `i`th nuclide in the first line of a set, where each takes up `width` columns
+fn species_field(line: &str, i: usize, width: usize) -> Result<&str, ParseError> {
+    range_err(line, reaclib_core::species(i, width))
+}
+
+// the reaction in the first line of a set, if the nuclides can be read, even if the rest of the
+// line can't
+fn reaction_from_line(chapter: Chapter, line: &str, species_width: usize) -> Option<Reaction> {
+    let nuclides = |range: Range<usize>| {
+        range
+            .map(|i| species_field(line, i, species_width).map(Nuclide::from_field))
+            .collect::<Result<Species, _>>()
+            .ok()
+    };
+    let reactants = chapter.num_reactants();
+    Some(Reaction::new(
+        nuclides(0..reactants)?,
+        nuclides(reactants..reactants + chapter.num_products())?,
+    ))
+}
+
+// the raw text is only a record of where the data came from, so it isn't compared
+impl PartialEq for Set {
+    fn eq(&self, other: &Self) -> bool {
+        self.reactants == other.reactants
+            && self.products == other.products
+            && self.label == other.label
+            && self.resonance == other.resonance
+            && self.reverse == other.reverse
+            && self.q_value == other.q_value
+            && self.params == other.params
+            && self.validity == other.validity
+    }
+}
+
+impl Set {
+    // errors come with the index of the line that they were found in, and the field
+    fn from_lines(
+        chapter: Chapter,
+        lines: &[String; 3],
+        raw_params: bool,
+        species_width: usize,
+    ) -> Result<Self, (usize, Field, ParseError)> {
+        let Header {
+            reactants,
+            products,
+            label,
+            resonance,
+            reverse,
+            q_value,
+        } = Header::from_line(chapter, &lines[0], species_width).map_err(|(f, e)| (0, f, e))?;
+        let param = |i: usize| {
+            let (line, range) = reaclib_core::param(i);
+            range_err(&lines[line], range)
+                .and_then(parse_float)
+                .map_err(|e| (line, Field::Param(i), e))
+        };
         let params = [
-            range_err(&lines[1], 0..13)?.parse()?,
-            range_err(&lines[1], 13..26)?.parse()?,
-            range_err(&lines[1], 26..39)?.parse()?,
-            range_err(&lines[1], 39..52)?.parse()?,
-            range_err(&lines[2], 0..13)?.parse()?,
-            range_err(&lines[2], 13..26)?.parse()?,
-            range_err(&lines[2], 26..39)?.parse()?,
+            param(0)?,
+            param(1)?,
+            param(2)?,
+            param(3)?,
+            param(4)?,
+            param(5)?,
+            param(6)?,
         ];
+        // the ranges have already been checked above
+        let raw_params = raw_params.then(|| {
+            Box::new(std::array::from_fn(|i| {
+                let (line, range) = reaclib_core::param(i);
+                ArrayString::from(&lines[line][range])
+                    .expect("the range is 13 and the capacity is 13")
+            }))
+        });
 
         Ok(Self {
             reactants,
@@ -166,60 +496,507 @@ impl Set {
             reverse,
             q_value,
             params,
+            raw_params,
+            raw_lines: None,
+            validity: None,
         })
     }
 
+    /// The reaction that this set belongs to.
+    #[must_use]
+    pub fn reaction(&self) -> Reaction {
+        Reaction::from(self)
+    }
+
+    /// Whether the Q-value is between `min` and `max` (inclusive), in MeV.
+    ///
+    /// This is useful as a predicate when filtering a stream of sets, e.g. to keep only
+    /// exothermic reactions with `set.q_between(0.0, f64::INFINITY)`.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"1
+    ///          n    p                            wc12w     7.82300e-01
+    /// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00");
+    /// let exothermic = Iter::new(reader, Format::Reaclib2)
+    ///     .filter(|s| s.as_ref().map_or(true, |s| s.q_between(0.0, f64::INFINITY)))
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    /// assert_eq!(exothermic.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn q_between(&self, min: f64, max: f64) -> bool {
+        (min..=max).contains(&self.q_value)
+    }
+
+    /// Whether this set describes a weak interaction.
+    ///
+    /// The resonance flag alone isn't a reliable indicator, so a set is considered weak if any of
+    /// these are true:
+    ///   * The resonance flag is [`Resonance::Weak`].
+    ///   * The label is known to only be used for weak rates (see [`label_info`]).
+    ///   * The total charge changes, but the total mass number doesn't.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"1
+    ///          n    p                            wc12w     7.82300e-01
+    /// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00");
+    /// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    /// assert!(set.is_weak());
+    /// ```
+    #[must_use]
+    pub fn is_weak(&self) -> bool {
+        // the total Z and A of some nuclides, if they are all known
+        fn totals(nuclides: &[Nuclide]) -> Option<(u32, u32)> {
+            nuclides.iter().try_fold((0, 0), |(z, a), n| {
+                let (nz, na) = n.z_a()?;
+                Some((z + u32::from(nz), a + u32::from(na)))
+            })
+        }
+
+        if self.resonance == Resonance::Weak || label_info(&self.label).is_some_and(|l| l.weak) {
+            return true;
+        }
+        match (totals(&self.reactants), totals(&self.products)) {
+            (Some((z_r, a_r)), Some((z_p, a_p))) => z_r != z_p && a_r == a_p,
+            _ => false,
+        }
+    }
+
+    /// Where the rate comes from, i.e. whether it is experimental, theoretical, or an evaluation.
+    ///
+    /// This is found from the label (see [`label_info`]), so it is `None` if the label isn't
+    /// known or its source isn't.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter, SourceKind};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01");
+    /// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    /// assert_eq!(set.source_kind(), Some(SourceKind::Evaluation));
+    /// ```
+    #[must_use]
+    pub fn source_kind(&self) -> Option<SourceKind> {
+        label_info(&self.label).and_then(|l| l.source)
+    }
+
+    /// The nuclides that aren't [well-formed][Nuclide::is_well_formed], with the fields that
+    /// they are in.
+    ///
+    /// A malformed nuclide usually means that the file is corrupted, e.g. with its columns
+    /// shifted, which otherwise goes unnoticed.
+    /// These can be reported while reading with [`IterBuilder::validate_nuclides`].
+    ///
+    /// ```
+    /// use reaclib::{Field, Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// // `c12` is shifted one column to the right
+    /// let reader = Cursor::new(r"4
+    ///        he4   c12 o16                       nac2n     7.16200e+00
+    ///  6.965260e+01-1.392540e+00 5.891280e+01-1.482730e+02
+    ///  9.083240e+00-5.410410e-01 7.035540e+01");
+    /// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    /// let fields = set.malformed_nuclides().map(|(f, n)| (f, n.as_str())).collect::<Vec<_>>();
+    /// assert_eq!(fields, [(Field::Reactant(1), "c1"), (Field::Product(0), "2 o16")]);
+    /// ```
+    pub fn malformed_nuclides(&self) -> impl Iterator<Item = (Field, &Nuclide)> {
+        let reactants = (self.reactants.iter().enumerate()).map(|(i, n)| (Field::Reactant(i), n));
+        let products = (self.products.iter().enumerate()).map(|(i, n)| (Field::Product(i), n));
+        reactants
+            .chain(products)
+            .filter(|(_, n)| !n.is_well_formed())
+    }
+
+    /// Classify what kind of reaction this set describes.
+    ///
+    /// See [`RateKind`] for the possible kinds.
+    #[must_use]
+    pub fn kind(&self) -> RateKind {
+        RateKind::of(self)
+    }
+
+    /// Calculate the half-life (in seconds) of a decay.
+    ///
+    /// Decay rates in reaclib don't depend on temperature, so only the first parameter is used.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"1
+    ///          n    p                            wc12w     7.82300e-01
+    /// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00");
+    /// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    ///
+    /// // the neutron half-life is about 10 minutes
+    /// assert!((set.half_life().unwrap() - 611.0).abs() < 1.0);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the set isn't a decay (see [`RateKind::Decay`]), or if its rate
+    /// depends on temperature.
+    pub fn half_life(&self) -> Result<f64, RError> {
+        if self.kind() != RateKind::Decay {
+            return Err(SetError::NotDecay.into());
+        }
+        if self.params[1..].iter().any(|&p| p != 0.0) {
+            return Err(SetError::TemperatureDependent.into());
+        }
+        Ok(std::f64::consts::LN_2 / self.params[0].exp())
+    }
+
+    /// Calculate the molar rate (in mol/g/s) at which this reaction occurs.
+    ///
+    /// Reaclib rates with `n` reactants are given as `N_A^(n-1) <σv>`, so the number of reactions
+    /// per gram per second, divided by Avogadro's number, is
+    /// `ρ^(n-1) * rate * Y_1 * ... * Y_n`, multiplied by the
+    /// [`symmetry_factor`][Self::symmetry_factor] to avoid double-counting identical reactants.
+    /// `density` is in g/cm³, and `abundances` gives the molar abundance `Y` of each nuclide.
+    /// Nuclides that aren't in `abundances` have an abundance of 0.
+    ///
+    /// Electron-capture rates which must be multiplied by `ρ Y_e` are not treated specially.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter, Nuclide};
+    /// use std::{collections::HashMap, io::Cursor};
+    ///
+    /// let reader = Cursor::new(r"4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01");
+    /// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    ///
+    /// let abundances = HashMap::from([
+    ///     ("p".parse::<Nuclide>().unwrap(), 0.7),
+    ///     ("c12".parse::<Nuclide>().unwrap(), 0.001),
+    /// ]);
+    /// let molar_rate = set.molar_rate(100.0, 0.02, &abundances);
+    /// let expected = 100.0 * set.rate(0.02) * 0.7 * 0.001;
+    /// assert!((molar_rate / expected - 1.0).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn molar_rate(
+        &self,
+        density: f64,
+        temperature: f64,
+        abundances: &HashMap<Nuclide, f64>,
+    ) -> f64 {
+        let abundance = self
+            .reactants
+            .iter()
+            .map(|n| abundances.get(n).copied().unwrap_or(0.0))
+            .product::<f64>();
+        self.symmetry_factor() * self.density_factor(density) * self.rate(temperature) * abundance
+    }
+
+    /// The factor `1 / (n_1! n_2! ...)` accounting for identical reactants, where `n_i` is the
+    /// number of times each distinct nuclide appears as a reactant.
+    ///
+    /// For example, this is `1/2` for `p + p` and `1/6` for the triple-alpha reaction.
+    /// Reaclib rates don't include this factor, so it must be applied when calculating abundance
+    /// changes; [`molar_rate`][Self::molar_rate] does this already.
+    #[must_use]
+    pub fn symmetry_factor(&self) -> f64 {
+        let mut factor = 1.0;
+        for (i, n) in self.reactants.iter().enumerate() {
+            // the number of times this nuclide has been seen so far, including this one
+            let count = self.reactants[..=i].iter().filter(|m| *m == n).count();
+            // there are at most 4 reactants, so this conversion is exact
+            factor /= f64::from(u8::try_from(count).unwrap_or(u8::MAX));
+        }
+        factor
+    }
+
+    /// The factor of density, `ρ^(n-1)`, that the rate must be multiplied by for a reaction with
+    /// `n` reactants.
+    #[must_use]
+    pub fn density_factor(&self, density: f64) -> f64 {
+        // there are at most 4 reactants, so this cast can't fail
+        let n = i32::try_from(self.reactants.len()).unwrap_or(i32::MAX);
+        density.powi(n - 1)
+    }
+
+    /// Compare two sets, allowing the Q-value and parameters to differ by up to `tolerance`.
+    ///
+    /// Everything else must be exactly equal, except for [`raw_params`][Self::raw_params],
+    /// [`raw_lines`][Self::raw_lines], and [`validity`][Self::validity], which are ignored.
+    /// Two values `a` and `b` are considered equal if `|a - b| <= tolerance * max(1, |a|, |b|)`,
+    /// so the tolerance is absolute for small values and relative for large values.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"1
+    ///          n    p                            wc12w     7.82300e-01
+    /// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00");
+    /// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    ///
+    /// let mut other = set.clone();
+    /// other.params[0] += 1e-10;
+    /// assert_ne!(set, other);
+    /// assert!(set.approx_eq(&other, 1e-9));
+    /// ```
+    #[must_use]
+    pub fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        let close =
+            |a: f64, b: f64| (a - b).abs() <= tolerance * f64::max(1.0, f64::max(a.abs(), b.abs()));
+
+        self.reactants == other.reactants
+            && self.products == other.products
+            && self.label == other.label
+            && self.resonance == other.resonance
+            && self.reverse == other.reverse
+            && close(self.q_value, other.q_value)
+            && self
+                .params
+                .iter()
+                .zip(&other.params)
+                .all(|(a, b)| close(*a, *b))
+    }
+
     /// Calculate the rate based on the rate parameters and their meaning, accoriding to the
     /// [reaclib format help](https://reaclib.jinaweb.org/help.php?topic=reaclib_format).
     #[must_use]
     pub fn rate(&self, temperature: f64) -> f64 {
-        // the indexing here can panic if the index is out of bounds, but `params` has a len of 7,
-        // so indices of 0..=6 will not cause a panic
-        // also, be careful with `i as f64`. this is fine because 0..=6 can all be represented by f64
-        #[allow(clippy::cast_precision_loss)]
-        let sum = (1..=5)
-            .map(|i| self.params[i] * f64::powf(temperature, 2.0 * (i as f64) * 5.0 / 3.0))
-            .sum::<f64>();
-        f64::exp(self.params[6].mul_add(f64::ln(temperature), self.params[0] + sum))
+        self.rate_with_basis(&RateBasis::new(temperature))
+    }
+
+    /// Calculate the rate, checking that the temperature is valid and the result is finite.
+    ///
+    /// [`rate`][Self::rate] doesn't check its input, so a temperature of 0 gives 0, infinity, or
+    /// NaN, depending on the parameters.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter, ReaclibError, SetError};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01");
+    /// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    ///
+    /// assert_eq!(set.checked_rate(0.5), Ok(set.rate(0.5)));
+    /// assert_eq!(
+    ///     set.checked_rate(0.0),
+    ///     Err(ReaclibError::Set(SetError::NonPositiveTemperature))
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `temperature` isn't positive (including if it is NaN), or if the rate
+    /// isn't finite.
+    pub fn checked_rate(&self, temperature: f64) -> Result<f64, RError> {
+        rate::check_rate(temperature, |t| self.rate(t))
+    }
+
+    /// Calculate the rate using basis functions that have already been evaluated.
+    ///
+    /// This gives the same result as [`rate`][Self::rate], but is faster when evaluating many sets
+    /// at the same temperature, since the powers of the temperature are only calculated once.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter, RateBasis};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01");
+    /// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    ///
+    /// let basis = RateBasis::new(0.5);
+    /// assert_eq!(set.rate_with_basis(&basis), set.rate(0.5));
+    /// ```
+    #[must_use]
+    pub fn rate_with_basis(&self, basis: &RateBasis) -> f64 {
+        f64::exp(basis.dot(&self.params))
+    }
+
+    /// Calculate the rate, using a generic floating point type.
+    ///
+    /// This allows the rate to be evaluated in other precisions, or with types such as dual
+    /// numbers for automatic differentiation.
+    /// Parameters that can't be represented in `T` are treated as NaN.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01");
+    /// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    ///
+    /// let rate: f32 = set.rate_generic(0.5_f32);
+    /// assert!((f64::from(rate) / set.rate(0.5) - 1.0).abs() < 1e-5);
+    /// ```
+    #[cfg(feature = "num-traits")]
+    #[must_use]
+    pub fn rate_generic<T: num_traits::Float>(&self, temperature: T) -> T {
+        rate::generic_ln_rate(&self.params, temperature).exp()
+    }
+
+    /// Calculate the logarithmic derivative of the rate, `d(ln rate) / d(ln T)`.
+    ///
+    /// This is calculated analytically from the parameters, and is the temperature sensitivity
+    /// needed for the temperature terms of a network's Jacobian.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01");
+    /// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    ///
+    /// let (t, h) = (0.5, 1e-6);
+    /// let numeric = (set.rate(t * (1.0 + h)).ln() - set.rate(t * (1.0 - h)).ln())
+    ///     / ((1.0 + h).ln() - (1.0 - h).ln());
+    /// assert!((set.log_derivative(t) - numeric).abs() < 1e-6);
+    /// ```
+    #[must_use]
+    pub fn log_derivative(&self, temperature: f64) -> f64 {
+        RateBasis::new(temperature).log_derivative(&self.params)
+    }
+
+    /// Calculate the second logarithmic derivative of the rate, `d²(ln rate) / d(ln T)²`.
+    ///
+    /// Like [`log_derivative`][Self::log_derivative], this is calculated analytically from the
+    /// parameters.
+    /// It measures the curvature of the rate in log-log space, which is useful for higher-order
+    /// sensitivities and for choosing where a rate needs to be tabulated more finely.
+    #[must_use]
+    pub fn log_second_derivative(&self, temperature: f64) -> f64 {
+        RateBasis::new(temperature).log_second_derivative(&self.params)
+    }
+
+    /// Find the temperature between `min` and `max` at which the rate equals `target`.
+    ///
+    /// This is useful for finding the temperature where a timescale is reached, by using a target
+    /// of `1.0 / timescale`.
+    /// The rate must cross `target` exactly once between `min` and `max` for the result to be
+    /// meaningful.
+    /// Returns `None` if the rate is on the same side of `target` at both ends of the range.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01");
+    /// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    ///
+    /// let t = set.temperature_for_rate(1e-5, 0.01, 0.1).unwrap();
+    /// assert!((set.rate(t) / 1e-5 - 1.0).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub fn temperature_for_rate(&self, target: f64, min: f64, max: f64) -> Option<f64> {
+        rate::solve_temperature(|t| self.rate(t), target, min, max)
+    }
+
+    /// Calculate the Gamow window of this set's entrance channel at `temperature`.
+    ///
+    /// See [`GamowWindow::for_reactants`] for when this returns `None`.
+    #[must_use]
+    pub fn gamow_window(&self, temperature: f64) -> Option<GamowWindow> {
+        GamowWindow::for_reactants(&self.reactants, temperature)
+    }
+
+    /// The entrance channel of this set, for estimating its Coulomb barrier.
+    ///
+    /// See [`EntranceChannel::for_reactants`] for when this returns `None`.
+    #[must_use]
+    pub fn entrance_channel(&self) -> Option<EntranceChannel> {
+        EntranceChannel::for_reactants(&self.reactants)
+    }
+
+    /// Calculate the rate, multiplied by the screening factor at `temperature` and `density`.
+    ///
+    /// See [`Screening`] for how the factor is applied.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the charge of a reactant can't be determined from its name.
+    pub fn screened_rate<S: Screening + ?Sized>(
+        &self,
+        temperature: f64,
+        density: f64,
+        screening: &S,
+    ) -> Result<f64, RError> {
+        let factor = rate::screening_factor(&self.reactants, temperature, density, screening)?;
+        Ok(factor * self.rate(temperature))
     }
 }
 
+// labels from other tools are often padded, so they are trimmed
+#[cfg(feature = "serde")]
+fn deserialize_label<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<ArrayString<4>, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    ArrayString::from(s.trim()).map_err(|_| de::Error::custom(format!("label too long: {s}")))
+}
+
+// this is adapted from arbitrary's implementation of Arbitrary for &str
 #[cfg(feature = "arbitrary")]
-impl<'a> Arbitrary<'a> for Set {
-    fn arbitrary(u: &mut Unstructured) -> arbitrary::Result<Self> {
-        // this is adapted from arbitrary's implementation of Arbitrary for &str
-        fn array_string<const CAP: usize>(
-            u: &mut Unstructured,
-        ) -> arbitrary::Result<ArrayString<CAP>> {
-            let size = usize::min(u.arbitrary_len::<u8>()?, CAP);
-            match std::str::from_utf8(u.peek_bytes(size).unwrap()) {
-                Ok(s) => {
-                    u.bytes(size).unwrap();
-                    Ok(ArrayString::from(s).expect("size is limited to CAP"))
-                }
-                Err(e) => {
-                    let i = e.valid_up_to();
-                    let valid = u.bytes(i).unwrap();
-                    let s = ArrayString::from(
-                        std::str::from_utf8(valid).expect("we already checked for validity"),
-                    )
-                    .expect("size is limited to CAP");
-                    Ok(s)
-                }
-            }
+fn arbitrary_array_string<const CAP: usize>(
+    u: &mut Unstructured,
+) -> arbitrary::Result<ArrayString<CAP>> {
+    let size = usize::min(u.arbitrary_len::<u8>()?, CAP);
+    match std::str::from_utf8(u.peek_bytes(size).unwrap()) {
+        Ok(s) => {
+            u.bytes(size).unwrap();
+            Ok(ArrayString::from(s).expect("size is limited to CAP"))
         }
+        Err(e) => {
+            let i = e.valid_up_to();
+            let valid = u.bytes(i).unwrap();
+            let s = ArrayString::from(
+                std::str::from_utf8(valid).expect("we already checked for validity"),
+            )
+            .expect("size is limited to CAP");
+            Ok(s)
+        }
+    }
+}
 
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for Set {
+    fn arbitrary(u: &mut Unstructured) -> arbitrary::Result<Self> {
         let chapter: Chapter = u.arbitrary()?;
 
-        let mut reactants = ArrayVec::new();
+        let mut reactants = Species::new();
         for _ in 0..(chapter.num_reactants()) {
-            reactants.push(array_string(u)?);
+            reactants.push(u.arbitrary()?);
         }
-        let mut products = ArrayVec::new();
+        let mut products = Species::new();
         for _ in 0..(chapter.num_products()) {
-            products.push(array_string(u)?);
+            products.push(u.arbitrary()?);
         }
-        let label = array_string(u)?;
+        let label = arbitrary_array_string(u)?;
         let resonance = u.arbitrary()?;
         let reverse = u.arbitrary()?;
         let q_value = u.arbitrary()?;
@@ -233,6 +1010,9 @@ impl<'a> Arbitrary<'a> for Set {
             reverse,
             q_value,
             params,
+            raw_params: None,
+            raw_lines: None,
+            validity: None,
         })
     }
 }
@@ -242,6 +1022,7 @@ impl<'a> Arbitrary<'a> for Set {
 /// There is also an undocumented "s" variant.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[non_exhaustive]
 pub enum Resonance {
@@ -251,16 +1032,30 @@ pub enum Resonance {
     S,
 }
 
+impl Resonance {
+    /// The flag used for this resonance in reaclib files.
+    ///
+    /// A non-resonant set is written as `n`, even though it can also be read from a blank flag.
+    #[must_use]
+    pub const fn as_char(self) -> char {
+        match self {
+            Self::NonResonant => 'n',
+            Self::Resonant => 'r',
+            Self::Weak => 'w',
+            Self::S => 's',
+        }
+    }
+}
+
 impl FromStr for Resonance {
-    type Err = RError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "" | " " | "n" => Ok(Self::NonResonant),
-            "r" => Ok(Self::Resonant),
-            "w" => Ok(Self::Weak),
-            "s" => Ok(Self::S),
-            _ => Err(RError::UnknownResonance(s.to_string())),
+        match reaclib_core::resonance(s)? {
+            'r' => Ok(Self::Resonant),
+            'w' => Ok(Self::Weak),
+            's' => Ok(Self::S),
+            _ => Ok(Self::NonResonant),
         }
     }
 }
@@ -284,9 +1079,12 @@ pub enum Format {
 /// Originally, Chapter 8 was used for both e1 + e2 + e3 → e4 and e1 + e2 + e3 → e4 + e5 reactions.
 /// Chapter 8 now is only used for the first type, and Chapter 9 is used for the second type.
 /// This library does not handle older reaclib files with both types in Chapter 8.
+///
+/// Chapters beyond the standard ones, such as those proposed for reactions with more species, can
+/// be read with [`IterBuilder::chapter`], and are [`Extended`][Self::Extended].
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
 #[non_exhaustive]
 pub enum Chapter {
     /// e1 → e2
@@ -311,9 +1109,70 @@ pub enum Chapter {
     Chapter10,
     /// e1 → e2 + e3 + e4 + e5
     Chapter11,
+    /// A chapter that isn't one of the standard ones, declared with [`IterBuilder::chapter`].
+    Extended {
+        /// The number used for the chapter in reaclib files.
+        number: u8,
+        /// The number of reactants.
+        reactants: u8,
+        /// The number of products.
+        products: u8,
+    },
+}
+
+// only the standard chapters, since sets in other chapters can't be written
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for Chapter {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::try_from(u.int_in_range(1..=11)?).expect("chapters 1 to 11 are standard"))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u8::size_hint(depth)
+    }
 }
 
 impl Chapter {
+    /// Get the chapter describing reactions with `reactants` reactants and `products` products.
+    ///
+    /// Returns `None` if there is no such chapter.
+    #[must_use]
+    pub const fn from_counts(reactants: usize, products: usize) -> Option<Self> {
+        match (reactants, products) {
+            (1, 1) => Some(Self::Chapter1),
+            (1, 2) => Some(Self::Chapter2),
+            (1, 3) => Some(Self::Chapter3),
+            (2, 1) => Some(Self::Chapter4),
+            (2, 2) => Some(Self::Chapter5),
+            (2, 3) => Some(Self::Chapter6),
+            (2, 4) => Some(Self::Chapter7),
+            (3, 1) => Some(Self::Chapter8),
+            (3, 2) => Some(Self::Chapter9),
+            (4, 2) => Some(Self::Chapter10),
+            (1, 4) => Some(Self::Chapter11),
+            _ => None,
+        }
+    }
+
+    /// The number used for this chapter in reaclib files.
+    #[must_use]
+    pub const fn number(&self) -> u8 {
+        match self {
+            Self::Chapter1 => 1,
+            Self::Chapter2 => 2,
+            Self::Chapter3 => 3,
+            Self::Chapter4 => 4,
+            Self::Chapter5 => 5,
+            Self::Chapter6 => 6,
+            Self::Chapter7 => 7,
+            Self::Chapter8 => 8,
+            Self::Chapter9 => 9,
+            Self::Chapter10 => 10,
+            Self::Chapter11 => 11,
+            Self::Extended { number, .. } => *number,
+        }
+    }
+
     #[must_use]
     pub const fn num_reactants(&self) -> usize {
         #[allow(clippy::match_same_arms)]
@@ -329,6 +1188,7 @@ impl Chapter {
             Self::Chapter9 => 3,
             Self::Chapter10 => 4,
             Self::Chapter11 => 1,
+            Self::Extended { reactants, .. } => *reactants as usize,
         }
     }
 
@@ -347,6 +1207,7 @@ impl Chapter {
             Self::Chapter9 => 2,
             Self::Chapter10 => 2,
             Self::Chapter11 => 4,
+            Self::Extended { products, .. } => *products as usize,
         }
     }
 
@@ -355,28 +1216,31 @@ impl Chapter {
     //   * It is a chapter header, but parsing fails (`Some(Err(_))`)
     // This is because we try to parse a group of 3 lines as a chapter header first, and if that
     // fails, we try to parse it as a reaction set.
-    fn from_lines_v1(lines: &[String; 3]) -> Option<Result<Self, RError>> {
+    fn from_lines_v1(lines: &[String; 3], extended: &[Self]) -> Option<Result<Self, ParseError>> {
         let [l1, l2, l3] = lines;
-
-        if l2.trim().is_empty() && l3.trim().is_empty() {
-            match l1.trim().parse::<u8>() {
-                Ok(c) => Some(c.try_into()),
-                Err(e) => Some(Err(e.into())),
-            }
-        } else {
-            None
-        }
+        reaclib_core::chapter_v1([l1, l2, l3]).map(|number| Self::from_number(number?, extended))
     }
 
     // This is simpler than _v1 because a set *always* contains a (one-line) chapter header.
     // So if we can't parse it, that's an error.
-    fn from_lines_v2(line: &str) -> Result<Self, RError> {
-        line.trim().parse::<u8>()?.try_into()
+    fn from_lines_v2(line: &str, extended: &[Self]) -> Result<Self, ParseError> {
+        Self::from_number(reaclib_core::chapter_v2(line)?, extended)
+    }
+
+    // the standard chapter numbered `number`, or else the one in `extended`
+    fn from_number(number: u8, extended: &[Self]) -> Result<Self, ParseError> {
+        Self::try_from(number).or_else(|e| {
+            extended
+                .iter()
+                .find(|c| c.number() == number)
+                .copied()
+                .ok_or(e)
+        })
     }
 }
 
 impl TryFrom<u8> for Chapter {
-    type Error = RError;
+    type Error = ParseError;
 
     fn try_from(x: u8) -> Result<Self, Self::Error> {
         match x {
@@ -391,7 +1255,7 @@ impl TryFrom<u8> for Chapter {
             9 => Ok(Self::Chapter9),
             10 => Ok(Self::Chapter10),
             11 => Ok(Self::Chapter11),
-            _ => Err(RError::UnknownChapter(x)),
+            _ => Err(ParseError::UnknownChapter(x)),
         }
     }
 }
@@ -429,92 +1293,621 @@ impl TryFrom<u8> for Chapter {
 ///
 /// If a set fails to parse or there is a reading error, [`next`][Self::next] will return `Some(Err)`.
 /// Calling `next` again may return `Some`, but the validity of the data is not guaranteed.
-pub struct Iter<R: BufRead> {
-    lines: Lines<R>,
+#[allow(clippy::struct_excessive_bools)]
+pub struct Iter<R: LineSource> {
+    reader: R,
+    // the lines of the current set, and the chapter line before it in `Format::Reaclib2`
+    // these are reused for every set, so that reading doesn't allocate once they are big enough
+    lines: [String; 3],
+    chapter_line: String,
     format: Format,
     chapter: Option<Chapter>,
+    raw_params: bool,
+    raw_lines: bool,
+    lenient: bool,
+    validate_nuclides: bool,
+    spans: bool,
+    species_width: usize,
+    // the chapters declared with `IterBuilder::chapter`
+    extended_chapters: Vec<Chapter>,
+    // whether the first chapter number hasn't been found yet with `IterBuilder::skip_preamble`,
+    // and the lines before it
+    in_preamble: bool,
+    preamble: Vec<String>,
+    // the number of lines that have been read, and the first line of the current set
+    line: usize,
+    start: usize,
+    // the line numbers of `lines` and `chapter_line`, for locating errors
+    line_numbers: [usize; 3],
+    chapter_line_number: usize,
+    span: Option<Span>,
+    // the reaction of the last set that failed to parse, if its nuclides could be read
+    failed_reaction: Option<Reaction>,
+    // the lines of the current set as they were read, with `IterBuilder::raw_lines`
+    source_lines: Option<Box<[String; 3]>>,
+    // a set that was found by `seek_to_chapter` and is in `lines`, with its first line
+    pending: Option<(usize, Chapter)>,
+    // the number of sets (or errors) that have been returned
+    #[cfg(feature = "tracing")]
+    count: usize,
+    #[cfg(feature = "tracing")]
+    trace_span: tracing::Span,
 }
 
-impl<R: BufRead> Iter<R> {
+// the length of a line in the reaclib format
+const LINE_LENGTH: usize = 74;
+
+// rewrite the lines of a set so that small deviations from the format parse:
+// short lines are padded, blank numbers become 0, and unknown resonance flags become blank
+fn make_lenient(lines: &mut [String; 3]) {
+    fn zero_if_blank(line: &mut String, range: Range<usize>) {
+        if line.get(range.clone()).is_some_and(|f| f.trim().is_empty()) {
+            line.replace_range(range.start..=range.start, "0");
+        }
+    }
+
+    for line in lines.iter_mut() {
+        let padding = LINE_LENGTH.saturating_sub(line.len());
+        line.extend(std::iter::repeat_n(' ', padding));
+    }
+    let header = &mut lines[0];
+    if header
+        .get(reaclib_core::RESONANCE)
+        .is_some_and(|f| f.parse::<Resonance>().is_err())
+    {
+        header.replace_range(reaclib_core::RESONANCE, " ");
+    }
+    zero_if_blank(header, reaclib_core::Q_VALUE);
+    for i in 0..7 {
+        let (line, range) = reaclib_core::param(i);
+        zero_if_blank(&mut lines[line], range);
+    }
+}
+
+// Whether a line is skipped by a lenient `Iter`: comments starting with `#` or `!` are skipped
+// anywhere, and blank lines are skipped before a set.
+// In `Format::Reaclib1`, the blank lines of a chapter header come after its first line, so they
+// aren't skipped.
+fn is_skipped(line: &str, first: bool) -> bool {
+    let line = line.trim();
+    line.starts_with(['#', '!']) || (first && line.is_empty())
+}
+
+// Whether an error from reading would likely repeat forever, so reading should stop: any io
+// error other than invalid UTF-8, which only affects one line.
+fn is_fatal(error: &RError) -> bool {
+    matches!(error.innermost(), RError::Io(io) if io.kind() != std::io::ErrorKind::InvalidData)
+}
+
+// how often (in sets) progress is reported with the `tracing` feature
+#[cfg(feature = "tracing")]
+const PROGRESS_INTERVAL: usize = 10_000;
+
+impl<R: LineSource> Iter<R> {
     /// Creates a new `Iter` from `reader`. It will be parsed according to the rules of `format`.
+    ///
+    /// `reader` can be any [`BufRead`], or any other [`LineSource`], such as [`Lines`] for an
+    /// iterator of lines.
+    /// See [`builder`][Self::builder] for more options.
     pub fn new(reader: R, format: Format) -> Self {
-        let lines = reader.lines();
         Self {
-            lines,
+            reader,
+            lines: Default::default(),
+            chapter_line: String::new(),
             format,
             chapter: None,
+            raw_params: false,
+            raw_lines: false,
+            lenient: false,
+            validate_nuclides: false,
+            spans: false,
+            species_width: 5,
+            extended_chapters: Vec::new(),
+            in_preamble: false,
+            preamble: Vec::new(),
+            line: 0,
+            start: 1,
+            line_numbers: [0; 3],
+            chapter_line_number: 0,
+            span: None,
+            failed_reaction: None,
+            source_lines: None,
+            pending: None,
+            #[cfg(feature = "tracing")]
+            count: 0,
+            #[cfg(feature = "tracing")]
+            trace_span: tracing::debug_span!("reaclib::parse", ?format),
+        }
+    }
+
+    /// Creates an [`IterBuilder`] for `reader`, for setting parsing options.
+    ///
+    /// ```
+    /// use reaclib::{Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"1
+    ///          n    p                            wc12w     7.82300e-01
+    /// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00");
+    /// let mut iter = Iter::builder(reader)
+    ///     .format(Format::Reaclib2)
+    ///     .lenient(true)
+    ///     .with_spans(true)
+    ///     .build();
+    /// assert!(iter.next().unwrap().is_ok());
+    /// assert_eq!(iter.span().unwrap().to_string(), "lines 1-4");
+    /// ```
+    pub fn builder(reader: R) -> IterBuilder<R> {
+        IterBuilder::new(reader)
+    }
+
+    /// Creates a new `Iter` from `reader`, like [`new`][Self::new], which also keeps the
+    /// parameters exactly as they appear in the source in [`Set::raw_params`].
+    pub fn with_raw_params(reader: R, format: Format) -> Self {
+        Self {
+            raw_params: true,
+            ..Self::new(reader, format)
+        }
+    }
+
+    // Read the lines of the next set into `self.lines`, and find the chapter that it belongs to.
+    // The lines are not parsed beyond what is needed to find the chapter.
+    fn next_lines(&mut self) -> Option<Result<Chapter, RError>> {
+        #[cfg(feature = "tracing")]
+        let (_entered, previous) = (self.trace_span.clone().entered(), self.chapter);
+        self.start = self.line + 1;
+        let next = if let Some((start, chapter)) = self.pending.take() {
+            self.start = start;
+            Some(Ok(chapter))
+        } else {
+            match self.format {
+                Format::Reaclib1 => self.next_lines_v1(),
+                Format::Reaclib2 => self.next_lines_v2(),
+            }
+        };
+        if self.spans {
+            self.span = next.as_ref().map(|_| Span {
+                first_line: self.start,
+                last_line: self.line,
+            });
+        }
+        // the lines are kept before they are changed to be lenient
+        self.source_lines =
+            (self.raw_lines && matches!(next, Some(Ok(_)))).then(|| Box::new(self.lines.clone()));
+        if self.lenient && matches!(next, Some(Ok(_))) {
+            make_lenient(&mut self.lines);
+        }
+        #[cfg(feature = "tracing")]
+        if let Some(Ok(chapter)) = &next {
+            if previous != Some(*chapter) {
+                tracing::debug!(chapter = chapter.number(), set = self.count, "new chapter");
+            }
+        }
+        next
+    }
+
+    // report a set that was returned, along with progress every so often
+    #[cfg(feature = "tracing")]
+    fn record<T>(&mut self, item: &Result<T, RError>) {
+        let _entered = self.trace_span.enter();
+        match item {
+            Err(e) => tracing::warn!(set = self.count, error = %e, "failed to read a set"),
+            Ok(_) if (self.count + 1).is_multiple_of(PROGRESS_INTERVAL) => {
+                tracing::info!(sets = self.count + 1, "read sets");
+            }
+            Ok(_) => {}
         }
+        self.count += 1;
     }
 
-    fn next_v1(&mut self) -> Option<<Self as Iterator>::Item> {
+    fn next_lines_v1(&mut self) -> Option<Result<Chapter, RError>> {
         loop {
-            let lines = match (self.lines.next(), self.lines.next(), self.lines.next()) {
-                (None, _, _) => return None,
-                (_, None, _) | (_, _, None) => {
-                    return Some(Err(RError::TooFewLines));
-                }
-                (Some(Err(e)), _, _) | (_, Some(Err(e)), _) | (_, _, Some(Err(e))) => {
-                    return Some(Err(e.into()));
-                }
-                (Some(Ok(l1)), Some(Ok(l2)), Some(Ok(l3))) => [l1, l2, l3],
-            };
+            self.start = self.line + 1;
+            if let Err(e) = self.read_group()? {
+                return Some(Err(e));
+            }
 
             // Try to interpret as chapter header
             // if that fails, try to interpret as a set
             // it is an error to have a set if the chapter hasn't been set yet
-            match Chapter::from_lines_v1(&lines) {
+            match Chapter::from_lines_v1(&self.lines, &self.extended_chapters) {
                 Some(Ok(chapter)) => {
                     self.chapter = Some(chapter);
-                    continue;
                 }
                 Some(Err(e)) => {
-                    break Some(Err(e));
+                    break Some(Err(field_error(self.line_numbers[0], Field::Chapter, e)));
                 }
                 None => {
                     if let Some(chapter) = self.chapter {
-                        break Some(Set::from_lines(chapter, &lines));
+                        break Some(Ok(chapter));
                     }
-                    break Some(Err(RError::ChapterUnset));
+                    break Some(Err(ParseError::ChapterUnset.into()));
                 }
             }
         }
     }
 
-    fn next_v2(&mut self) -> Option<<Self as Iterator>::Item> {
-        let (ch_line, set_lines) = match (
-            self.lines.next(),
-            self.lines.next(),
-            self.lines.next(),
-            self.lines.next(),
-        ) {
-            (None, _, _, _) => return None,
-            (_, None, _, _) | (_, _, None, _) | (_, _, _, None) => {
-                return Some(Err(RError::TooFewLines));
+    fn next_lines_v2(&mut self) -> Option<Result<Chapter, RError>> {
+        if let Err(e) = self.read_group()? {
+            return Some(Err(e));
+        }
+
+        match Chapter::from_lines_v2(&self.chapter_line, &self.extended_chapters) {
+            Ok(chapter) => {
+                self.chapter = Some(chapter);
+                Some(Ok(chapter))
             }
-            (Some(Err(e)), _, _, _)
-            | (_, Some(Err(e)), _, _)
-            | (_, _, Some(Err(e)), _)
-            | (_, _, _, Some(Err(e))) => {
-                return Some(Err(e.into()));
+            Err(e) => Some(Err(field_error(
+                self.chapter_line_number,
+                Field::Chapter,
+                e,
+            ))),
+        }
+    }
+
+    // Read the next group of lines into `self.lines`, after reading the chapter line into
+    // `self.chapter_line` for `Format::Reaclib2`.
+    // Every line of the group is read, even after an error, so that the next group starts in the
+    // same place.
+    // When lenient, comments and blank lines before the group are skipped, and `self.start` is
+    // the first line that isn't.
+    fn read_group(&mut self) -> Option<Result<(), RError>> {
+        let chapter_lines = usize::from(self.format == Format::Reaclib2);
+        let mut result = Ok(());
+        for i in 0..chapter_lines + 3 {
+            let buf = match i.checked_sub(chapter_lines) {
+                Some(l) => &mut self.lines[l],
+                None => &mut self.chapter_line,
+            };
+            loop {
+                match self.reader.next_line(buf) {
+                    None if i == 0 => return None,
+                    None => return Some(Err(ParseError::TooFewLines.into())),
+                    Some(r) => {
+                        self.line += 1;
+                        if i == 0 && self.in_preamble && r.is_ok() {
+                            let chapter = buf.trim().parse::<u8>().ok().filter(|&n| {
+                                Chapter::from_number(n, &self.extended_chapters).is_ok()
+                            });
+                            if chapter.is_none() {
+                                self.preamble.push(buf.clone());
+                                continue;
+                            }
+                            self.in_preamble = false;
+                        }
+                        if self.lenient && r.is_ok() && is_skipped(buf, i == 0) {
+                            continue;
+                        }
+                        if i == 0 {
+                            self.start = self.line;
+                        }
+                        match i.checked_sub(chapter_lines) {
+                            Some(l) => self.line_numbers[l] = self.line,
+                            None => self.chapter_line_number = self.line,
+                        }
+                        if let (Err(e), Ok(())) = (r, &result) {
+                            result = Err(e);
+                        }
+                        break;
+                    }
+                }
             }
-            (Some(Ok(l1)), Some(Ok(l2)), Some(Ok(l3)), Some(Ok(l4))) => (l1, [l2, l3, l4]),
-        };
+        }
+        Some(result)
+    }
+
+    /// Skip ahead to the next set in `chapter`, so that it is the next one returned.
+    ///
+    /// The sets that are skipped are only read far enough to find their chapters, so nothing is
+    /// parsed, and sets that would fail to parse are skipped without an error.
+    /// This makes it much faster than calling [`next`][Iterator::next] until a set in `chapter`
+    /// comes up, for tools that only need one chapter of a large file.
+    ///
+    /// Returns `Ok(false)` if the end of the input is reached without finding a set in
+    /// `chapter`.
+    ///
+    /// ```
+    /// use reaclib::{Chapter, Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"1
+    ///          n    p                            wc12w     7.82300e-01
+    /// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00
+    /// 4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01");
+    /// let mut iter = Iter::new(reader, Format::Reaclib2);
+    /// assert!(iter.seek_to_chapter(Chapter::Chapter4).unwrap());
+    /// assert_eq!(iter.next().unwrap().unwrap().label.as_str(), "nacr");
+    /// assert!(!iter.seek_to_chapter(Chapter::Chapter4).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error.
+    pub fn seek_to_chapter(&mut self, chapter: Chapter) -> Result<bool, RError> {
+        if let Some((_, c)) = self.pending {
+            if c == chapter {
+                return Ok(true);
+            }
+            self.pending = None;
+        }
+
+        loop {
+            match self.read_group() {
+                None | Some(Err(RError::Invalid(ParseError::TooFewLines))) => return Ok(false),
+                Some(Err(e)) => return Err(e),
+                Some(Ok(())) => {}
+            }
+
+            let found = match self.format {
+                Format::Reaclib1 => {
+                    match Chapter::from_lines_v1(&self.lines, &self.extended_chapters) {
+                        Some(Ok(c)) => {
+                            self.chapter = Some(c);
+                            false
+                        }
+                        Some(Err(_)) => false,
+                        None => self.chapter == Some(chapter),
+                    }
+                }
+                Format::Reaclib2 => {
+                    Chapter::from_lines_v2(&self.chapter_line, &self.extended_chapters)
+                        == Ok(chapter)
+                }
+            };
+            if found {
+                self.chapter = Some(chapter);
+                self.pending = Some((self.start, chapter));
+                return Ok(true);
+            }
+        }
+    }
+
+    /// The lines that the last set (or error) returned by [`next`][Iterator::next] came from.
+    ///
+    /// This is only kept if it was turned on with [`IterBuilder::with_spans`], otherwise it is
+    /// always `None`.
+    /// For [`Format::Reaclib1`], chapter headers before a set aren't included in its span.
+    #[must_use]
+    pub const fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// The lines before the first chapter number that were skipped with
+    /// [`skip_preamble`][IterBuilder::skip_preamble], so far.
+    ///
+    /// The preamble is complete once [`next`][Iterator::next] has returned anything.
+    #[must_use]
+    pub fn preamble(&self) -> &[String] {
+        &self.preamble
+    }
 
-        match Chapter::from_lines_v2(&ch_line) {
-            Ok(chapter) => Some(Set::from_lines(chapter, &set_lines)),
-            Err(e) => Some(Err(e)),
+    /// The chapter of the last set returned by [`next`][Iterator::next], or `None` if no chapter
+    /// has been read yet.
+    #[must_use]
+    pub const fn chapter(&self) -> Option<Chapter> {
+        self.chapter
+    }
+
+    /// Read every set that can be parsed, along with the errors for the ones that can't.
+    ///
+    /// Unlike collecting into a `Result`, this doesn't stop at the first error, so everything
+    /// that can be salvaged is loaded and the problems can be reported afterwards.
+    /// Each error comes with the [`Span`] of lines that it was found in, whether or not
+    /// [`with_spans`][IterBuilder::with_spans] was set.
+    /// Reading stops at an io error (other than invalid UTF-8), since it would likely repeat.
+    ///
+    /// As with [`next`][Iterator::next], the sets after an error may not be valid, e.g. if a set
+    /// was missing a line.
+    ///
+    /// ```
+    /// use reaclib::{Field, Format, Iter, ParseError};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"1
+    ///          n    p                            wc12x     7.82300e-01
+    /// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00
+    /// 1
+    ///          n    p                            wc12w     7.82300e-01
+    /// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00");
+    /// let (sets, errors) = Iter::new(reader, Format::Reaclib2).collect_partial();
+    /// assert_eq!(sets.len(), 1);
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].0.to_string(), "lines 1-4");
+    /// assert_eq!(errors[0].1.field(), Some((2, Field::Resonance)));
+    /// assert_eq!(
+    ///     errors[0].1.parse_error(),
+    ///     Some(&ParseError::UnknownResonance("x".to_string()))
+    /// );
+    /// ```
+    pub fn collect_partial(mut self) -> (Vec<Set>, Vec<(Span, RError)>) {
+        self.spans = true;
+        let (mut sets, mut errors) = (Vec::new(), Vec::new());
+        while let Some(item) = self.next() {
+            match item {
+                Ok(set) => sets.push(set),
+                Err(e) => {
+                    let fatal = is_fatal(&e);
+                    // spans are kept, so this is always `Some`
+                    let span = self.span.unwrap_or(Span {
+                        first_line: self.start,
+                        last_line: self.line,
+                    });
+                    errors.push((span, e));
+                    if fatal {
+                        break;
+                    }
+                }
+            }
         }
+        (sets, errors)
+    }
+
+    /// Read every set into a [`HashMap`] keyed by the chapter that each set was read from and
+    /// its reaction.
+    ///
+    /// This is like [`to_hash_map`], except that the same reaction in different chapters (which
+    /// can only happen with [`IterBuilder::chapter`]) has separate keys, e.g. when a library has
+    /// its own chapter for a reaction with extra leptons among the products.
+    ///
+    /// ```
+    /// use reaclib::{Chapter, Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///          p    p    d                       nacrr     1.44200e+00
+    /// -3.478630e+01 0.000000e+00-3.511930e+00 3.100860e+00
+    /// -1.983140e-01 1.262510e-02-1.025170e+00
+    /// 12
+    ///          p    p    d                       bet+w     1.44200e+00
+    /// -3.478630e+01 0.000000e+00-3.511930e+00 3.100860e+00
+    /// -1.983140e-01 1.262510e-02-1.025170e+00");
+    /// let map = Iter::builder(reader)
+    ///     .format(Format::Reaclib2)
+    ///     .chapter(12, 2, 1)
+    ///     .build()
+    ///     .chapter_map()
+    ///     .unwrap();
+    /// assert_eq!(map.len(), 2);
+    /// let (chapter, reaction) = map.keys().find(|(c, _)| *c != Chapter::Chapter4).unwrap();
+    /// assert_eq!(chapter.number(), 12);
+    /// assert_eq!(reaction.to_string(), "p p -> d");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error or a parsing error, with the location of the error
+    /// like [`to_hash_map`].
+    pub fn chapter_map(mut self) -> Result<HashMap<(Chapter, Reaction), Vec<Set>>, RError> {
+        let mut m = HashMap::new();
+        while let Some(set) = self.next() {
+            let set = set.map_err(|e| self.locate(e))?;
+            // a set is only returned once its chapter is known
+            let chapter = self.chapter.ok_or(ParseError::ChapterUnset)?;
+            m.entry((chapter, set.reaction()))
+                .or_insert_with(Vec::new)
+                .push(set);
+        }
+        Ok(m)
+    }
+
+    // with `IterBuilder::validate_nuclides`, an error for the first malformed nuclide in `set`
+    fn validate(&self, set: Set) -> Result<Set, RError> {
+        if !self.validate_nuclides {
+            return Ok(set);
+        }
+        if let Some((field, nuclide)) = set.malformed_nuclides().next() {
+            return Err(field_error(
+                self.line_numbers[0],
+                field,
+                ParseError::MalformedNuclide(nuclide.to_string()),
+            ));
+        }
+        Ok(set)
+    }
+
+    // add the location of the last set to `error`
+    fn locate(&self, error: RError) -> RError {
+        RError::InSet {
+            span: Span {
+                first_line: self.start,
+                last_line: self.line,
+            },
+            reaction: self.failed_reaction.clone().map(Box::new),
+            source: Box::new(error),
+        }
+    }
+
+    /// Converts this `Iter` into a [`HeaderIter`], which only parses the first line of each set.
+    pub fn headers(self) -> HeaderIter<R> {
+        HeaderIter { iter: self }
     }
 }
 
-impl<R: BufRead> Iterator for Iter<R> {
+impl<R: LineSource> Iterator for Iter<R> {
     type Item = Result<Set, RError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.format {
-            Format::Reaclib1 => self.next_v1(),
-            Format::Reaclib2 => self.next_v2(),
-        }
+        self.failed_reaction = None;
+        let item = match self.next_lines()? {
+            Ok(chapter) => {
+                let set =
+                    Set::from_lines(chapter, &self.lines, self.raw_params, self.species_width)
+                        .map(|set| Set {
+                            raw_lines: self.source_lines.take(),
+                            ..set
+                        })
+                        .map_err(|(i, field, e)| field_error(self.line_numbers[i], field, e))
+                        .and_then(|set| self.validate(set));
+                if set.is_err() {
+                    self.failed_reaction =
+                        reaction_from_line(chapter, &self.lines[0], self.species_width);
+                }
+                set
+            }
+            Err(e) => Err(e),
+        };
+        #[cfg(feature = "tracing")]
+        self.record(&item);
+        Some(item)
+    }
+}
+
+/// An iterator that reads only the first line of each set of reaclib data.
+///
+/// This is useful for indexing or counting reactions, since parsing the rate parameters is skipped.
+/// Because the parameter lines are not parsed, errors in them are not reported.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, HeaderIter};
+/// use std::io::Cursor;
+///
+/// let data = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01          
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00                      
+///  0.000000e+00 0.000000e+00 0.000000e+00                                   ");
+/// let iter = HeaderIter::new(data, Format::Reaclib2);
+/// assert_eq!(iter.count(), 1);
+/// ```
+///
+/// # Errors
+///
+/// If a header fails to parse or there is a reading error, [`next`][Self::next] will return
+/// `Some(Err)`.
+/// Calling `next` again may return `Some`, but the validity of the data is not guaranteed.
+pub struct HeaderIter<R: LineSource> {
+    iter: Iter<R>,
+}
+
+impl<R: LineSource> HeaderIter<R> {
+    /// Creates a new `HeaderIter` from `reader`. It will be parsed according to the rules of
+    /// `format`.
+    pub fn new(reader: R, format: Format) -> Self {
+        Iter::new(reader, format).headers()
+    }
+}
+
+impl<R: LineSource> Iterator for HeaderIter<R> {
+    type Item = Result<Header, RError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next_lines().map(|r| {
+            r.and_then(|chapter| {
+                Header::from_line(chapter, &self.iter.lines[0], self.iter.species_width)
+                    .map_err(|(field, e)| field_error(self.iter.line_numbers[0], field, e))
+            })
+        })?;
+        #[cfg(feature = "tracing")]
+        self.iter.record(&item);
+        Some(item)
     }
 }
 
@@ -535,16 +1928,158 @@ impl<R: BufRead> Iterator for Iter<R> {
 /// # Errors
 ///
 /// Will return `Err` if there is an io error or a parsing error.
+/// The error is a [`ReaclibError::InSet`], with the lines of the set that failed, and its
+/// reaction if the nuclides could be read.
+///
+/// ```
+/// use reaclib::{to_hash_map, Format, ReaclibError};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12x     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+/// let error = to_hash_map(reader, Format::Reaclib2).unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "lines 1-4 (n -> p): line 2, resonance flag: unknown resonance: x"
+/// );
+/// ```
 pub fn to_hash_map<R: BufRead>(
     reader: R,
     format: Format,
+) -> Result<HashMap<Reaction, Vec<Set>>, RError> {
+    to_grouped_hash_map(reader, format, Grouping::Exact)
+}
+
+/// Get an [`IndexMap`][indexmap::IndexMap] mapping reactions to a [`Vec`] of [`Set`]s, with the
+/// reactions in the order they first appear.
+///
+/// This is like [`to_hash_map`], except that iterating over the map follows the order of the
+/// input, so output that is generated from it keeps the original ordering.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{to_index_map, Format};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00
+/// 1
+///          t  he3                            wc12w     1.86000e-02
+/// -2.014560e+01 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+/// let map = to_index_map(reader, Format::Reaclib2).unwrap();
+/// let reactions = map.keys().map(ToString::to_string).collect::<Vec<_>>();
+/// assert_eq!(reactions, ["n -> p", "t -> he3"]);
+/// ```
+///
+/// # Errors
+///
+/// Will return `Err` if there is an io error or a parsing error, with the location of the error
+/// like [`to_hash_map`].
+#[cfg(feature = "indexmap")]
+pub fn to_index_map<R: BufRead>(
+    reader: R,
+    format: Format,
+) -> Result<indexmap::IndexMap<Reaction, Vec<Set>>, RError> {
+    let mut m = indexmap::IndexMap::new();
+
+    let mut iter = Iter::new(reader, format);
+    while let Some(set) = iter.next() {
+        let set = set.map_err(|e| iter.locate(e))?;
+        m.entry(set.reaction()).or_insert_with(Vec::new).push(set);
+    }
+
+    Ok(m)
+}
+
+/// Get a [`HashMap`] mapping reactions to a [`Vec`] of [`Set`]s, with the reactions in canonical
+/// form.
+///
+/// This is like [`to_hash_map`], except that the keys are [`Reaction::canonical`], so sets that
+/// only differ in the order of their nuclides (e.g. `p c12` and `c12 p`) share a key.
+///
+/// # Errors
+///
+/// Will return `Err` if there is an io error or a parsing error, with the location of the error
+/// like [`to_hash_map`].
+pub fn to_canonical_hash_map<R: BufRead>(
+    reader: R,
+    format: Format,
+) -> Result<HashMap<Reaction, Vec<Set>>, RError> {
+    to_grouped_hash_map(reader, format, Grouping::Canonical)
+}
+
+/// How sets are grouped by reaction, for [`to_grouped_hash_map`] and [`Library::group_by`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[non_exhaustive]
+pub enum Grouping {
+    /// By the reaction exactly as it is written, like [`to_hash_map`].
+    #[default]
+    Exact,
+    /// By the reaction with its products sorted, by [`Reaction::sorted_products`], for libraries
+    /// that list the products of the same reaction in different orders.
+    SortedProducts,
+    /// By the reaction in canonical form, like [`to_canonical_hash_map`].
+    Canonical,
+}
+
+impl Grouping {
+    /// The key that `set` is grouped under.
+    #[must_use]
+    pub fn key(self, set: &Set) -> Reaction {
+        let reaction = set.reaction();
+        match self {
+            Self::Exact => reaction,
+            Self::SortedProducts => reaction.sorted_products(),
+            Self::Canonical => reaction.canonical(),
+        }
+    }
+}
+
+/// Get a [`HashMap`] mapping reactions to a [`Vec`] of [`Set`]s, with the reactions keyed
+/// according to `grouping`.
+///
+/// ```
+/// use reaclib::{to_grouped_hash_map, Format, Grouping};
+/// use std::io::Cursor;
+///
+/// let input = r"5
+///          p  o18  he4  n15                  il10n     3.98100e+00
+///  1.910200e+01 0.000000e+00-1.689100e+01-2.400000e+00
+///  0.000000e+00 0.000000e+00-6.666670e-01
+/// 5
+///          p  o18  n15  he4                  il10r     3.98100e+00
+///  7.278800e+00-2.968870e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00-1.500000e+00";
+/// let map = to_grouped_hash_map(Cursor::new(input), Format::Reaclib2, Grouping::Exact).unwrap();
+/// assert_eq!(map.len(), 2);
+/// let map = to_grouped_hash_map(Cursor::new(input), Format::Reaclib2, Grouping::SortedProducts)
+///     .unwrap();
+/// assert_eq!(map.len(), 1);
+/// ```
+///
+/// # Errors
+///
+/// Will return `Err` if there is an io error or a parsing error, with the location of the error
+/// like [`to_hash_map`].
+pub fn to_grouped_hash_map<R: BufRead>(
+    reader: R,
+    format: Format,
+    grouping: Grouping,
 ) -> Result<HashMap<Reaction, Vec<Set>>, RError> {
     let mut m = HashMap::new();
 
-    for set in Iter::new(reader, format) {
-        let set = set?;
-        let key = (set.reactants.clone(), set.products.clone());
-        m.entry(key).or_insert_with(Vec::new).push(set);
+    let mut iter = Iter::new(reader, format);
+    while let Some(set) = iter.next() {
+        let set = set.map_err(|e| iter.locate(e))?;
+        m.entry(grouping.key(&set))
+            .or_insert_with(Vec::new)
+            .push(set);
     }
 
     Ok(m)
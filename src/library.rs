@@ -0,0 +1,745 @@
+use crate::{
+    error::ReaclibError as RError, Format, Grouping, Iter, LibraryMetadata, Nuclide, RateBasis,
+    RateCurve, Reaction, ReactionRate, Set, SetKey,
+};
+#[cfg(feature = "schemars")]
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "schemars")]
+use std::borrow::Cow;
+use std::{
+    collections::{HashMap, HashSet},
+    io::BufRead,
+    ops::Index,
+    slice::SliceIndex,
+};
+
+/// A collection of [`Set`]s, indexed by reaction.
+///
+/// The sets are kept in the order they were added, and the sets for a reaction can be looked up
+/// with [`get`][Self::get].
+/// With the `serde` feature, it is serialized as a struct of its [`metadata`][Self::metadata]
+/// and its `sets`, and the index is rebuilt when it is deserialized.
+/// A plain list of sets can be read as a `Vec<Set>` and collected into a `Library`.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Library};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+///
+/// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+/// assert_eq!(library.sets().len(), 1);
+/// assert_eq!(library.reactions().count(), 1);
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Library {
+    sets: Vec<Set>,
+    index: HashMap<Reaction, Vec<usize>>,
+    // the same as `index`, but keyed by the canonical form of each reaction
+    canonical_index: HashMap<Reaction, Vec<usize>>,
+    metadata: LibraryMetadata,
+}
+
+impl Library {
+    /// Creates an empty `Library`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a `Library` from `reader`. It will be parsed according to the rules of `format`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error or a parsing error.
+    pub fn from_reader<R: BufRead>(reader: R, format: Format) -> Result<Self, RError> {
+        Iter::new(reader, format).collect()
+    }
+
+    /// All of the sets in the library, in the order they were added.
+    #[must_use]
+    pub fn sets(&self) -> &[Set] {
+        &self.sets
+    }
+
+    /// An iterator over the sets in the library, in the order they were added.
+    pub fn iter(&self) -> std::slice::Iter<'_, Set> {
+        self.sets.iter()
+    }
+
+    /// The number of sets in the library.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    /// Whether the library has no sets.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    /// Where the library came from, and how it was put together.
+    #[must_use]
+    pub const fn metadata(&self) -> &LibraryMetadata {
+        &self.metadata
+    }
+
+    /// A mutable reference to the [`metadata`][Self::metadata], e.g. for adding notes while
+    /// curating the library.
+    pub fn metadata_mut(&mut self) -> &mut LibraryMetadata {
+        &mut self.metadata
+    }
+
+    /// Replace the [`metadata`][Self::metadata] with `metadata`.
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: LibraryMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// An iterator over all of the distinct reactions in the library, in arbitrary order.
+    pub fn reactions(&self) -> impl Iterator<Item = &Reaction> {
+        self.index.keys()
+    }
+
+    /// An iterator over the sets that make up `reaction`.
+    ///
+    /// If the reaction isn't in the library, the iterator will be empty.
+    pub fn get<'a>(&'a self, reaction: &Reaction) -> impl Iterator<Item = &'a Set> {
+        self.index
+            .get(reaction)
+            .into_iter()
+            .flatten()
+            .map(|&i| &self.sets[i])
+    }
+
+    /// An iterator over the sets that make up `reaction`, with the nuclides matched in
+    /// [canonical order][Reaction::canonical].
+    ///
+    /// Unlike [`get`][Self::get], this finds the sets even if their nuclides are written in a
+    /// different order than in `reaction`.
+    /// If the reaction isn't in the library, the iterator will be empty.
+    ///
+    /// ```
+    /// use reaclib::{Format, Library, Reaction};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///        he4  c12  o16                       nac2n     7.16200e+00
+    ///  6.965260e+01-1.392540e+00 5.891280e+01-1.482730e+02
+    ///  9.083240e+00-5.410410e-01 7.035540e+01");
+    /// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+    ///
+    /// let reaction: Reaction = "c12 he4 -> o16".parse().unwrap();
+    /// assert_eq!(library.get(&reaction).count(), 0);
+    /// assert_eq!(library.get_canonical(&reaction).count(), 1);
+    /// ```
+    pub fn get_canonical<'a>(&'a self, reaction: &Reaction) -> impl Iterator<Item = &'a Set> {
+        self.canonical_index
+            .get(&reaction.canonical())
+            .into_iter()
+            .flatten()
+            .map(|&i| &self.sets[i])
+    }
+
+    /// Group the sets by reaction, keyed according to `grouping`, e.g. with the products sorted so
+    /// that sets listing them in different orders are together.
+    ///
+    /// The sets of each reaction are in the order they were added.
+    #[must_use]
+    pub fn group_by(&self, grouping: Grouping) -> HashMap<Reaction, Vec<&Set>> {
+        let mut m = HashMap::<_, Vec<_>>::new();
+        for set in &self.sets {
+            m.entry(grouping.key(set)).or_default().push(set);
+        }
+        m
+    }
+
+    /// An iterator over the sets with a Q-value between `min` and `max` (inclusive), in MeV.
+    ///
+    /// See [`Set::q_between`].
+    pub fn q_between(&self, min: f64, max: f64) -> impl Iterator<Item = &Set> {
+        self.sets.iter().filter(move |s| s.q_between(min, max))
+    }
+
+    /// An iterator over the sets of the reverse of `reaction`.
+    ///
+    /// The nuclides are matched in [canonical order][Reaction::canonical], so the reverse
+    /// reaction is found even if its nuclides are written in a different order.
+    /// If the reverse reaction isn't in the library, the iterator will be empty.
+    ///
+    /// ```
+    /// use reaclib::{Format, Library, Reaction};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///        he4  c12  o16                       nac2n     7.16200e+00
+    ///  6.965260e+01-1.392540e+00 5.891280e+01-1.482730e+02
+    ///  9.083240e+00-5.410410e-01 7.035540e+01
+    /// 2
+    ///        o16  c12  he4                       nac2nv   -7.16200e+00
+    ///  9.431310e+01-8.450300e+01 5.891280e+01-1.482730e+02
+    ///  9.083240e+00-5.410410e-01 7.185540e+01");
+    /// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+    ///
+    /// let capture = &library.sets()[0].reaction();
+    /// let inverse = library.inverse_of(capture).collect::<Vec<_>>();
+    /// assert_eq!(inverse.len(), 1);
+    /// assert!(inverse[0].reverse);
+    /// ```
+    pub fn inverse_of<'a>(&'a self, reaction: &Reaction) -> impl Iterator<Item = &'a Set> {
+        self.get_canonical(&reaction.reversed())
+    }
+
+    /// Match every reverse reaction in the library with its forward reaction.
+    ///
+    /// A reaction is considered a reverse reaction if any of its sets has the
+    /// [`reverse`][Set::reverse] flag, and its forward reaction is the
+    /// [inverse][Self::inverse_of] that has sets without the flag.
+    /// Reverse sets with no forward reaction are reported as orphans.
+    /// Everything is in the order that it first appears in the library.
+    ///
+    /// ```
+    /// use reaclib::{Format, Library};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///        he4  c12  o16                       nac2n     7.16200e+00
+    ///  6.965260e+01-1.392540e+00 5.891280e+01-1.482730e+02
+    ///  9.083240e+00-5.410410e-01 7.035540e+01
+    /// 2
+    ///        o16  he4  c12                       nac2nv   -7.16200e+00
+    ///  9.431310e+01-8.450300e+01 5.891280e+01-1.482730e+02
+    ///  9.083240e+00-5.410410e-01 7.185540e+01
+    /// 2
+    ///        f17    p  o16                       ia08nv   -6.00270e-01
+    ///  3.000000e+01 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00");
+    /// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+    ///
+    /// let report = library.pair_reverses();
+    /// assert_eq!(report.pairs.len(), 1);
+    /// assert_eq!(report.pairs[0].0.to_string(), "he4 c12 -> o16");
+    /// assert_eq!(report.orphans.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn pair_reverses(&self) -> ReversePairs<'_> {
+        let mut report = ReversePairs::default();
+        let mut seen = HashSet::new();
+
+        for set in self.sets.iter().filter(|s| s.reverse) {
+            let reverse = set.reaction();
+            let forward = self.inverse_of(&reverse).find(|s| !s.reverse);
+            match forward {
+                Some(forward) => {
+                    if seen.insert(reverse.clone()) {
+                        report.pairs.push((forward.reaction(), reverse));
+                    }
+                }
+                None => report.orphans.push(set),
+            }
+        }
+
+        report
+    }
+
+    /// The total rate of `reaction`, made up of all of its sets.
+    ///
+    /// Returns `None` if the reaction isn't in the library.
+    #[must_use]
+    pub fn reaction_rate(&self, reaction: &Reaction) -> Option<ReactionRate> {
+        let sets = self.get(reaction).cloned().collect::<Vec<_>>();
+        (!sets.is_empty()).then(|| ReactionRate {
+            reaction: reaction.clone(),
+            sets,
+        })
+    }
+
+    /// Evaluate the total rate of each of `reactions` on a logarithmic temperature grid.
+    ///
+    /// See [`ReactionRate::curve`] for how the grid is made.
+    /// Reactions that aren't in the library are skipped.
+    pub fn curves<'a>(
+        &self,
+        reactions: impl IntoIterator<Item = &'a Reaction>,
+        min: f64,
+        max: f64,
+        points: usize,
+    ) -> Vec<RateCurve> {
+        reactions
+            .into_iter()
+            .filter_map(|r| self.reaction_rate(r))
+            .map(|r| r.curve(min, max, points))
+            .collect()
+    }
+
+    /// Adds `set` to the end of the library.
+    pub fn insert(&mut self, set: Set) {
+        self.push(set);
+    }
+
+    /// Removes all of the sets for `reaction`, returning them in the order they were added.
+    ///
+    /// Like [`get`][Self::get], the reaction must match exactly.
+    pub fn remove(&mut self, reaction: &Reaction) -> Vec<Set> {
+        let Some(positions) = self.index.get(reaction).cloned() else {
+            return Vec::new();
+        };
+        self.remove_at(&positions)
+    }
+
+    /// Replaces the sets for `reaction` that have `label` with `sets`, returning the ones that
+    /// were replaced.
+    ///
+    /// The new sets are added to the end of the library, whether or not there were any sets to
+    /// replace, and they are added as they are, so they need not be for `reaction` or have
+    /// `label`.
+    pub fn replace_label(
+        &mut self,
+        reaction: &Reaction,
+        label: &str,
+        sets: impl IntoIterator<Item = Set>,
+    ) -> Vec<Set> {
+        let positions = self
+            .index
+            .get(reaction)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&i| self.sets[i].label.as_str() == label)
+            .collect::<Vec<_>>();
+        let replaced = self.remove_at(&positions);
+        self.extend(sets);
+        replaced
+    }
+
+    // remove the sets at `positions`, which must be in increasing order, and shift the rest of
+    // the index down to match
+    fn remove_at(&mut self, positions: &[usize]) -> Vec<Set> {
+        if positions.is_empty() {
+            return Vec::new();
+        }
+
+        let mut removed = Vec::with_capacity(positions.len());
+        let mut kept = Vec::with_capacity(self.sets.len() - positions.len());
+        let mut next = positions.iter().peekable();
+        for (i, set) in std::mem::take(&mut self.sets).into_iter().enumerate() {
+            if next.next_if_eq(&&i).is_some() {
+                removed.push(set);
+            } else {
+                kept.push(set);
+            }
+        }
+        self.sets = kept;
+
+        // the new position of a set is its old one less the number of removed sets before it
+        for index in [&mut self.index, &mut self.canonical_index] {
+            index.retain(|_, v| {
+                v.retain_mut(|i| match positions.binary_search(i) {
+                    Ok(_) => false,
+                    Err(shift) => {
+                        *i -= shift;
+                        true
+                    }
+                });
+                !v.is_empty()
+            });
+        }
+        removed
+    }
+
+    fn push(&mut self, set: Set) {
+        let reaction = set.reaction();
+        self.canonical_index
+            .entry(reaction.canonical())
+            .or_default()
+            .push(self.sets.len());
+        self.index
+            .entry(reaction)
+            .or_default()
+            .push(self.sets.len());
+        self.sets.push(set);
+    }
+
+    /// Keeps only the reactions whose reactants and products are all in `nuclides`.
+    ///
+    /// Unlike [`trim`][Self::trim], light particles are treated like any other nuclide, so they
+    /// must be included in `nuclides` if reactions involving them should be kept.
+    /// The result only refers to the given nuclides, so it can be written out as a
+    /// self-consistent network.
+    #[must_use]
+    pub fn restrict_to(&self, nuclides: &[Nuclide]) -> Self {
+        let nuclides = nuclides.iter().collect::<HashSet<_>>();
+        self.sets
+            .iter()
+            .filter(|s| {
+                s.reactants
+                    .iter()
+                    .chain(&s.products)
+                    .all(|n| nuclides.contains(n))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Keeps only the reactions whose total rate at `temperature` is greater than `threshold`.
+    ///
+    /// This is a simple way to prune negligible reactions before building a network.
+    /// The rates are compared as they are, without any density or abundance factors.
+    #[must_use]
+    pub fn faster_than(&self, temperature: f64, threshold: f64) -> Self {
+        let basis = RateBasis::new(temperature);
+        let kept = self
+            .index
+            .iter()
+            .filter(|(_, sets)| {
+                sets.iter()
+                    .map(|&i| self.sets[i].rate_with_basis(&basis))
+                    .sum::<f64>()
+                    > threshold
+            })
+            .map(|(r, _)| r)
+            .collect::<HashSet<_>>();
+
+        self.sets
+            .iter()
+            .filter(|s| kept.contains(&s.reaction()))
+            .cloned()
+            .collect()
+    }
+
+    /// Keeps only the reactions that can be reached from the nuclides in `seeds`.
+    ///
+    /// Starting from `seeds`, each step adds every reaction whose reactants are all available,
+    /// and makes its products available for the next step.
+    /// This stops after `max_steps` steps, or when no new reactions are found.
+    ///
+    /// Light particles are not assumed to be present, so they must be included in `seeds` if
+    /// reactions involving them should be kept.
+    #[must_use]
+    pub fn trim(&self, seeds: &[Nuclide], max_steps: usize) -> Self {
+        let mut available = seeds.iter().copied().collect::<HashSet<_>>();
+        let mut kept = HashSet::new();
+
+        for _ in 0..max_steps {
+            let new = self
+                .index
+                .keys()
+                .filter(|r| {
+                    !kept.contains(*r) && r.reactants().iter().all(|n| available.contains(n))
+                })
+                .collect::<Vec<_>>();
+            if new.is_empty() {
+                break;
+            }
+            for r in new {
+                available.extend(r.products().iter().copied());
+                kept.insert(r);
+            }
+        }
+
+        self.sets
+            .iter()
+            .filter(|s| kept.contains(&s.reaction()))
+            .cloned()
+            .collect()
+    }
+
+    /// The sets that differ between this library and `other`.
+    ///
+    /// Sets are compared as by [`SetKey`], and each set is matched at most once, so a set that
+    /// appears twice in one library and once in the other is reported once.
+    ///
+    /// ```
+    /// use reaclib::{Format, Library};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"1
+    ///          n    p                            wc12w     7.82300e-01
+    /// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+    ///  0.000000e+00 0.000000e+00 0.000000e+00");
+    /// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+    ///
+    /// let empty = Library::new();
+    /// let diff = empty.diff(&library);
+    /// assert!(diff.removed.is_empty());
+    /// assert_eq!(diff.added, [&library.sets()[0]]);
+    /// assert!(library.diff(&library).is_empty());
+    /// ```
+    #[must_use]
+    pub fn diff<'a>(&'a self, other: &'a Self) -> LibraryDiff<'a> {
+        // the sets of `a` that aren't matched by a set of `b`
+        let unmatched = |a: &'a Self, b: &Self| {
+            let mut counts = HashMap::<_, usize>::new();
+            for set in &b.sets {
+                *counts.entry(SetKey(set.clone())).or_default() += 1;
+            }
+            a.sets
+                .iter()
+                .filter(|set| match counts.get_mut(&SetKey((*set).clone())) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                })
+                .collect()
+        };
+        LibraryDiff {
+            removed: unmatched(self, other),
+            added: unmatched(other, self),
+        }
+    }
+
+    /// Compare the total rate of each reaction that is in both this library and `other`, on a
+    /// grid of `points` temperatures, logarithmically spaced between `min` and `max` (in GK).
+    ///
+    /// This shows how much the physics changed between two releases, even when the sets were
+    /// refit or relabelled so that [`diff`][Self::diff] only shows that they differ.
+    /// The comparisons are sorted by [`max_factor`][RateComparison::max_factor], from the largest
+    /// discrepancy to the smallest, and then by reaction.
+    /// See [`log_grid`][crate::log_grid] for how the grid is made.
+    /// If `points` is 0, there is nothing to compare, so the result is empty.
+    ///
+    /// ```
+    /// use reaclib::{Format, Library};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01");
+    /// let old = Library::from_reader(reader, Format::Reaclib2).unwrap();
+    /// // doubling the rate
+    /// let new = old
+    ///     .sets()
+    ///     .iter()
+    ///     .map(|set| {
+    ///         let mut set = set.clone();
+    ///         set.params[0] += 2f64.ln();
+    ///         set
+    ///     })
+    ///     .collect::<Library>();
+    ///
+    /// let comparisons = old.compare_rates(&new, 0.1, 10.0, 21);
+    /// assert_eq!(comparisons.len(), 1);
+    /// assert!((comparisons[0].max_factor - 2.0).abs() < 1e-12);
+    /// assert!((comparisons[0].mean_ratio - 2.0).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn compare_rates(
+        &self,
+        other: &Self,
+        min: f64,
+        max: f64,
+        points: usize,
+    ) -> Vec<RateComparison> {
+        if points == 0 {
+            return Vec::new();
+        }
+        let temperatures = crate::log_grid(min, max, points);
+        let bases = temperatures
+            .iter()
+            .map(|&t| RateBasis::new(t))
+            .collect::<Vec<_>>();
+        let total = |library: &Self, reaction: &Reaction, basis: &RateBasis| {
+            library
+                .get(reaction)
+                .map(|s| s.rate_with_basis(basis))
+                .sum::<f64>()
+        };
+
+        let mut comparisons = self
+            .reactions()
+            .filter(|r| other.index.contains_key(*r))
+            .map(|reaction| {
+                let ratios = bases
+                    .iter()
+                    .map(|basis| {
+                        let (a, b) = (total(self, reaction, basis), total(other, reaction, basis));
+                        // two rates of 0 agree
+                        if a == 0.0 && b == 0.0 {
+                            1.0
+                        } else {
+                            b / a
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                let factors = ratios.iter().map(|&r| r.max(1.0 / r));
+                let (max_index, max_factor) = factors
+                    .enumerate()
+                    .max_by(|a, b| a.1.total_cmp(&b.1))
+                    .unwrap_or((0, 1.0));
+                // the geometric mean, since rates can differ by orders of magnitude
+                #[allow(clippy::cast_precision_loss)]
+                let mean_ratio =
+                    (ratios.iter().map(|r| r.ln()).sum::<f64>() / ratios.len() as f64).exp();
+                RateComparison {
+                    reaction: reaction.clone(),
+                    max_factor,
+                    max_temperature: temperatures[max_index],
+                    mean_ratio,
+                }
+            })
+            .collect::<Vec<_>>();
+        comparisons.sort_by(|a, b| {
+            b.max_factor
+                .total_cmp(&a.max_factor)
+                .then_with(|| a.reaction.to_string().cmp(&b.reaction.to_string()))
+        });
+        comparisons
+    }
+}
+
+// how a library is serialized
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+#[serde(rename = "Library")]
+struct SerializeLibrary<'a> {
+    metadata: &'a LibraryMetadata,
+    sets: &'a [Set],
+}
+
+// how a library is deserialized, before the index is rebuilt
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[serde(rename = "Library", deny_unknown_fields)]
+struct DeserializeLibrary {
+    #[serde(default)]
+    metadata: LibraryMetadata,
+    sets: Vec<Set>,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Library {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializeLibrary {
+            metadata: &self.metadata,
+            sets: &self.sets,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Library {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let DeserializeLibrary { metadata, sets } = DeserializeLibrary::deserialize(deserializer)?;
+        Ok(sets.into_iter().collect::<Self>().with_metadata(metadata))
+    }
+}
+
+// the schema of the struct that the library is serialized as
+#[cfg(feature = "schemars")]
+impl JsonSchema for Library {
+    fn schema_name() -> Cow<'static, str> {
+        "Library".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        DeserializeLibrary::json_schema(generator)
+    }
+}
+
+impl FromIterator<Set> for Library {
+    fn from_iter<I: IntoIterator<Item = Set>>(iter: I) -> Self {
+        let mut library = Self::new();
+        library.extend(iter);
+        library
+    }
+}
+
+impl Extend<Set> for Library {
+    fn extend<I: IntoIterator<Item = Set>>(&mut self, iter: I) {
+        for set in iter {
+            self.push(set);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Library {
+    type Item = &'a Set;
+    type IntoIter = std::slice::Iter<'a, Set>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for Library {
+    type Item = Set;
+    type IntoIter = std::vec::IntoIter<Set>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.sets.into_iter()
+    }
+}
+
+// the sets can only be read, since changing them in place would leave the index out of date
+
+impl<I: SliceIndex<[Set]>> Index<I> for Library {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &I::Output {
+        &self.sets[index]
+    }
+}
+
+impl AsRef<[Set]> for Library {
+    fn as_ref(&self) -> &[Set] {
+        &self.sets
+    }
+}
+
+/// How much the total rate of a reaction differs between two libraries, from
+/// [`Library::compare_rates`].
+///
+/// The ratios are of the rate in the second library to the rate in the first.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RateComparison {
+    /// The reaction that is compared.
+    pub reaction: Reaction,
+    /// The largest factor by which the rates differ, in either direction, so that it is at least
+    /// 1 (when the rates are the same), and infinite if only one of them is 0.
+    pub max_factor: f64,
+    /// The temperature (in GK) at which the rates differ by [`max_factor`][Self::max_factor].
+    pub max_temperature: f64,
+    /// The geometric mean of the ratios over the grid.
+    pub mean_ratio: f64,
+}
+
+/// The result of [`Library::diff`].
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct LibraryDiff<'a> {
+    /// The sets that are only in the first library.
+    pub removed: Vec<&'a Set>,
+    /// The sets that are only in the second library.
+    pub added: Vec<&'a Set>,
+}
+
+impl LibraryDiff<'_> {
+    /// Whether the libraries have the same sets.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty()
+    }
+}
+
+/// The result of [`Library::pair_reverses`].
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ReversePairs<'a> {
+    /// The matched reactions, as `(forward, reverse)`.
+    pub pairs: Vec<(Reaction, Reaction)>,
+    /// The reverse sets that have no forward reaction.
+    pub orphans: Vec<&'a Set>,
+}
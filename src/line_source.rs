@@ -0,0 +1,134 @@
+use crate::error::ReaclibError as RError;
+use std::io::BufRead;
+
+/// A source of lines for an [`Iter`][crate::Iter].
+///
+/// This is implemented for every [`BufRead`], and for [`Lines`], which wraps an iterator of lines.
+/// It can be implemented for other sources, such as decompression streams, network chunks, or the
+/// lines of an editor, so that they are parsed by exactly the same rules as a file.
+pub trait LineSource {
+    /// Read the next line into `buf`, replacing what was there, without the line ending.
+    ///
+    /// Returns `None` when there are no more lines.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Some(Err)` if the line can't be read.
+    fn next_line(&mut self, buf: &mut String) -> Option<Result<(), RError>>;
+}
+
+// This is like `BufRead::lines`, without allocating a new `String` for every line, and with the
+// line endings of `read_raw_line`.
+impl<R: BufRead> LineSource for R {
+    fn next_line(&mut self, buf: &mut String) -> Option<Result<(), RError>> {
+        // read into the allocation of `buf`, which is given back whether or not it is valid UTF-8
+        let mut bytes = std::mem::take(buf).into_bytes();
+        bytes.clear();
+        let read = read_raw_line(self, &mut bytes);
+        let line = String::from_utf8(bytes);
+        let result = match (read, line) {
+            (Ok(false), Ok(line)) => {
+                *buf = line;
+                return None;
+            }
+            (Ok(_), Ok(line)) => {
+                *buf = line;
+                trim_end_control(buf);
+                Ok(())
+            }
+            (Err(e), line) => {
+                *buf = line.unwrap_or_default();
+                buf.clear();
+                Err(e.into())
+            }
+            (Ok(_), Err(e)) => {
+                let mut bytes = e.into_bytes();
+                bytes.clear();
+                *buf = String::from_utf8(bytes).unwrap_or_default();
+                Err(std::io::Error::from(std::io::ErrorKind::InvalidData).into())
+            }
+        };
+        Some(result)
+    }
+}
+
+// the line endings are in `reaclib_core`, so that `include_reaclib` splits lines the same way
+pub(crate) use reaclib_core::read_raw_line;
+
+// Remove control characters from the end of a line, with the rule in `reaclib_core`.
+fn trim_end_control(line: &mut String) {
+    let len = reaclib_core::trim_end_control(line).len();
+    line.truncate(len);
+}
+
+/// A [`LineSource`] for an iterator of lines, which shouldn't include the line endings.
+///
+/// As when reading from a [`BufRead`], control characters at the end of each line are removed.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Iter, Lines};
+///
+/// let text = "1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00";
+///
+/// // lines that may fail, e.g. from a stream
+/// let lines = text.lines().map(|l| Ok::<_, std::io::Error>(l.to_string()));
+/// let mut iter = Iter::new(Lines::new(lines), Format::Reaclib2);
+/// assert!(iter.next().unwrap().is_ok());
+///
+/// // lines that are already in memory
+/// let mut iter = Iter::new(Lines::from_strs(text.lines()), Format::Reaclib2);
+/// assert!(iter.next().unwrap().is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Lines<I> {
+    lines: I,
+}
+
+impl<I, S, E> Lines<I>
+where
+    I: Iterator<Item = Result<S, E>>,
+    S: AsRef<str>,
+    E: Into<RError>,
+{
+    /// Creates a new `Lines` from an iterator of lines that may fail.
+    pub const fn new(lines: I) -> Self {
+        Self { lines }
+    }
+}
+
+impl<I, S> Lines<std::iter::Map<I, fn(S) -> Result<S, RError>>>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    /// Creates a new `Lines` from an iterator of lines that can't fail, such as [`str::lines`].
+    pub fn from_strs(lines: I) -> Self {
+        Self {
+            lines: lines.map(Ok),
+        }
+    }
+}
+
+impl<I, S, E> LineSource for Lines<I>
+where
+    I: Iterator<Item = Result<S, E>>,
+    S: AsRef<str>,
+    E: Into<RError>,
+{
+    fn next_line(&mut self, buf: &mut String) -> Option<Result<(), RError>> {
+        buf.clear();
+        match self.lines.next()? {
+            Ok(line) => {
+                buf.push_str(line.as_ref());
+                trim_end_control(buf);
+                Some(Ok(()))
+            }
+            Err(e) => Some(Err(e.into())),
+        }
+    }
+}
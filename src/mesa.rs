@@ -0,0 +1,134 @@
+use crate::{
+    error::ReaclibError as RError, log_grid, writer::format_float, NameMap, Nuclide, Reaction,
+    ReactionRate,
+};
+use std::io::Write;
+
+/// Write the total rate of `rate` as a rate table that can be used by MESA's `rates` module.
+///
+/// The rate is evaluated on a grid of `points` temperatures, logarithmically spaced between
+/// `min` and `max` (in GK), as for [`ReactionRate::curve`].
+/// The table starts with a comment naming the reaction and the number of points, followed by a
+/// line of column names, and then a line for each point with the temperature in units of 10⁸ K
+/// (T8) and the rate.
+/// To use it, the file should be placed in MESA's rate tables directory and listed in its rate
+/// list, with the name given by [`mesa_rate_name`].
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{write_mesa_table, Format, Library};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+/// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+/// let rate = library.reaction_rate(library.reactions().next().unwrap()).unwrap();
+///
+/// let mut table = Vec::new();
+/// write_mesa_table(&mut table, &rate, 0.01, 10.0, 61).unwrap();
+/// let table = String::from_utf8(table).unwrap();
+/// assert_eq!(table.lines().count(), 64);
+/// ```
+///
+/// # Errors
+///
+/// Will return `Err` if there is an io error.
+pub fn write_mesa_table<W: Write>(
+    mut writer: W,
+    rate: &ReactionRate,
+    min: f64,
+    max: f64,
+    points: usize,
+) -> Result<(), RError> {
+    let temperatures = log_grid(min, max, points);
+    writeln!(writer, "! {}", rate.reaction)?;
+    writeln!(writer, "{points:>8}   ! number of lines of data")?;
+    writeln!(writer, "{:>16}{:>16}", "T8", "rate")?;
+    for t in temperatures {
+        writeln!(
+            writer,
+            "{}{}",
+            format_float(10.0 * t, 16, 8),
+            format_float(rate.rate(t), 16, 8)
+        )?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// The name that MESA would use for `reaction`.
+///
+/// Reactions between a nuclide and a light particle (n, p, or α), or a photon, are named like
+/// `r_c12_ag_o16`, with `g` standing for the photon.
+/// Other reactions are named like `r_n13_to_c13`, listing all of the nuclides.
+/// The nuclides have MESA's names, from [`NameMap::mesa`], so that e.g. p + p → d is
+/// `r_h1_pg_h2`.
+///
+/// ```
+/// use reaclib::{mesa_rate_name, Reaction};
+///
+/// let nuclide = |s: &str| s.parse().unwrap();
+/// let reaction = Reaction::new(
+///     [nuclide("he4"), nuclide("c12")].into_iter().collect(),
+///     [nuclide("o16")].into_iter().collect(),
+/// );
+/// assert_eq!(mesa_rate_name(&reaction), "r_c12_ag_o16");
+/// assert_eq!(mesa_rate_name(&reaction.reversed()), "r_o16_ga_c12");
+///
+/// let reaction = Reaction::new(
+///     [nuclide("p"), nuclide("p")].into_iter().collect(),
+///     [nuclide("d")].into_iter().collect(),
+/// );
+/// assert_eq!(mesa_rate_name(&reaction), "r_h1_pg_h2");
+/// ```
+#[must_use]
+pub fn mesa_rate_name(reaction: &Reaction) -> String {
+    let split = |nuclides: &[Nuclide]| match nuclides {
+        [heavy] => Some(('g', *heavy)),
+        [a, b] => match (particle(a), particle(b)) {
+            (Some(p), _) => Some((p, *b)),
+            (None, Some(p)) => Some((p, *a)),
+            (None, None) => None,
+        },
+        _ => None,
+    };
+
+    let names = NameMap::mesa();
+    let join = |nuclides: &[Nuclide]| {
+        nuclides
+            .iter()
+            .map(|n| names.to_other(n))
+            .collect::<Vec<_>>()
+            .join("_")
+    };
+
+    match (split(reaction.reactants()), split(reaction.products())) {
+        (Some((incoming, target)), Some((outgoing, product)))
+            if (incoming, outgoing) != ('g', 'g') =>
+        {
+            let (target, product) = (names.to_other(&target), names.to_other(&product));
+            format!("r_{target}_{incoming}{outgoing}_{product}")
+        }
+        _ => format!(
+            "r_{}_to_{}",
+            join(reaction.reactants()),
+            join(reaction.products())
+        ),
+    }
+}
+
+// the letter used for a light particle in the names of reactions
+fn particle(nuclide: &Nuclide) -> Option<char> {
+    if nuclide.is_neutron() {
+        Some('n')
+    } else if nuclide.is_proton() {
+        Some('p')
+    } else if nuclide.is_alpha() {
+        Some('a')
+    } else {
+        None
+    }
+}
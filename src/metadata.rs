@@ -0,0 +1,53 @@
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Where a [`Library`][crate::Library] came from, and how it was put together.
+///
+/// None of it is needed to use the library, but it is kept with it, so that a curated library
+/// that is stored as a single file (e.g. with the `serde` feature) still records its provenance.
+/// [`Snapshot::parse`][crate::Snapshot::parse] fills it in for a downloaded library.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Library, LibraryMetadata};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+/// let mut metadata = LibraryMetadata::default();
+/// metadata.name = Some("decays".to_string());
+/// metadata.notes.push("only the free neutron decay".to_string());
+///
+/// let library = Library::from_reader(reader, Format::Reaclib2).unwrap().with_metadata(metadata);
+/// assert_eq!(library.metadata().name.as_deref(), Some("decays"));
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct LibraryMetadata {
+    /// The name of the library, such as the snapshot that it was downloaded as.
+    pub name: Option<String>,
+    /// Where the library was read from, such as a URL or a path.
+    pub source: Option<String>,
+    /// The SHA-256 checksum of the data that the library was read from, in hexadecimal.
+    pub sha256: Option<String>,
+    /// When the library was retrieved, in any format.
+    pub retrieved: Option<String>,
+    /// Notes on how the library was curated, such as which sets were changed and why.
+    pub notes: Vec<String>,
+}
+
+impl LibraryMetadata {
+    /// Whether nothing is known about the library.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
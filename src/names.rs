@@ -0,0 +1,143 @@
+use crate::{nuclide::ELEMENTS, Nuclide};
+use std::collections::HashMap;
+
+/// A translation between reaclib nuclide names and the names used by another code.
+///
+/// Names are translated using a table of exceptions, and otherwise with a rule that applies to
+/// every nuclide.
+/// The presets are for the codes whose names differ from reaclib's, and cover the light
+/// particles, which are where codes differ most.
+/// A code that uses the reaclib names needs no preset, and can start from [`new`][Self::new].
+/// More exceptions can be added with [`insert`][Self::insert], for example for isomers like
+/// `al-6` and `al*6`, which have no standard name outside of reaclib.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{NameMap, Nuclide};
+///
+/// let p: Nuclide = "p".parse().unwrap();
+/// let c12: Nuclide = "c12".parse().unwrap();
+///
+/// let mesa = NameMap::mesa();
+/// assert_eq!(mesa.to_other(&p), "h1");
+/// assert_eq!(mesa.to_other(&c12), "c12");
+/// assert_eq!(mesa.from_other("h1"), Some(p));
+///
+/// let nugrid = NameMap::nugrid();
+/// assert_eq!(nugrid.to_other(&c12), "C  12");
+/// assert_eq!(nugrid.from_other("C  12"), Some(c12));
+///
+/// let mut custom = NameMap::new();
+/// let al26 = "al-6".parse().unwrap();
+/// custom.insert(al26, "al26m");
+/// assert_eq!(custom.to_other(&al26), "al26m");
+/// assert_eq!(custom.from_other("al26m"), Some(al26));
+/// ```
+#[derive(Clone, Debug)]
+pub struct NameMap {
+    to_other: HashMap<Nuclide, String>,
+    from_other: HashMap<String, Nuclide>,
+    rule: Rule,
+}
+
+// how names that aren't in the table are translated
+#[derive(Copy, Clone, Debug)]
+enum Rule {
+    // the name is the same as in reaclib
+    Same,
+    // the element symbol in upper case and the mass number, padded to 5 characters
+    UpperPadded,
+}
+
+impl NameMap {
+    /// A map where every name is the same as in reaclib, until exceptions are inserted.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            to_other: HashMap::new(),
+            from_other: HashMap::new(),
+            rule: Rule::Same,
+        }
+    }
+
+    /// The names used by MESA, which names the light particles `neut`, `h1`, `h2`, and `h3`.
+    #[must_use]
+    pub fn mesa() -> Self {
+        Self::new().with(&[("n", "neut"), ("p", "h1"), ("d", "h2"), ("t", "h3")])
+    }
+
+    /// The names used by NuGrid, which are the element symbol in upper case followed by the mass
+    /// number, padded to 5 characters (e.g. `HE  4`), with `NEUT` and `PROT` for the neutron and
+    /// proton.
+    #[must_use]
+    pub fn nugrid() -> Self {
+        let mut map =
+            Self::new().with(&[("n", "NEUT"), ("p", "PROT"), ("d", "H   2"), ("t", "H   3")]);
+        map.rule = Rule::UpperPadded;
+        map
+    }
+
+    /// Add an exception, so that `nuclide` is translated to `other` and back.
+    ///
+    /// This replaces any previous exception for either name.
+    pub fn insert(&mut self, nuclide: Nuclide, other: impl Into<String>) {
+        let other = other.into();
+        if let Some(old) = self.to_other.insert(nuclide, other.clone()) {
+            self.from_other.remove(&old);
+        }
+        if let Some(old) = self.from_other.insert(other, nuclide) {
+            if old != nuclide {
+                self.to_other.remove(&old);
+            }
+        }
+    }
+
+    /// Translate a reaclib name to the other code's name.
+    #[must_use]
+    pub fn to_other(&self, nuclide: &Nuclide) -> String {
+        if let Some(other) = self.to_other.get(nuclide) {
+            return other.clone();
+        }
+        match self.rule {
+            Rule::Same => nuclide.to_string(),
+            Rule::UpperPadded => match nuclide.z_a() {
+                Some((z, a)) => format!("{:<2}{a:>3}", ELEMENTS[usize::from(z)].to_uppercase()),
+                None => nuclide.to_string(),
+            },
+        }
+    }
+
+    /// Translate the other code's name to a reaclib name.
+    ///
+    /// Returns `None` if the name can't be translated into a valid reaclib name.
+    #[must_use]
+    pub fn from_other(&self, other: &str) -> Option<Nuclide> {
+        if let Some(nuclide) = self.from_other.get(other) {
+            return Some(*nuclide);
+        }
+        match self.rule {
+            Rule::Same => other.parse().ok(),
+            Rule::UpperPadded => {
+                let split = other.find(|c: char| c.is_ascii_digit())?;
+                let (symbol, mass) = other.split_at(split);
+                let name = format!("{}{}", symbol.trim().to_lowercase(), mass.trim());
+                name.parse().ok().filter(|n: &Nuclide| n.z_a().is_some())
+            }
+        }
+    }
+
+    fn with(mut self, exceptions: &[(&str, &str)]) -> Self {
+        for (nuclide, other) in exceptions {
+            let nuclide = nuclide.parse().expect("the presets are valid nuclides");
+            self.insert(nuclide, *other);
+        }
+        self
+    }
+}
+
+impl Default for NameMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
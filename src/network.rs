@@ -0,0 +1,116 @@
+use crate::{Nuclide, Set};
+
+/// How a set contributes to the rates of change of the abundances in a network, from
+/// [`Set::contributions`].
+///
+/// The abundances are indexed by their position in the ordering of species that the network
+/// uses.
+/// The molar flux of the set is
+/// `symmetry_factor * ρ^(n-1) * rate * Y[abundances[0]] * ... * Y[abundances[n-1]]`, like
+/// [`Set::molar_rate`], and each `(i, m)` in `changes` adds `m` times the flux to `dY[i]/dt`.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Iter, Nuclide};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"8
+///        he4  he4  he4  c12                  fy05r     7.27500e+00
+/// -9.710520e-01 0.000000e+00-3.706000e+01 2.934930e+01
+/// -1.155070e+02-1.000000e+01-1.333330e+00");
+/// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+///
+/// let species = ["p", "he4", "c12"].map(|n| n.parse::<Nuclide>().unwrap());
+/// let contributions = set.contributions(&species).unwrap();
+/// assert_eq!(contributions.abundances, [1, 1, 1]);
+/// assert_eq!(contributions.changes, [(1, -3), (2, 1)]);
+///
+/// let mut dydt = [0.0; 3];
+/// contributions.apply(set.rate(0.2), 1e5, &[0.0, 0.25, 0.0], &mut dydt);
+/// assert_eq!(dydt[1], -3.0 * dydt[2]);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Contributions {
+    /// The index of the abundance of each reactant, with identical reactants repeated.
+    pub abundances: Vec<usize>,
+    /// The factor accounting for identical reactants. See [`Set::symmetry_factor`].
+    pub symmetry_factor: f64,
+    /// The index of each abundance that changes, with the number of that nuclide that is made by
+    /// the reaction (negative if it is destroyed), sorted by index.
+    ///
+    /// Nuclides that are on both sides equally, so that their abundance doesn't change, are left
+    /// out.
+    pub changes: Vec<(usize, i32)>,
+}
+
+impl Contributions {
+    /// The molar flux (in mol/g/s), with the set's `rate` at some temperature, at `density` (in
+    /// g/cm³), with the molar abundances `abundances` in the order of the network's species.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `abundances` is too short for an index.
+    #[must_use]
+    pub fn flux(&self, rate: f64, density: f64, abundances: &[f64]) -> f64 {
+        // there are very few reactants, so this cast can't fail
+        let n = i32::try_from(self.abundances.len()).unwrap_or(i32::MAX);
+        let abundance = self
+            .abundances
+            .iter()
+            .map(|&i| abundances[i])
+            .product::<f64>();
+        self.symmetry_factor * density.powi(n - 1) * rate * abundance
+    }
+
+    /// Add the contribution of the set to `dydt`, the rates of change of the abundances, with the
+    /// [`flux`][Self::flux] for `rate`, `density`, and `abundances`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `abundances` or `dydt` is too short for an index.
+    pub fn apply(&self, rate: f64, density: f64, abundances: &[f64], dydt: &mut [f64]) {
+        let flux = self.flux(rate, density, abundances);
+        for &(i, m) in &self.changes {
+            dydt[i] += f64::from(m) * flux;
+        }
+    }
+}
+
+impl Set {
+    /// How this set contributes to the rates of change of the abundances of `species`, which is
+    /// the ordering of the abundances in a network.
+    ///
+    /// This is the stoichiometry that is needed to build the right-hand side of a network.
+    /// See [`Contributions`].
+    /// Returns `None` if any reactant or product isn't in `species`.
+    #[must_use]
+    pub fn contributions(&self, species: &[Nuclide]) -> Option<Contributions> {
+        let index = |n: &Nuclide| species.iter().position(|s| s == n);
+        let abundances = self
+            .reactants
+            .iter()
+            .map(index)
+            .collect::<Option<Vec<_>>>()?;
+
+        let mut changes: Vec<(usize, i32)> = Vec::new();
+        let sides = [(-1, &self.reactants), (1, &self.products)];
+        for (sign, nuclides) in sides {
+            for n in nuclides {
+                let i = index(n)?;
+                match changes.iter_mut().find(|(j, _)| *j == i) {
+                    Some((_, m)) => *m += sign,
+                    None => changes.push((i, sign)),
+                }
+            }
+        }
+        changes.retain(|&(_, m)| m != 0);
+        changes.sort_unstable();
+
+        Some(Contributions {
+            abundances,
+            symmetry_factor: self.symmetry_factor(),
+            changes,
+        })
+    }
+}
@@ -0,0 +1,280 @@
+use crate::ParseError;
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
+use arrayvec::ArrayString;
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::{borrow::Borrow, cmp::Ordering, fmt, ops::Deref, str::FromStr};
+
+// element symbols, indexed by Z
+// the neutron is at index 0, and protons, deuterons, and tritons are handled separately
+pub(crate) const ELEMENTS: [&str; 119] = [
+    "n", "h", "he", "li", "be", "b", "c", "n", "o", "f", "ne", "na", "mg", "al", "si", "p", "s",
+    "cl", "ar", "k", "ca", "sc", "ti", "v", "cr", "mn", "fe", "co", "ni", "cu", "zn", "ga", "ge",
+    "as", "se", "br", "kr", "rb", "sr", "y", "zr", "nb", "mo", "tc", "ru", "rh", "pd", "ag", "cd",
+    "in", "sn", "sb", "te", "i", "xe", "cs", "ba", "la", "ce", "pr", "nd", "pm", "sm", "eu", "gd",
+    "tb", "dy", "ho", "er", "tm", "yb", "lu", "hf", "ta", "w", "re", "os", "ir", "pt", "au", "hg",
+    "tl", "pb", "bi", "po", "at", "rn", "fr", "ra", "ac", "th", "pa", "u", "np", "pu", "am", "cm",
+    "bk", "cf", "es", "fm", "md", "no", "lr", "rf", "db", "sg", "bh", "hs", "mt", "ds", "rg", "cn",
+    "nh", "fl", "mc", "lv", "ts", "og",
+];
+
+/// Get the proton number (Z) and mass number (A) of a nuclide from its reaclib name.
+///
+/// Besides the usual element symbol followed by a mass number (e.g. `he4`), the special names
+/// `n`, `p`, `d`, and `t` are understood, as are the aluminium-26 states `al-6` and `al*6`.
+/// Returns `None` if the name isn't understood.
+///
+/// ```
+/// use reaclib::nuclide_z_a;
+///
+/// assert_eq!(nuclide_z_a("he4"), Some((2, 4)));
+/// assert_eq!(nuclide_z_a("p"), Some((1, 1)));
+/// assert_eq!(nuclide_z_a("al*6"), Some((13, 26)));
+/// assert_eq!(nuclide_z_a("xx12"), None);
+/// ```
+#[must_use]
+pub fn nuclide_z_a(name: &str) -> Option<(u8, u16)> {
+    let name = name.trim();
+    match name {
+        "n" => return Some((0, 1)),
+        "p" => return Some((1, 1)),
+        "d" => return Some((1, 2)),
+        "t" => return Some((1, 3)),
+        "al-6" | "al*6" => return Some((13, 26)),
+        _ => {}
+    }
+
+    let split = name.find(|c: char| c.is_ascii_digit())?;
+    let (symbol, mass) = name.split_at(split);
+    // the neutron has no mass number in its name
+    let z = ELEMENTS.iter().skip(1).position(|&e| e == symbol)? + 1;
+    let a = mass.parse().ok().filter(|&a| a != 0)?;
+
+    Some((u8::try_from(z).ok()?, a))
+}
+
+/// A type that represents a nuclide, by its reaclib name.
+///
+/// Names are at most 6 bytes long, and are stored without any padding.
+/// Standard reaclib files only have room for 5 bytes, but some extended libraries have longer
+/// names, which can be read with [`IterBuilder::species_width`][crate::IterBuilder::species_width].
+/// It dereferences to [`str`], so it can be used like a string.
+///
+/// With the `serde` feature, names are trimmed and lowercased when deserialized, so that data from
+/// other tools with e.g. `"   he4"` or `"C12"` can be read.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::Nuclide;
+///
+/// let alpha: Nuclide = "he4".parse().unwrap();
+/// assert!(alpha.is_alpha());
+/// assert_eq!(alpha.z(), Some(2));
+/// assert_eq!(alpha.a(), Some(4));
+/// assert_eq!(alpha.to_string(), "he4");
+/// assert_eq!(&*alpha, "he4");
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(transparent))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct Nuclide(ArrayString<6>);
+
+impl Nuclide {
+    /// The name of the nuclide.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The proton number (Z) and mass number (A) of the nuclide.
+    ///
+    /// See [`nuclide_z_a`] for which names are understood.
+    #[must_use]
+    pub fn z_a(&self) -> Option<(u8, u16)> {
+        nuclide_z_a(self)
+    }
+
+    /// The proton number (Z) of the nuclide.
+    #[must_use]
+    pub fn z(&self) -> Option<u8> {
+        self.z_a().map(|(z, _)| z)
+    }
+
+    /// The mass number (A) of the nuclide.
+    #[must_use]
+    pub fn a(&self) -> Option<u16> {
+        self.z_a().map(|(_, a)| a)
+    }
+
+    /// Whether this is a neutron.
+    #[must_use]
+    pub fn is_neutron(&self) -> bool {
+        self.z_a() == Some((0, 1))
+    }
+
+    /// Whether this is a proton.
+    #[must_use]
+    pub fn is_proton(&self) -> bool {
+        self.z_a() == Some((1, 1))
+    }
+
+    /// Whether this is an alpha particle.
+    #[must_use]
+    pub fn is_alpha(&self) -> bool {
+        self.z_a() == Some((2, 4))
+    }
+
+    /// Whether the name is well-formed: either one of the special names understood by
+    /// [`nuclide_z_a`], or an element symbol followed by a plausible mass number.
+    ///
+    /// A mass number is plausible if it has no leading zeros and is between Z and 3Z + 20, which
+    /// covers every nuclide from the proton to the neutron drip line.
+    /// This catches names that come from a corrupted file, such as one with shifted columns.
+    ///
+    /// ```
+    /// use reaclib::Nuclide;
+    ///
+    /// let well_formed = |s: &str| s.parse::<Nuclide>().unwrap().is_well_formed();
+    /// assert!(well_formed("he4"));
+    /// assert!(well_formed("al*6"));
+    /// assert!(!well_formed("e4"));
+    /// assert!(!well_formed("c1"));
+    /// assert!(!well_formed("c012"));
+    /// assert!(!well_formed("he42"));
+    /// ```
+    #[must_use]
+    pub fn is_well_formed(&self) -> bool {
+        match self.as_str() {
+            "n" | "p" | "d" | "t" | "al-6" | "al*6" => true,
+            name => match (self.z_a(), name.find(|c: char| c.is_ascii_digit())) {
+                (Some((z, a)), Some(i)) if !name[i..].starts_with('0') => {
+                    let z = u16::from(z);
+                    (z..=3 * z + 20).contains(&a)
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Compare nuclides by Z, then A, then isomeric state, with the ground state first.
+    ///
+    /// Nuclides whose Z and A can't be determined come after all others, and are compared by
+    /// name.
+    /// This is the ordering used by [`Reaction::canonical`][crate::Reaction::canonical].
+    ///
+    /// ```
+    /// use reaclib::Nuclide;
+    ///
+    /// let mut nuclides = ["he4", "al*6", "p", "al-6", "n"].map(|n| n.parse::<Nuclide>().unwrap());
+    /// nuclides.sort_by(Nuclide::canonical_cmp);
+    /// assert_eq!(nuclides.map(|n| n.to_string()), ["n", "p", "he4", "al-6", "al*6"]);
+    /// ```
+    #[must_use]
+    pub fn canonical_cmp(&self, other: &Self) -> Ordering {
+        // only `al*6` is marked as an isomer
+        let key = |n: &Self| (n.z_a().is_none(), n.z_a(), n.contains('*'), n.0);
+        key(self).cmp(&key(other))
+    }
+
+    // used when parsing, where the field is never longer than the capacity
+    pub(crate) fn from_field(s: &str) -> Self {
+        Self(ArrayString::from(s).expect("the range is at most 6 and the capacity is 6"))
+    }
+}
+
+impl FromStr for Nuclide {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ArrayString::from(s)
+            .map(Self)
+            .map_err(|_| ParseError::NuclideTooLong(s.to_string()))
+    }
+}
+
+// names from other tools are often padded or uppercase, so they are trimmed and lowercased
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Nuclide {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.trim()
+            .to_ascii_lowercase()
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl TryFrom<&str> for Nuclide {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Nuclide> for ArrayString<6> {
+    fn from(n: Nuclide) -> Self {
+        n.0
+    }
+}
+
+impl From<ArrayString<6>> for Nuclide {
+    fn from(s: ArrayString<6>) -> Self {
+        Self(s)
+    }
+}
+
+impl From<ArrayString<5>> for Nuclide {
+    fn from(s: ArrayString<5>) -> Self {
+        Self(ArrayString::from(&s).expect("the capacity is larger"))
+    }
+}
+
+impl Deref for Nuclide {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Nuclide {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Nuclide {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Nuclide {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Nuclide {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for Nuclide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for Nuclide {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        crate::arbitrary_array_string(u).map(Self)
+    }
+}
@@ -0,0 +1,395 @@
+use crate::{
+    error::ReaclibError as RError, Library, Nuclide, ParseError, RateBasis, Reaction, ReactionRate,
+    Resonance, Set, Species,
+};
+use arrayvec::ArrayString;
+use std::{
+    cmp::Ordering,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+// The packed layout is made up of three parts, with all numbers in little endian:
+//
+// * a header of `HEADER_SIZE` bytes: `MAGIC`, `VERSION` (u32), the number of sets (u32), the
+//   number of reactions (u32), and 4 reserved bytes
+// * a table of sets, each `RECORD_SIZE` bytes, with the sets of each reaction next to each other
+// * an index with an entry of `ENTRY_SIZE` bytes for each reaction: the position of its first set
+//   in the table (u32) and its number of sets (u32)
+//
+// The index is sorted by the nuclide names of each reaction (the first `KEY_SIZE` bytes of its
+// sets), so that a reaction can be found with a binary search.
+//
+// Each set is laid out as:
+//
+// * 0..20: the reactants, as 4 names of 5 bytes, padded with zeros
+// * 20..40: the products, in the same way
+// * 40..44: the label, padded with zeros
+// * 44: the resonance (0: non-resonant, 1: resonant, 2: weak, 3: s-process)
+// * 45: whether the set is a reverse rate (0 or 1)
+// * 46..48: reserved
+// * 48..56: the Q-value (f64)
+// * 56..112: the parameters (7 f64s)
+
+// the start of every packed file
+const MAGIC: [u8; 8] = *b"reaclibp";
+// this must be changed whenever the layout changes
+const VERSION: u32 = 1;
+const HEADER_SIZE: usize = 24;
+const RECORD_SIZE: usize = 112;
+const ENTRY_SIZE: usize = 8;
+const NAME_SIZE: usize = 5;
+const KEY_SIZE: usize = 8 * NAME_SIZE;
+
+/// A library in a packed binary layout, which can be queried without reading all of it.
+///
+/// The layout has a table of fixed-size records, one for each set, and an index of reactions
+/// sorted so that they can be found with a binary search.
+/// Only the records of the reactions that are looked up are decoded, so the data can be a memory
+/// map of a large file (for example from the [memmap2](https://crates.io/crates/memmap2) crate),
+/// and only the parts that are used will be read from disk.
+/// Packed data is written with [`Library::write_packed`].
+///
+/// Only the fields that are in the reaclib format are stored, so
+/// [`raw_params`][Set::raw_params], [`raw_lines`][Set::raw_lines], and
+/// [`validity`][Set::validity] are always `None` in the sets that are read back.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Library, PackedLibrary};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+/// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+///
+/// let mut data = Vec::new();
+/// library.write_packed(&mut data).unwrap();
+///
+/// let packed = PackedLibrary::new(data).unwrap();
+/// let reaction = "n -> p".parse().unwrap();
+/// assert_eq!(packed.len(), 1);
+/// assert_eq!(packed.rate(&reaction, 1.0), Some(library.sets()[0].rate(1.0)));
+/// assert_eq!(packed.get(&reaction).unwrap(), library.sets());
+/// ```
+#[derive(Clone, Debug)]
+pub struct PackedLibrary<B> {
+    data: B,
+    sets: usize,
+    reactions: usize,
+}
+
+impl<B: AsRef<[u8]>> PackedLibrary<B> {
+    /// Creates a view of the packed library in `data`.
+    ///
+    /// Only the header and the index are checked, so this is fast even for large libraries.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `data` isn't a packed library, or if it was written with a different
+    /// format version.
+    pub fn new(data: B) -> Result<Self, RError> {
+        let bytes = data.as_ref();
+        let header = bytes.get(..HEADER_SIZE).ok_or(RError::InvalidPacked)?;
+        if header[..8] != MAGIC {
+            return Err(RError::InvalidPacked);
+        }
+        let version = read_u32(header, 8);
+        if version != VERSION {
+            return Err(RError::PackedVersion(version));
+        }
+        let sets = read_u32(header, 12) as usize;
+        let reactions = read_u32(header, 16) as usize;
+
+        let len = sets
+            .checked_mul(RECORD_SIZE)
+            .zip(reactions.checked_mul(ENTRY_SIZE))
+            .and_then(|(s, r)| s.checked_add(r))
+            .and_then(|l| l.checked_add(HEADER_SIZE));
+        if len != Some(bytes.len()) {
+            return Err(RError::InvalidPacked);
+        }
+
+        let packed = Self {
+            data,
+            sets,
+            reactions,
+        };
+        if (0..reactions).any(|i| packed.entry(i).1.end > sets) {
+            return Err(RError::InvalidPacked);
+        }
+        Ok(packed)
+    }
+
+    /// The number of sets in the library.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sets
+    }
+
+    /// Whether the library has no sets.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sets == 0
+    }
+
+    /// The number of distinct reactions in the library.
+    #[must_use]
+    pub fn reaction_count(&self) -> usize {
+        self.reactions
+    }
+
+    /// The sets that make up `reaction`.
+    ///
+    /// If the reaction isn't in the library, the list will be empty.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the records of the sets are invalid.
+    pub fn get(&self, reaction: &Reaction) -> Result<Vec<Set>, RError> {
+        self.find(reaction)
+            .into_iter()
+            .flatten()
+            .map(|i| decode_set(self.record(i)))
+            .collect()
+    }
+
+    /// The total rate of `reaction`, made up of all of its sets.
+    ///
+    /// Returns `Ok(None)` if the reaction isn't in the library.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the records of the sets are invalid.
+    pub fn reaction_rate(&self, reaction: &Reaction) -> Result<Option<ReactionRate>, RError> {
+        let sets = self.get(reaction)?;
+        Ok((!sets.is_empty()).then(|| ReactionRate {
+            reaction: reaction.clone(),
+            sets,
+        }))
+    }
+
+    /// Calculate the total rate of `reaction` at `temperature` (in GK), reading the parameters
+    /// directly from the data.
+    ///
+    /// Returns `None` if the reaction isn't in the library.
+    #[must_use]
+    pub fn rate(&self, reaction: &Reaction, temperature: f64) -> Option<f64> {
+        let basis = RateBasis::new(temperature);
+        self.find(reaction).map(|sets| {
+            sets.map(|i| basis.dot(&read_params(self.record(i))).exp())
+                .sum()
+        })
+    }
+
+    /// An iterator over all of the sets, grouped by reaction.
+    pub fn sets(&self) -> impl Iterator<Item = Result<Set, RError>> + '_ {
+        (0..self.sets).map(|i| decode_set(self.record(i)))
+    }
+
+    /// Decode all of the sets into a [`Library`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if any of the records are invalid.
+    pub fn to_library(&self) -> Result<Library, RError> {
+        self.sets().collect()
+    }
+
+    fn record(&self, i: usize) -> &[u8] {
+        let start = HEADER_SIZE + i * RECORD_SIZE;
+        &self.data.as_ref()[start..start + RECORD_SIZE]
+    }
+
+    // the key and the range of sets of the `i`th reaction in the index
+    fn entry(&self, i: usize) -> (&[u8], std::ops::Range<usize>) {
+        let bytes = self.data.as_ref();
+        let entry = HEADER_SIZE + self.sets * RECORD_SIZE + i * ENTRY_SIZE;
+        let start = read_u32(bytes, entry) as usize;
+        let count = read_u32(bytes, entry + 4) as usize;
+        let key = bytes
+            .get(HEADER_SIZE + start * RECORD_SIZE..)
+            .and_then(|b| b.get(..KEY_SIZE))
+            .unwrap_or_default();
+        (key, start..start + count)
+    }
+
+    // the range of sets of `reaction`, found with a binary search of the index
+    fn find(&self, reaction: &Reaction) -> Option<std::ops::Range<usize>> {
+        let key = encode_key(reaction);
+        let (mut low, mut high) = (0, self.reactions);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let (mid_key, sets) = self.entry(mid);
+            match mid_key.cmp(&key[..]) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Some(sets),
+            }
+        }
+        None
+    }
+}
+
+impl Library {
+    /// Write the library to `writer` in the packed layout read by [`PackedLibrary`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error, if the library is too large for the layout, or if
+    /// a reaction has more nuclides (more than 4 on either side, or names longer than 5 bytes) than
+    /// the layout has room for.
+    pub fn write_packed<W: Write>(&self, mut writer: W) -> Result<(), RError> {
+        // there are only 4 slots for the reactants or products of a reaction
+        if let Some(r) = self
+            .reactions()
+            .find(|r| r.reactants().len() > 4 || r.products().len() > 4)
+        {
+            return Err(ParseError::InvalidReaction(r.to_string()).into());
+        }
+        if let Some(n) = self
+            .reactions()
+            .flat_map(|r| r.reactants().iter().chain(r.products()))
+            .find(|n| n.len() > NAME_SIZE)
+        {
+            return Err(ParseError::NuclideTooLong(n.to_string()).into());
+        }
+        let mut reactions = self
+            .reactions()
+            .map(|r| (encode_key(r), r))
+            .collect::<Vec<_>>();
+        reactions.sort_unstable_by_key(|&(key, _)| key);
+
+        let to_u32 = |n: usize| u32::try_from(n).map_err(|_| RError::InvalidPacked);
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&to_u32(self.sets().len())?.to_le_bytes())?;
+        writer.write_all(&to_u32(reactions.len())?.to_le_bytes())?;
+        writer.write_all(&[0; 4])?;
+
+        let mut index = Vec::with_capacity(reactions.len() * ENTRY_SIZE);
+        let mut written = 0;
+        for (_, reaction) in &reactions {
+            let start = written;
+            for set in self.get(reaction) {
+                writer.write_all(&encode_set(set))?;
+                written += 1;
+            }
+            index.extend_from_slice(&to_u32(start)?.to_le_bytes());
+            index.extend_from_slice(&to_u32(written - start)?.to_le_bytes());
+        }
+        writer.write_all(&index)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Save the library to a packed file at `path`.
+    ///
+    /// See [`write_packed`][Self::write_packed].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error, or if the library is too large for the layout.
+    pub fn save_packed(&self, path: impl AsRef<Path>) -> Result<(), RError> {
+        self.write_packed(BufWriter::new(File::create(path)?))
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(
+        bytes[offset..offset + 4]
+            .try_into()
+            .expect("the slice has 4 bytes"),
+    )
+}
+
+fn read_f64(bytes: &[u8], offset: usize) -> f64 {
+    f64::from_le_bytes(
+        bytes[offset..offset + 8]
+            .try_into()
+            .expect("the slice has 8 bytes"),
+    )
+}
+
+fn read_params(record: &[u8]) -> [f64; 7] {
+    std::array::from_fn(|i| read_f64(record, 56 + 8 * i))
+}
+
+// the names of the nuclides in a reaction
+fn encode_key(reaction: &Reaction) -> [u8; KEY_SIZE] {
+    let mut key = [0; KEY_SIZE];
+    let slots = reaction.reactants().iter().enumerate().chain(
+        reaction
+            .products()
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (i + 4, n)),
+    );
+    for (slot, nuclide) in slots {
+        let name = nuclide.as_bytes();
+        key[slot * NAME_SIZE..][..name.len()].copy_from_slice(name);
+    }
+    key
+}
+
+fn encode_set(set: &Set) -> [u8; RECORD_SIZE] {
+    let mut record = [0; RECORD_SIZE];
+    record[..KEY_SIZE].copy_from_slice(&encode_key(&Reaction::from(set)));
+    record[40..40 + set.label.len()].copy_from_slice(set.label.as_bytes());
+    record[44] = match set.resonance {
+        Resonance::NonResonant => 0,
+        Resonance::Resonant => 1,
+        Resonance::Weak => 2,
+        Resonance::S => 3,
+    };
+    record[45] = u8::from(set.reverse);
+    record[48..56].copy_from_slice(&set.q_value.to_le_bytes());
+    for (i, p) in set.params.iter().enumerate() {
+        record[56 + 8 * i..64 + 8 * i].copy_from_slice(&p.to_le_bytes());
+    }
+    record
+}
+
+fn decode_set(record: &[u8]) -> Result<Set, RError> {
+    let nuclides = |slots: &[u8]| {
+        slots
+            .chunks(NAME_SIZE)
+            .filter(|name| name[0] != 0)
+            .map(|name| decode_str(name).map(Nuclide::from_field))
+            .collect::<Result<Species, _>>()
+    };
+
+    Ok(Set {
+        reactants: nuclides(&record[..20])?,
+        products: nuclides(&record[20..40])?,
+        label: ArrayString::from(decode_str(&record[40..44])?)
+            .map_err(|_| RError::InvalidPacked)?,
+        resonance: match record[44] {
+            0 => Resonance::NonResonant,
+            1 => Resonance::Resonant,
+            2 => Resonance::Weak,
+            3 => Resonance::S,
+            _ => return Err(RError::InvalidPacked),
+        },
+        reverse: match record[45] {
+            0 => false,
+            1 => true,
+            _ => return Err(RError::InvalidPacked),
+        },
+        q_value: read_f64(record, 48),
+        params: read_params(record),
+        raw_params: None,
+        raw_lines: None,
+        validity: None,
+    })
+}
+
+// a string that is padded with zeros
+fn decode_str(bytes: &[u8]) -> Result<&str, RError> {
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    std::str::from_utf8(&bytes[..len]).map_err(|_| RError::InvalidPacked)
+}
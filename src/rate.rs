@@ -0,0 +1,815 @@
+use crate::{
+    error::ReaclibError as RError, nuclide_z_a, Nuclide, ParseError, Reaction, Resonance, Set,
+    SetError,
+};
+use arrayvec::ArrayString;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The temperature-dependent basis functions of a reaclib rate, evaluated at one temperature.
+///
+/// The logarithm of a rate is a linear combination of these functions, with the set's
+/// parameters as the coefficients.
+/// Since the basis is the same for every set, it can be calculated once and reused with
+/// [`Set::rate_with_basis`] and [`ReactionRate::rate_with_basis`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RateBasis {
+    temperature: f64,
+    values: [f64; 7],
+}
+
+impl RateBasis {
+    /// Evaluate the basis functions at `temperature` (in GK).
+    #[must_use]
+    pub fn new(temperature: f64) -> Self {
+        let t13 = temperature.cbrt();
+        let values = [
+            1.0,
+            temperature.recip(),
+            t13.recip(),
+            t13,
+            temperature,
+            temperature * t13 * t13,
+            temperature.ln(),
+        ];
+        Self {
+            temperature,
+            values,
+        }
+    }
+
+    /// The temperature that the basis was evaluated at.
+    #[must_use]
+    pub const fn temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    /// The values of the basis functions: 1, T⁻¹, T⁻¹ᐟ³, T¹ᐟ³, T, T⁵ᐟ³, and ln T.
+    #[must_use]
+    pub const fn values(&self) -> &[f64; 7] {
+        &self.values
+    }
+
+    // the logarithm of the rate with parameters `params`
+    pub(crate) fn dot(&self, params: &[f64; 7]) -> f64 {
+        params
+            .iter()
+            .zip(&self.values)
+            .fold(0.0, |acc, (p, b)| p.mul_add(*b, acc))
+    }
+
+    // the derivative of the logarithm of the rate with respect to ln T
+    pub(crate) fn log_derivative(&self, params: &[f64; 7]) -> f64 {
+        const FACTORS: [f64; 7] = [0.0, -1.0, -1.0 / 3.0, 1.0 / 3.0, 1.0, 5.0 / 3.0, 0.0];
+        FACTORS
+            .iter()
+            .zip(params)
+            .zip(&self.values)
+            .fold(params[6], |acc, ((f, p), b)| (f * p).mul_add(*b, acc))
+    }
+
+    // the second derivative of the logarithm of the rate with respect to ln T
+    pub(crate) fn log_second_derivative(&self, params: &[f64; 7]) -> f64 {
+        const FACTORS: [f64; 7] = [0.0, 1.0, 1.0 / 9.0, 1.0 / 9.0, 1.0, 25.0 / 9.0, 0.0];
+        FACTORS
+            .iter()
+            .zip(params)
+            .zip(&self.values)
+            .fold(0.0, |acc, ((f, p), b)| (f * p).mul_add(*b, acc))
+    }
+}
+
+// the same as `RateBasis::new(temperature).dot(params)`, but for a generic float
+#[cfg(feature = "num-traits")]
+pub(crate) fn generic_ln_rate<T: num_traits::Float>(params: &[f64; 7], temperature: T) -> T {
+    let t13 = temperature.cbrt();
+    let basis = [
+        T::one(),
+        temperature.recip(),
+        t13.recip(),
+        t13,
+        temperature,
+        temperature * t13 * t13,
+        temperature.ln(),
+    ];
+    params.iter().zip(basis).fold(T::zero(), |acc, (p, b)| {
+        T::from(*p).unwrap_or_else(T::nan).mul_add(b, acc)
+    })
+}
+
+/// A plasma screening correction that can be applied when evaluating a rate.
+///
+/// The factor is multiplied into the rate for each pair of charged particles that come together.
+/// For reactions with more than two reactants, the particles are combined one at a time, so the
+/// factors for (Z1, Z2), then (Z1 + Z2, Z3), and so on are all applied.
+///
+/// This is implemented for closures taking `(temperature, density, z1, z2)`.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Iter};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"4
+///          p  c12  n13                       nacrn     1.94400e+00
+///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+///  4.443620e+00-3.158980e+00-6.666670e-01");
+/// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+///
+/// let screening = |_t: f64, _rho: f64, z1: u8, z2: u8| 1.0 + 0.01 * f64::from(z1 * z2);
+/// let screened = set.screened_rate(1.0, 1.0e4, &screening).unwrap();
+/// assert!((screened / set.rate(1.0) - 1.06).abs() < 1e-12);
+/// ```
+pub trait Screening {
+    /// The factor multiplying the rate at `temperature` and `density` for two particles with
+    /// charges `z1` and `z2`.
+    fn factor(&self, temperature: f64, density: f64, z1: u8, z2: u8) -> f64;
+}
+
+impl<F: Fn(f64, f64, u8, u8) -> f64> Screening for F {
+    fn factor(&self, temperature: f64, density: f64, z1: u8, z2: u8) -> f64 {
+        self(temperature, density, z1, z2)
+    }
+}
+
+// the combined screening factor for the entrance channel made up of `reactants`
+pub(crate) fn screening_factor<S: Screening + ?Sized>(
+    reactants: &[Nuclide],
+    temperature: f64,
+    density: f64,
+    screening: &S,
+) -> Result<f64, RError> {
+    let mut charges = reactants.iter().map(|n| {
+        nuclide_z_a(n)
+            .map(|(z, _)| z)
+            .ok_or_else(|| ParseError::UnknownNuclide(n.to_string()))
+    });
+
+    let mut factor = 1.0;
+    if let Some(z) = charges.next() {
+        let mut z_total = z?;
+        for z in charges {
+            let z = z?;
+            factor *= screening.factor(temperature, density, z_total, z);
+            z_total = z_total.saturating_add(z);
+        }
+    }
+    Ok(factor)
+}
+
+/// A source of a temperature-dependent rate.
+///
+/// This allows code to be generic over where a rate comes from, whether that is a single
+/// [`Set`], a summed [`ReactionRate`], a compiled [`Rate`], a
+/// [`TabulatedRate`][crate::TabulatedRate], or a user-defined type.
+/// Only [`ln_rate`][Self::ln_rate] needs to be implemented.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::RateEvaluator;
+///
+/// // a rate that goes as a power of the temperature
+/// struct PowerLaw(f64, f64);
+///
+/// impl RateEvaluator for PowerLaw {
+///     fn ln_rate(&self, temperature: f64) -> f64 {
+///         self.0.ln() + self.1 * temperature.ln()
+///     }
+/// }
+///
+/// fn timescale(rate: &impl RateEvaluator, temperature: f64) -> f64 {
+///     1.0 / rate.rate(temperature)
+/// }
+///
+/// assert!((timescale(&PowerLaw(2.0, 3.0), 2.0) - 1.0 / 16.0).abs() < 1e-12);
+/// ```
+pub trait RateEvaluator {
+    /// Calculate the natural logarithm of the rate at `temperature` (in GK).
+    fn ln_rate(&self, temperature: f64) -> f64;
+
+    /// Calculate the rate at `temperature` (in GK).
+    fn rate(&self, temperature: f64) -> f64 {
+        self.ln_rate(temperature).exp()
+    }
+}
+
+impl<T: RateEvaluator + ?Sized> RateEvaluator for &T {
+    fn ln_rate(&self, temperature: f64) -> f64 {
+        (**self).ln_rate(temperature)
+    }
+
+    fn rate(&self, temperature: f64) -> f64 {
+        (**self).rate(temperature)
+    }
+}
+
+impl<T: RateEvaluator + ?Sized> RateEvaluator for Box<T> {
+    fn ln_rate(&self, temperature: f64) -> f64 {
+        (**self).ln_rate(temperature)
+    }
+
+    fn rate(&self, temperature: f64) -> f64 {
+        (**self).rate(temperature)
+    }
+}
+
+impl RateEvaluator for Set {
+    fn ln_rate(&self, temperature: f64) -> f64 {
+        RateBasis::new(temperature).dot(&self.params)
+    }
+
+    fn rate(&self, temperature: f64) -> f64 {
+        Self::rate(self, temperature)
+    }
+}
+
+impl RateEvaluator for ReactionRate {
+    fn ln_rate(&self, temperature: f64) -> f64 {
+        Self::rate(self, temperature).ln()
+    }
+
+    fn rate(&self, temperature: f64) -> f64 {
+        Self::rate(self, temperature)
+    }
+}
+
+impl RateEvaluator for Rate {
+    fn ln_rate(&self, temperature: f64) -> f64 {
+        Self::rate(self, temperature).ln()
+    }
+
+    fn rate(&self, temperature: f64) -> f64 {
+        Self::rate(self, temperature)
+    }
+}
+
+/// A reaction rate made up of the sum of all of the [`Set`]s for a reaction.
+///
+/// It can be obtained from a [`Library`][crate::Library] with
+/// [`reaction_rate`][crate::Library::reaction_rate].
+#[derive(Clone, PartialEq, Debug)]
+pub struct ReactionRate {
+    /// The reaction that this rate describes.
+    pub reaction: Reaction,
+    /// The sets whose rates are summed to get the total rate.
+    pub sets: Vec<Set>,
+}
+
+impl ReactionRate {
+    /// Calculate the total rate by summing the rate of each set.
+    #[must_use]
+    pub fn rate(&self, temperature: f64) -> f64 {
+        self.rate_with_basis(&RateBasis::new(temperature))
+    }
+
+    /// Calculate the total rate, checking that the temperature is valid and the result is finite.
+    ///
+    /// See [`Set::checked_rate`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `temperature` isn't positive (including if it is NaN), or if the rate
+    /// isn't finite.
+    pub fn checked_rate(&self, temperature: f64) -> Result<f64, RError> {
+        check_rate(temperature, |t| self.rate(t))
+    }
+
+    /// Calculate the total rate, using a generic floating point type.
+    ///
+    /// See [`Set::rate_generic`].
+    #[cfg(feature = "num-traits")]
+    #[must_use]
+    pub fn rate_generic<T: num_traits::Float>(&self, temperature: T) -> T {
+        self.sets
+            .iter()
+            .map(|s| generic_ln_rate(&s.params, temperature).exp())
+            .fold(T::zero(), |acc, r| acc + r)
+    }
+
+    /// Calculate the molar flux (in mol/g/s) of the reaction, summed over its sets, at
+    /// `temperature` for a composition at `density` (in g/cm³) with the molar abundances
+    /// `abundances`.
+    ///
+    /// This is the rate at which each reactant is destroyed and each product made (for each time
+    /// it appears), so comparing it between reactions shows which ones dominate under those
+    /// conditions.
+    /// See [`Set::molar_rate`] for how the flux of each set is calculated, and
+    /// [`RatePair::net_flux`][crate::RatePair::net_flux] for the flux net of the reverse
+    /// reaction.
+    ///
+    /// ```
+    /// use reaclib::{Format, Library, Nuclide};
+    /// use std::{collections::HashMap, io::Cursor};
+    ///
+    /// let reader = Cursor::new(r"4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01
+    /// 4
+    ///          p  c12  n13                       nacrr     1.94400e+00
+    ///  1.754280e+01-3.778490e+00-5.107350e+00-2.241110e+00
+    ///  1.488830e-01 0.000000e+00-1.500000e+00");
+    /// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+    /// let rate = library.reaction_rate(&"c12(p,g)n13".parse().unwrap()).unwrap();
+    ///
+    /// let abundances = HashMap::from([
+    ///     ("p".parse::<Nuclide>().unwrap(), 0.7),
+    ///     ("c12".parse::<Nuclide>().unwrap(), 0.001),
+    /// ]);
+    /// let flux = rate.flux(100.0, 0.02, &abundances);
+    /// assert!((flux / (100.0 * rate.rate(0.02) * 0.7 * 0.001) - 1.0).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn flux(&self, density: f64, temperature: f64, abundances: &HashMap<Nuclide, f64>) -> f64 {
+        self.sets
+            .iter()
+            .map(|s| s.molar_rate(density, temperature, abundances))
+            .sum()
+    }
+
+    /// Calculate the total rate using basis functions that have already been evaluated.
+    ///
+    /// See [`Set::rate_with_basis`].
+    #[must_use]
+    pub fn rate_with_basis(&self, basis: &RateBasis) -> f64 {
+        self.sets.iter().map(|s| s.rate_with_basis(basis)).sum()
+    }
+
+    /// Calculate the logarithmic derivative of the total rate, `d(ln rate) / d(ln T)`.
+    ///
+    /// This is the average of each set's [`log_derivative`][Set::log_derivative], weighted by
+    /// the set's rate.
+    /// Returns NaN if the total rate is 0.
+    #[must_use]
+    pub fn log_derivative(&self, temperature: f64) -> f64 {
+        let basis = RateBasis::new(temperature);
+        let (sum, weighted) = self.sets.iter().fold((0.0, 0.0), |(sum, weighted), s| {
+            let rate = s.rate_with_basis(&basis);
+            (
+                sum + rate,
+                rate.mul_add(basis.log_derivative(&s.params), weighted),
+            )
+        });
+        weighted / sum
+    }
+
+    /// Calculate the second logarithmic derivative of the total rate,
+    /// `d²(ln rate) / d(ln T)²`.
+    ///
+    /// Returns NaN if the total rate is 0.
+    #[must_use]
+    pub fn log_second_derivative(&self, temperature: f64) -> f64 {
+        let basis = RateBasis::new(temperature);
+        let (mut sum, mut first, mut second) = (0.0, 0.0, 0.0);
+        for s in &self.sets {
+            let rate = s.rate_with_basis(&basis);
+            let d = basis.log_derivative(&s.params);
+            sum += rate;
+            first = rate.mul_add(d, first);
+            second = rate.mul_add(d.mul_add(d, basis.log_second_derivative(&s.params)), second);
+        }
+        let first = first / sum;
+        first.mul_add(-first, second / sum)
+    }
+
+    /// Calculate the total rate, multiplied by the screening factor at `temperature` and
+    /// `density`.
+    ///
+    /// See [`Screening`] for how the factor is applied.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the charge of a reactant can't be determined from its name.
+    pub fn screened_rate<S: Screening + ?Sized>(
+        &self,
+        temperature: f64,
+        density: f64,
+        screening: &S,
+    ) -> Result<f64, RError> {
+        let factor = screening_factor(self.reaction.reactants(), temperature, density, screening)?;
+        Ok(factor * self.rate(temperature))
+    }
+
+    /// Find the temperature between `min` and `max` at which the total rate equals `target`.
+    ///
+    /// See [`Set::temperature_for_rate`] for details.
+    #[must_use]
+    pub fn temperature_for_rate(&self, target: f64, min: f64, max: f64) -> Option<f64> {
+        solve_temperature(|t| self.rate(t), target, min, max)
+    }
+
+    /// Compile this rate into a [`Rate`], for faster repeated evaluation.
+    #[must_use]
+    pub fn compile(&self) -> Rate {
+        Rate::from(self)
+    }
+
+    /// Evaluate the total rate on a grid of `points` temperatures, logarithmically spaced between
+    /// `min` and `max` (inclusive).
+    ///
+    /// See [`log_grid`] for how the grid is made.
+    #[must_use]
+    pub fn curve(&self, min: f64, max: f64, points: usize) -> RateCurve {
+        let temperatures = log_grid(min, max, points);
+        let rates = temperatures.iter().map(|&t| self.rate(t)).collect();
+        RateCurve {
+            reaction: self.reaction.clone(),
+            temperatures,
+            rates,
+        }
+    }
+
+    /// Group the sets by their label and resonance flag.
+    ///
+    /// Each component is a separate contribution to the rate, such as a non-resonant part and
+    /// one or more resonances, and the total rate is always the sum of all of the components.
+    /// This is useful for seeing which components dominate at a given temperature.
+    /// The components are in the order that they first appear in [`sets`][Self::sets].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reaclib::{Format, Library, Resonance};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01
+    /// 4
+    ///          p  c12  n13                       nacrr     1.94400e+00
+    ///  1.754280e+01-3.778490e+00-5.107350e+00-2.241110e+00
+    ///  1.488830e-01 0.000000e+00-1.500000e+00");
+    /// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+    /// let rate = library.reaction_rate(library.reactions().next().unwrap()).unwrap();
+    ///
+    /// let components = rate.components();
+    /// assert_eq!(components.len(), 2);
+    /// assert_eq!(components[1].resonance, Resonance::Resonant);
+    ///
+    /// // the resonance dominates at high temperatures
+    /// let fraction = components[1].rate(2.0) / rate.rate(2.0);
+    /// assert!(fraction > 0.5);
+    /// ```
+    #[must_use]
+    pub fn components(&self) -> Vec<RateComponent> {
+        let mut components: Vec<RateComponent> = Vec::new();
+        for set in &self.sets {
+            match components
+                .iter_mut()
+                .find(|c| c.label == set.label && c.resonance == set.resonance)
+            {
+                Some(component) => component.sets.push(set.clone()),
+                None => components.push(RateComponent {
+                    label: set.label,
+                    resonance: set.resonance,
+                    sets: vec![set.clone()],
+                }),
+            }
+        }
+        components
+    }
+
+    /// A one-line summary of the rate, for logs, command line tools, and notebooks.
+    ///
+    /// It has the reaction in the [compact notation][Reaction::compact], the Q-value of the first
+    /// set, the number of sets with their labels, and their resonance flags, followed by
+    /// `reverse` if any of the sets are reverse rates.
+    /// The labels and flags are listed once each, in the order that they first appear.
+    ///
+    /// ```
+    /// use reaclib::{Format, Library};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01
+    /// 4
+    ///          p  c12  n13                       nacrr     1.94400e+00
+    ///  1.754280e+01-3.778490e+00-5.107350e+00-2.241110e+00
+    ///  1.488830e-01 0.000000e+00-1.500000e+00");
+    /// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+    /// let rate = library.reaction_rate(library.reactions().next().unwrap()).unwrap();
+    /// assert_eq!(
+    ///     rate.summary(),
+    ///     "c12(p,g)n13 — Q=1.944 MeV, 2 sets (nacr), non-resonant+resonant"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn summary(&self) -> String {
+        fn unique<T: PartialEq>(items: impl Iterator<Item = T>) -> Vec<T> {
+            let mut unique = Vec::new();
+            for item in items {
+                if !unique.contains(&item) {
+                    unique.push(item);
+                }
+            }
+            unique
+        }
+
+        let labels = unique(self.sets.iter().map(|s| s.label.as_str()));
+        let resonances = unique(self.sets.iter().map(|s| resonance_name(s.resonance)));
+        let sets = self.sets.len();
+        let details = match self.sets.first() {
+            Some(set) => format!(
+                "Q={:.3} MeV, {sets} set{} ({}), {}",
+                set.q_value,
+                if sets == 1 { "" } else { "s" },
+                labels.join(", "),
+                resonances.join("+")
+            ),
+            None => "0 sets".to_string(),
+        };
+        let reverse = if self.sets.iter().any(|s| s.reverse) {
+            ", reverse"
+        } else {
+            ""
+        };
+        format!("{} — {details}{reverse}", self.reaction.compact())
+    }
+}
+
+// the name of a resonance flag in `ReactionRate::summary`
+const fn resonance_name(resonance: Resonance) -> &'static str {
+    match resonance {
+        Resonance::NonResonant => "non-resonant",
+        Resonance::Resonant => "resonant",
+        Resonance::Weak => "weak",
+        Resonance::S => "s",
+    }
+}
+
+/// The sets of a [`ReactionRate`] that share a label and resonance flag.
+///
+/// It can be obtained with [`ReactionRate::components`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct RateComponent {
+    /// The label that all of the sets have.
+    pub label: ArrayString<4>,
+    /// The resonance flag that all of the sets have.
+    pub resonance: Resonance,
+    /// The sets whose rates are summed to get this component's rate.
+    pub sets: Vec<Set>,
+}
+
+impl RateComponent {
+    /// Calculate this component's contribution to the total rate.
+    #[must_use]
+    pub fn rate(&self, temperature: f64) -> f64 {
+        let basis = RateBasis::new(temperature);
+        self.sets.iter().map(|s| s.rate_with_basis(&basis)).sum()
+    }
+}
+
+impl RateEvaluator for RateComponent {
+    fn ln_rate(&self, temperature: f64) -> f64 {
+        Self::rate(self, temperature).ln()
+    }
+
+    fn rate(&self, temperature: f64) -> f64 {
+        Self::rate(self, temperature)
+    }
+}
+
+/// A compiled form of the sum of several sets' rates, for fast repeated evaluation.
+///
+/// The parameters are stored together, so that evaluating the total rate needs only one
+/// evaluation of the basis functions, followed by a fused multiply-add loop over the sets.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Iter, Rate};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"4
+///          p  c12  n13                       nacrn     1.94400e+00
+///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+///  4.443620e+00-3.158980e+00-6.666670e-01
+/// 4
+///          p  c12  n13                       nacrr     1.94400e+00
+///  1.754280e+01-3.778490e+00-5.107350e+00-2.241110e+00
+///  1.488830e-01 0.000000e+00-1.500000e+00");
+/// let sets = Iter::new(reader, Format::Reaclib2).collect::<Result<Vec<_>, _>>().unwrap();
+///
+/// let rate = Rate::new(&sets);
+/// let sum = sets[0].rate(0.1) + sets[1].rate(0.1);
+/// assert!((rate.rate(0.1) / sum - 1.0).abs() < 1e-12);
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Rate {
+    // params[j][k] is the j-th parameter of the k-th set
+    params: [Vec<f64>; 7],
+}
+
+impl Rate {
+    /// Compile the sum of the rates of `sets`.
+    #[must_use]
+    pub fn new(sets: &[Set]) -> Self {
+        let mut params: [Vec<f64>; 7] = Default::default();
+        for set in sets {
+            for (p, v) in params.iter_mut().zip(set.params) {
+                p.push(v);
+            }
+        }
+        Self { params }
+    }
+
+    /// The number of sets that make up this rate.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.params[0].len()
+    }
+
+    /// Whether there are no sets in this rate, in which case the rate is always 0.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.params[0].is_empty()
+    }
+
+    /// Calculate the total rate at `temperature`.
+    #[must_use]
+    pub fn rate(&self, temperature: f64) -> f64 {
+        self.rate_with_basis(&RateBasis::new(temperature))
+    }
+
+    /// Calculate the total rate using basis functions that have already been evaluated.
+    #[must_use]
+    pub fn rate_with_basis(&self, basis: &RateBasis) -> f64 {
+        let b = basis.values();
+        let [p0, p1, p2, p3, p4, p5, p6] = &self.params;
+        let mut sum = 0.0;
+        for k in 0..self.len() {
+            let mut x = p0[k];
+            x = p1[k].mul_add(b[1], x);
+            x = p2[k].mul_add(b[2], x);
+            x = p3[k].mul_add(b[3], x);
+            x = p4[k].mul_add(b[4], x);
+            x = p5[k].mul_add(b[5], x);
+            x = p6[k].mul_add(b[6], x);
+            sum += x.exp();
+        }
+        sum
+    }
+}
+
+impl From<&ReactionRate> for Rate {
+    fn from(rate: &ReactionRate) -> Self {
+        Self::new(&rate.sets)
+    }
+}
+
+/// A reaction rate evaluated on a grid of temperatures, ready for plotting.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Library};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+/// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+///
+/// let curves = library.curves(library.reactions(), 0.01, 10.0, 31);
+/// assert_eq!(curves[0].temperatures.len(), 31);
+/// assert_eq!(curves[0].rates.len(), 31);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RateCurve {
+    /// The reaction that the rate belongs to.
+    pub reaction: Reaction,
+    /// The temperatures (in GK) that the rate was evaluated at.
+    pub temperatures: Vec<f64>,
+    /// The rate at each of the temperatures.
+    pub rates: Vec<f64>,
+}
+
+/// Make a grid of `points` values, logarithmically spaced between `min` and `max` (inclusive).
+///
+/// If `points` is 1, the grid is just `min`.
+///
+/// ```
+/// use reaclib::log_grid;
+///
+/// let grid = log_grid(0.1, 10.0, 3);
+/// assert_eq!(grid.len(), 3);
+/// assert!((grid[1] - 1.0).abs() < 1e-12);
+/// ```
+#[must_use]
+pub fn log_grid(min: f64, max: f64, points: usize) -> Vec<f64> {
+    let (ln_min, ln_max) = (min.ln(), max.ln());
+    // be careful with `as f64`. grids are never large enough for this to lose precision
+    #[allow(clippy::cast_precision_loss)]
+    let step = (ln_max - ln_min) / (points.saturating_sub(1).max(1) as f64);
+    #[allow(clippy::cast_precision_loss)]
+    (0..points)
+        .map(|i| {
+            if i == 0 {
+                min
+            } else if i + 1 == points {
+                max
+            } else {
+                f64::exp(step.mul_add(i as f64, ln_min))
+            }
+        })
+        .collect()
+}
+
+// evaluate `rate` at `temperature`, rejecting temperatures where the fit isn't defined and results
+// that have overflowed or are NaN
+pub(crate) fn check_rate(temperature: f64, rate: impl Fn(f64) -> f64) -> Result<f64, RError> {
+    if temperature.is_nan() || temperature <= 0.0 {
+        return Err(SetError::NonPositiveTemperature.into());
+    }
+    let rate = rate(temperature);
+    if rate.is_finite() {
+        Ok(rate)
+    } else {
+        Err(SetError::NonFiniteRate.into())
+    }
+}
+
+// find where `rate` crosses `target` between `min` and `max` by bisection in log-log space
+// rates vary over many orders of magnitude, so this behaves much better than working linearly
+pub(crate) fn solve_temperature(
+    rate: impl Fn(f64) -> f64,
+    target: f64,
+    min: f64,
+    max: f64,
+) -> Option<f64> {
+    let ln_target = target.ln();
+    let f = |ln_t: f64| rate(f64::exp(ln_t)).ln() - ln_target;
+
+    let (mut lo, mut hi) = (min.ln(), max.ln());
+    let (mut f_lo, f_hi) = (f(lo), f(hi));
+    if f_lo.is_nan() || f_hi.is_nan() || lo.is_nan() || hi.is_nan() {
+        return None;
+    }
+    if f_lo == 0.0 {
+        return Some(min);
+    }
+    if f_hi == 0.0 {
+        return Some(max);
+    }
+    if f_lo.signum() == f_hi.signum() {
+        return None;
+    }
+
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        let f_mid = f(mid);
+        if f_mid.is_nan() {
+            return None;
+        }
+        if f_mid == 0.0 || (hi - lo).abs() < 1e-14 {
+            return Some(f64::exp(mid));
+        }
+        if f_mid.signum() == f_lo.signum() {
+            lo = mid;
+            f_lo = f_mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some(f64::exp(0.5 * (lo + hi)))
+}
+
+/// Evaluate the rate of each of `sets` at each of `temperatures`, in parallel.
+///
+/// The result has one row per set, and each row has one rate per temperature.
+///
+/// ```
+/// use reaclib::{rates_par, Format, Iter};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+/// let sets = Iter::new(reader, Format::Reaclib2).collect::<Result<Vec<_>, _>>().unwrap();
+///
+/// let rates = rates_par(&sets, &[0.1, 1.0, 10.0]);
+/// assert_eq!(rates.len(), 1);
+/// assert_eq!(rates[0].len(), 3);
+/// ```
+#[cfg(feature = "rayon")]
+#[must_use]
+pub fn rates_par(sets: &[Set], temperatures: &[f64]) -> Vec<Vec<f64>> {
+    let bases = temperatures
+        .iter()
+        .map(|&t| RateBasis::new(t))
+        .collect::<Vec<_>>();
+    sets.par_iter()
+        .map(|s| bases.iter().map(|b| s.rate_with_basis(b)).collect())
+        .collect()
+}
@@ -0,0 +1,48 @@
+use crate::{error::ReaclibError as RError, writer::format_float, ReactionRate};
+use std::io::Write;
+
+/// Write the total rate of `rate` at each of `temperatures` (in GK) as a two-column text table,
+/// like the rate tables that the reaclib website provides for individual reactions.
+///
+/// The table starts with a line of column names, `T9  rate`, followed by a line for each
+/// temperature with the temperature as it is given and the rate in scientific notation,
+/// separated by two spaces.
+/// This is useful for comparing with the website, since its tables can be pasted next to the
+/// output and the grid can be the same as the website's.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{write_rate_table, Format, Library};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+/// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+/// let rate = library.reaction_rate(library.reactions().next().unwrap()).unwrap();
+///
+/// let mut table = Vec::new();
+/// write_rate_table(&mut table, &rate, &[0.1, 1.0]).unwrap();
+/// assert_eq!(
+///     String::from_utf8(table).unwrap(),
+///     "T9  rate\n0.1  1.13445e-03\n1  1.13445e-03\n"
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Will return `Err` if there is an io error.
+pub fn write_rate_table<W: Write>(
+    mut writer: W,
+    rate: &ReactionRate,
+    temperatures: &[f64],
+) -> Result<(), RError> {
+    writeln!(writer, "T9  rate")?;
+    for &t in temperatures {
+        writeln!(writer, "{t}  {}", format_float(rate.rate(t), 11, 5).trim())?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,264 @@
+use crate::{Chapter, Nuclide, ParseError, Set, Species};
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// A type that represents a reaction, made up of reactants and products.
+///
+/// It can be converted to and from a tuple of `(reactants, products)`, and to and from a string
+/// like `he4 c12 -> o16`.
+/// It can also be parsed from the compact notation `c12(a,g)o16`.
+/// In either form, nuclides can be separated by `+` as well as whitespace, `a` or `alpha` stands
+/// for `he4` (and `h1`, `h2`, `h3`, and `n1` for `p`, `d`, `t`, and `n`), and photons (`g` or
+/// `gamma`) and leptons (`e-`, `e+`, `nu`, `nubar`) are left out, so e.g. `p + c12 -> n13 + g` is
+/// the chapter 4 reaction `p c12 -> n13`.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Chapter, Nuclide, Reaction};
+///
+/// let he4: Nuclide = "he4".parse().unwrap();
+/// let c12: Nuclide = "c12".parse().unwrap();
+/// let o16: Nuclide = "o16".parse().unwrap();
+///
+/// let reaction = Reaction::new([he4, c12].into_iter().collect(), [o16].into_iter().collect());
+/// assert_eq!(reaction.chapter(), Some(Chapter::Chapter4));
+/// assert!(reaction.contains(&c12));
+/// assert_eq!(reaction.to_string(), "he4 c12 -> o16");
+/// assert_eq!(reaction.reversed().to_string(), "o16 -> he4 c12");
+/// assert_eq!("he4 c12 -> o16".parse::<Reaction>().unwrap(), reaction);
+/// assert_eq!("c12(a,g)o16".parse::<Reaction>().unwrap(), reaction);
+/// assert_eq!("o16(g,a)c12".parse::<Reaction>().unwrap(), reaction.reversed());
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct Reaction {
+    reactants: Species,
+    products: Species,
+}
+
+impl Reaction {
+    /// Creates a new `Reaction` from its reactants and products.
+    #[must_use]
+    pub const fn new(reactants: Species, products: Species) -> Self {
+        Self {
+            reactants,
+            products,
+        }
+    }
+
+    /// The nuclides going into the reaction.
+    #[must_use]
+    pub fn reactants(&self) -> &[Nuclide] {
+        &self.reactants
+    }
+
+    /// The nuclides resulting from the reaction.
+    #[must_use]
+    pub fn products(&self) -> &[Nuclide] {
+        &self.products
+    }
+
+    /// The chapter describing this reaction's number of reactants and products.
+    ///
+    /// Returns `None` if there is no such chapter.
+    #[must_use]
+    pub fn chapter(&self) -> Option<Chapter> {
+        Chapter::from_counts(self.reactants.len(), self.products.len())
+    }
+
+    /// Whether `nuclide` is one of the reactants or products.
+    #[must_use]
+    pub fn contains(&self, nuclide: &Nuclide) -> bool {
+        self.reactants.contains(nuclide) || self.products.contains(nuclide)
+    }
+
+    /// The reaction with its reactants and products each sorted into a canonical order.
+    ///
+    /// Nuclides are ordered by [`Nuclide::canonical_cmp`], so reactions that only differ in the
+    /// order of their nuclides have the same canonical form.
+    ///
+    /// ```
+    /// use reaclib::{Nuclide, Reaction};
+    ///
+    /// let nuclide = |s: &str| s.parse::<Nuclide>().unwrap();
+    /// let heavy_first = Reaction::new(
+    ///     [nuclide("c12"), nuclide("p")].into_iter().collect(),
+    ///     [nuclide("n13")].into_iter().collect(),
+    /// );
+    /// assert_eq!(heavy_first.canonical().to_string(), "p c12 -> n13");
+    /// ```
+    #[must_use]
+    pub fn canonical(&self) -> Self {
+        let mut reaction = self.clone();
+        reaction.reactants.sort_by(Nuclide::canonical_cmp);
+        reaction.products.sort_by(Nuclide::canonical_cmp);
+        reaction
+    }
+
+    /// The reaction with only its products sorted into a canonical order, by
+    /// [`Nuclide::canonical_cmp`].
+    ///
+    /// Unlike [`canonical`][Self::canonical], the reactants are left as they are.
+    ///
+    /// ```
+    /// use reaclib::Reaction;
+    ///
+    /// let reaction = "p o18 -> n15 he4".parse::<Reaction>().unwrap();
+    /// assert_eq!(reaction.sorted_products().to_string(), "p o18 -> he4 n15");
+    /// ```
+    #[must_use]
+    pub fn sorted_products(&self) -> Self {
+        let mut reaction = self.clone();
+        reaction.products.sort_by(Nuclide::canonical_cmp);
+        reaction
+    }
+
+    /// The reaction in the compact notation, like `ne20(a,g)mg24`, which can be parsed back into
+    /// the same reaction, up to the order of the nuclides.
+    ///
+    /// The heaviest reactant and product (by [`Nuclide::canonical_cmp`]) are the target and
+    /// residual, and the others are the incoming and outgoing particles, with `a` for `he4`.
+    /// A photon (`g`) is added to a side with no particles, unless the charge changes, in which
+    /// case the leptons are added (`e- nubar` or `e+ nu`) to the outgoing side.
+    ///
+    /// ```
+    /// use reaclib::Reaction;
+    ///
+    /// let reaction = "he4 ne20 -> mg24".parse::<Reaction>().unwrap();
+    /// assert_eq!(reaction.compact(), "ne20(a,g)mg24");
+    /// assert_eq!(reaction.reversed().compact(), "mg24(g,a)ne20");
+    /// assert_eq!("n13 -> c13".parse::<Reaction>().unwrap().compact(), "n13(,e+ nu)c13");
+    /// ```
+    #[must_use]
+    pub fn compact(&self) -> String {
+        let split = |nuclides: &[Nuclide]| {
+            let mut sorted = nuclides.to_vec();
+            sorted.sort_by(Nuclide::canonical_cmp);
+            let heavy = sorted.pop().map(|n| n.to_string()).unwrap_or_default();
+            let light = sorted
+                .iter()
+                .map(|n| if n.is_alpha() { "a" } else { n.as_str() })
+                .collect::<Vec<_>>()
+                .join("+");
+            (light, heavy)
+        };
+        let charge = |nuclides: &[Nuclide]| {
+            nuclides
+                .iter()
+                .map(|n| n.z().map(i32::from))
+                .sum::<Option<i32>>()
+        };
+
+        let (mut incoming, target) = split(&self.reactants);
+        let (mut outgoing, residual) = split(&self.products);
+        if incoming.is_empty() && self.products.len() > 1 {
+            incoming = "g".to_string();
+        }
+        if outgoing.is_empty() {
+            outgoing = match charge(&self.products)
+                .zip(charge(&self.reactants))
+                .map(|(p, r)| p - r)
+            {
+                Some(1) => "e- nubar",
+                Some(-1) => "e+ nu",
+                _ => "g",
+            }
+            .to_string();
+        }
+        format!("{target}({incoming},{outgoing}){residual}")
+    }
+
+    /// The reverse reaction, with the reactants and products swapped.
+    #[must_use]
+    pub fn reversed(&self) -> Self {
+        Self {
+            reactants: self.products.clone(),
+            products: self.reactants.clone(),
+        }
+    }
+}
+
+impl<R: Into<Species>, P: Into<Species>> From<(R, P)> for Reaction {
+    fn from((reactants, products): (R, P)) -> Self {
+        Self::new(reactants.into(), products.into())
+    }
+}
+
+impl From<Reaction> for (Species, Species) {
+    fn from(r: Reaction) -> Self {
+        (r.reactants, r.products)
+    }
+}
+
+impl From<&Set> for Reaction {
+    fn from(set: &Set) -> Self {
+        Self::new(set.reactants.clone(), set.products.clone())
+    }
+}
+
+impl fmt::Display for Reaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} -> {}",
+            self.reactants.join(" "),
+            self.products.join(" ")
+        )
+    }
+}
+
+impl FromStr for Reaction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseError::InvalidReaction(s.to_string());
+
+        if let Some((reactants, products)) = s.split_once("->") {
+            let (reactants, products) = (species(reactants)?, species(products)?);
+            // e.g. `g -> p`, which has nothing left after dropping the photon
+            if reactants.is_empty() || products.is_empty() {
+                return Err(invalid());
+            }
+            return Ok(Self::new(reactants, products));
+        }
+
+        // the compact notation, `target(incoming,outgoing)residual`
+        let (target, rest) = s.split_once('(').ok_or_else(invalid)?;
+        let (particles, residual) = rest.split_once(')').ok_or_else(invalid)?;
+        let (incoming, outgoing) = particles.split_once(',').ok_or_else(invalid)?;
+        let parse = |particles: &str, heavy: &str| -> Result<Species, ParseError> {
+            let mut nuclides = species(particles)?;
+            nuclides.extend(species(heavy)?);
+            Ok(nuclides)
+        };
+        let reactants = parse(incoming, target)?;
+        if target.trim().is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self::new(reactants, parse(outgoing, residual)?))
+    }
+}
+
+// the nuclides in a list of particles separated by whitespace or `+`, with aliases resolved to
+// their reaclib names, and photons and leptons left out
+fn species(particles: &str) -> Result<Species, ParseError> {
+    particles
+        .split(|c: char| c.is_whitespace() || c == '+')
+        .filter_map(|p| match p {
+            // `e+` is split into `e` and an empty string
+            "" | "g" | "gamma" | "e" | "e-" | "nu" | "nubar" => None,
+            "a" | "alpha" => Some("he4"),
+            "h1" => Some("p"),
+            "h2" => Some("d"),
+            "h3" => Some("t"),
+            "n1" => Some("n"),
+            p => Some(p),
+        })
+        .map(str::parse)
+        .collect()
+}
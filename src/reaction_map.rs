@@ -0,0 +1,65 @@
+//! Serialize a map keyed by [`Reaction`] with strings as the keys.
+//!
+//! Many formats, such as JSON, only allow strings as the keys of maps, and others, such as YAML,
+//! need complex keys to represent a [`Reaction`] as a key, which many tools don't support.
+//! This module can be used with `#[serde(with = "reaclib::reaction_map")]` on a
+//! `HashMap<Reaction, V>` field, such as the result of [`to_hash_map`][crate::to_hash_map], to
+//! write each reaction as a string like `he4 c12 -> o16` instead.
+//!
+//! # Examples
+//!
+//! ```
+//! use reaclib::{to_hash_map, Format, Reaction, Set};
+//! use serde::{Deserialize, Serialize};
+//! use std::{collections::HashMap, io::Cursor};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Network {
+//!     #[serde(with = "reaclib::reaction_map")]
+//!     rates: HashMap<Reaction, Vec<Set>>,
+//! }
+//!
+//! let reader = Cursor::new(r"1
+//!          n    p                            wc12w     7.82300e-01
+//! -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+//!  0.000000e+00 0.000000e+00 0.000000e+00");
+//! let network = Network { rates: to_hash_map(reader, Format::Reaclib2).unwrap() };
+//!
+//! let json = serde_json::to_string(&network).unwrap();
+//! assert!(json.starts_with(r#"{"rates":{"n -> p":[{"#));
+//! let reread: Network = serde_json::from_str(&json).unwrap();
+//! assert_eq!(reread.rates, network.rates);
+//! ```
+use crate::Reaction;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::HashMap, hash::BuildHasher};
+
+/// Serialize `map` with each reaction written as a string.
+///
+/// # Errors
+///
+/// Will return `Err` if the serializer fails.
+pub fn serialize<V, H, S>(map: &HashMap<Reaction, V, H>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    V: Serialize,
+    S: Serializer,
+{
+    serializer.collect_map(map.iter().map(|(r, v)| (r.to_string(), v)))
+}
+
+/// Deserialize a map with each reaction written as a string.
+///
+/// # Errors
+///
+/// Will return `Err` if the deserializer fails or a key isn't a valid reaction.
+pub fn deserialize<'de, V, H, D>(deserializer: D) -> Result<HashMap<Reaction, V, H>, D::Error>
+where
+    V: Deserialize<'de>,
+    H: BuildHasher + Default,
+    D: Deserializer<'de>,
+{
+    HashMap::<String, V>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(k, v)| Ok((k.parse().map_err(de::Error::custom)?, v)))
+        .collect()
+}
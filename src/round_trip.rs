@@ -0,0 +1,67 @@
+use crate::{write_sets, Format, Iter, Set};
+use std::io::BufRead;
+
+/// Check that the [`Writer`][crate::Writer] and the parser agree with each other on `input`.
+///
+/// The sets in `input` are parsed, written according to `format`, and parsed again.
+/// The second parse must succeed, it must give the same reactions, labels, and flags, and writing
+/// those sets must give exactly the same output as the first time.
+/// The numbers are compared through the written output, since the writer rounds them to the
+/// precision of the format.
+///
+/// Input that doesn't parse is ignored, so this can be given arbitrary data, such as from a
+/// fuzzer.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{assert_round_trip, Format};
+///
+/// let input = "1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00";
+/// assert_round_trip(input.as_bytes(), Format::Reaclib2);
+/// ```
+///
+/// # Panics
+///
+/// Will panic if the parsed sets can't be written, if the written sets can't be parsed, or if
+/// they don't match.
+pub fn assert_round_trip<R: BufRead>(input: R, format: Format) {
+    let Ok(sets) = Iter::new(input, format).collect::<Result<Vec<_>, _>>() else {
+        return;
+    };
+
+    let written = write(&sets, format);
+    let reread = Iter::new(&written[..], format)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("written sets can't be parsed: {e}"));
+    assert_eq!(sets.len(), reread.len(), "the number of sets changed");
+    for (set, reread) in sets.iter().zip(&reread) {
+        let key = |s: &Set| {
+            (
+                s.reactants.clone(),
+                s.products.clone(),
+                s.label,
+                s.resonance,
+                s.reverse,
+            )
+        };
+        assert_eq!(key(set), key(reread), "a set changed");
+    }
+
+    let rewritten = write(&reread, format);
+    assert_eq!(
+        String::from_utf8_lossy(&written),
+        String::from_utf8_lossy(&rewritten),
+        "writing the parsed output changed it"
+    );
+}
+
+fn write(sets: &[Set], format: Format) -> Vec<u8> {
+    let mut written = Vec::new();
+    write_sets(&mut written, sets, format)
+        .unwrap_or_else(|e| panic!("parsed sets can't be written: {e}"));
+    written
+}
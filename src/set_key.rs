@@ -0,0 +1,72 @@
+use crate::Set;
+use std::hash::{Hash, Hasher};
+
+/// A wrapper around [`Set`] that implements [`Eq`] and [`Hash`], so sets can be deduplicated or
+/// put in a [`HashSet`][std::collections::HashSet].
+///
+/// Floats are compared by their bit patterns, so `0.0` and `-0.0` are different, and a NaN is
+/// equal to another NaN with the same bits.
+/// [`raw_params`][Set::raw_params], [`raw_lines`][Set::raw_lines], and
+/// [`validity`][Set::validity] are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Iter, SetKey};
+/// use std::{collections::HashSet, io::Cursor};
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+/// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+///
+/// let mut unique = HashSet::new();
+/// assert!(unique.insert(SetKey(set.clone())));
+/// assert!(!unique.insert(SetKey(set)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SetKey(pub Set);
+
+impl SetKey {
+    fn bits(&self) -> (u64, [u64; 7]) {
+        (self.0.q_value.to_bits(), self.0.params.map(f64::to_bits))
+    }
+}
+
+impl PartialEq for SetKey {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = (&self.0, &other.0);
+        a.reactants == b.reactants
+            && a.products == b.products
+            && a.label == b.label
+            && a.resonance == b.resonance
+            && a.reverse == b.reverse
+            && self.bits() == other.bits()
+    }
+}
+
+impl Eq for SetKey {}
+
+impl Hash for SetKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.reactants.hash(state);
+        self.0.products.hash(state);
+        self.0.label.hash(state);
+        self.0.resonance.hash(state);
+        self.0.reverse.hash(state);
+        self.bits().hash(state);
+    }
+}
+
+impl From<Set> for SetKey {
+    fn from(set: Set) -> Self {
+        Self(set)
+    }
+}
+
+impl From<SetKey> for Set {
+    fn from(key: SetKey) -> Self {
+        key.0
+    }
+}
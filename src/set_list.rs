@@ -0,0 +1,63 @@
+use crate::{Library, Set};
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A list of sets that can be serialized as a document on its own.
+///
+/// Formats such as TOML need a table at the top level, so a bare list of sets can't be
+/// serialized. This wraps the list in a table with a single `set` key, which makes a TOML
+/// document with one `[[set]]` table per set. This is convenient for small, hand-editable
+/// collections of sets, such as rate overrides that are kept alongside a project.
+///
+/// Each set is written with the names of the fields of [`Set`]:
+///
+/// ```toml
+/// [[set]]
+/// reactants = ["p", "c12"]
+/// products = ["n13"]
+/// label = "nacr"
+/// resonance = "NonResonant"
+/// reverse = false
+/// q_value = 1.944
+/// params = [17.1482, 0.0, -13.692, -0.230881, 4.44362, -3.15898, -0.666667]
+/// ```
+///
+/// The optional fields [`raw_params`][Set::raw_params], [`raw_lines`][Set::raw_lines], and
+/// [`validity`][Set::validity] can be left out.
+/// The `resonance` is one of the names of the variants of [`Resonance`][crate::Resonance].
+///
+/// See the `toml_overrides` example for using this to override the sets in a library.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct SetList {
+    /// The sets in the list.
+    #[serde(default)]
+    pub set: Vec<Set>,
+}
+
+impl From<Vec<Set>> for SetList {
+    fn from(set: Vec<Set>) -> Self {
+        Self { set }
+    }
+}
+
+impl From<SetList> for Vec<Set> {
+    fn from(list: SetList) -> Self {
+        list.set
+    }
+}
+
+impl From<&Library> for SetList {
+    fn from(library: &Library) -> Self {
+        Self {
+            set: library.sets().to_vec(),
+        }
+    }
+}
+
+impl From<SetList> for Library {
+    fn from(list: SetList) -> Self {
+        list.set.into_iter().collect()
+    }
+}
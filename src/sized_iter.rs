@@ -0,0 +1,151 @@
+use crate::{error::ReaclibError as RError, Chapter, Format, Iter, Set, Span};
+use std::io::{BufRead, Seek, SeekFrom};
+
+/// An [`Iter`] that knows how many items it will return, so it implements [`ExactSizeIterator`].
+///
+/// The count comes from a first pass over the reader, which only looks at the layout of the lines
+/// (without parsing any numbers), after which the reader is rewound.
+/// Every item is counted, including errors, so the count is exactly the number of times that
+/// [`next`][Iterator::next] returns `Some`.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, SizedIter};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+/// let mut iter = SizedIter::new(reader, Format::Reaclib2).unwrap();
+/// assert_eq!(iter.len(), 1);
+/// let sets = iter.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(sets.len(), 1);
+/// ```
+pub struct SizedIter<R: BufRead> {
+    iter: Iter<R>,
+    remaining: usize,
+}
+
+impl<R: BufRead + Seek> SizedIter<R> {
+    /// Creates a new `SizedIter` from `reader`, like [`Iter::new`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error while counting the sets.
+    pub fn new(reader: R, format: Format) -> Result<Self, RError> {
+        Iter::builder(reader).format(format).build_sized()
+    }
+}
+
+impl<R: BufRead> SizedIter<R> {
+    /// The lines that the last item came from, like [`Iter::span`].
+    #[must_use]
+    pub const fn span(&self) -> Option<Span> {
+        self.iter.span()
+    }
+
+    /// The lines that were skipped before the first chapter number, like [`Iter::preamble`].
+    #[must_use]
+    pub fn preamble(&self) -> &[String] {
+        self.iter.preamble()
+    }
+
+    // count the items in `reader`, then rewind it and create the `Iter` with `build`
+    // `layout` has to match the `Iter`
+    pub(crate) fn with_count(
+        mut reader: R,
+        layout: &Layout,
+        build: impl FnOnce(R) -> Iter<R>,
+    ) -> Result<Self, RError>
+    where
+        R: Seek,
+    {
+        let start = reader.stream_position()?;
+        let remaining = count(&mut reader, layout)?;
+        reader.seek(SeekFrom::Start(start))?;
+        Ok(Self {
+            iter: build(reader),
+            remaining,
+        })
+    }
+}
+
+impl<R: BufRead> Iterator for SizedIter<R> {
+    type Item = Result<Set, RError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        self.remaining = self.remaining.saturating_sub(1);
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<R: BufRead> ExactSizeIterator for SizedIter<R> {}
+
+// the options of an `Iter` that change which lines are skipped and which are chapter headers
+pub(crate) struct Layout<'a> {
+    pub(crate) format: Format,
+    pub(crate) lenient: bool,
+    // the numbers of the extended chapters
+    pub(crate) chapters: &'a [u8],
+    pub(crate) skip_preamble: bool,
+}
+
+impl Layout<'_> {
+    fn is_chapter(&self, number: u8) -> bool {
+        Chapter::try_from(number).is_ok() || self.chapters.contains(&number)
+    }
+}
+
+// count the items that `Iter` would return, following the same grouping of lines
+fn count<R: BufRead>(reader: &mut R, layout: &Layout) -> Result<usize, RError> {
+    let Layout {
+        format, lenient, ..
+    } = *layout;
+    let mut in_preamble = layout.skip_preamble;
+    let group_size = match format {
+        Format::Reaclib1 => 3,
+        Format::Reaclib2 => 4,
+    };
+    let (mut count, mut group) = (0, Vec::with_capacity(group_size));
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let end = !crate::line_source::read_raw_line(reader, &mut line)?;
+        if !end {
+            // only whether a line is blank, and the first line of a chapter header, matter
+            let text = std::str::from_utf8(&line).ok().map(str::trim);
+            let mut preamble = false;
+            if in_preamble && group.is_empty() {
+                if let Some(t) = text {
+                    in_preamble = !t.parse::<u8>().is_ok_and(|c| layout.is_chapter(c));
+                    preamble = in_preamble;
+                }
+            }
+            let skipped = preamble
+                || (lenient && text.is_some_and(|t| crate::is_skipped(t, group.is_empty())));
+            if !skipped {
+                group.push(text.map(|t| (t.is_empty(), t.parse::<u8>().ok())));
+            }
+        }
+        if group.len() == group_size || (end && !group.is_empty()) {
+            let is_chapter = match group[..] {
+                [Some((_, chapter)), Some((true, _)), Some((true, _))] => {
+                    format == Format::Reaclib1 && chapter.is_some_and(|c| layout.is_chapter(c))
+                }
+                _ => false,
+            };
+            count += usize::from(!is_chapter);
+            group.clear();
+        }
+        if end {
+            return Ok(count);
+        }
+    }
+}
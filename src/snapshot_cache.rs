@@ -0,0 +1,140 @@
+use crate::{error::ReaclibError as RError, Library, Snapshot};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// A directory of downloaded [`Snapshot`]s, so that repeated runs don't download them again.
+///
+/// Each snapshot is stored in a file named after the snapshot, so the name should identify a
+/// version of the data.
+/// A stored copy of a snapshot without an expected size or checksum is never checked against
+/// the source again, and is kept until it is [`refresh`][Self::refresh]ed, so pinning the size
+/// and checksum is what makes a run reproducible.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use reaclib::{Format, Snapshot, SnapshotCache};
+///
+/// let cache = SnapshotCache::user().expect("no cache directory");
+/// let url = std::env::var("REACLIB_SNAPSHOT_URL")?;
+/// let snapshot = Snapshot::new("default", url, Format::Reaclib2);
+/// // only the first run downloads the snapshot
+/// let library = cache.fetch(&snapshot)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SnapshotCache {
+    dir: PathBuf,
+}
+
+impl SnapshotCache {
+    /// Creates a new `SnapshotCache` that stores snapshots in `dir`.
+    ///
+    /// The directory is created when the first snapshot is stored.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache in the user's cache directory.
+    ///
+    /// This is `$XDG_CACHE_HOME/reaclib`, or `$HOME/.cache/reaclib` if `XDG_CACHE_HOME` isn't
+    /// set to an absolute path.
+    /// Returns `None` if neither variable is set.
+    #[must_use]
+    pub fn user() -> Option<Self> {
+        let xdg = env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute());
+        let base = xdg.or_else(|| env::var_os("HOME").map(|h| Path::new(&h).join(".cache")))?;
+        Some(Self::new(base.join("reaclib")))
+    }
+
+    /// The directory that the snapshots are stored in.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The path that `snapshot` is stored at.
+    ///
+    /// Characters in the name that could change the directory, such as `/`, are replaced with
+    /// `_`.
+    #[must_use]
+    pub fn path(&self, snapshot: &Snapshot) -> PathBuf {
+        let name = snapshot
+            .name()
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || "-_.".contains(c) {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        self.dir.join(format!("{name}.reaclib"))
+    }
+
+    /// Whether `snapshot` is stored in the cache.
+    #[must_use]
+    pub fn contains(&self, snapshot: &Snapshot) -> bool {
+        self.path(snapshot).is_file()
+    }
+
+    /// Read `snapshot` from the cache, downloading and storing it first if it isn't there.
+    ///
+    /// A stored copy that doesn't match the snapshot's expected size or checksum, or that doesn't
+    /// parse, is downloaded again.
+    /// If the snapshot has no expected size or checksum, a stored copy that parses is used
+    /// without being re-validated, even if it is truncated at the end of a set, so such a
+    /// snapshot should be [`refresh`][Self::refresh]ed when it may be stale or damaged.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error, if the download fails, if the data doesn't
+    /// match the expected size or checksum, or if there is a parsing error.
+    pub fn fetch(&self, snapshot: &Snapshot) -> Result<Library, RError> {
+        // `parse` also checks the size and checksum
+        match fs::read(self.path(snapshot)).map(|data| snapshot.parse(&data)) {
+            Ok(Ok(library)) => Ok(library),
+            _ => self.refresh(snapshot),
+        }
+    }
+
+    /// Download `snapshot` and store it in the cache, replacing any stored copy.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error, if the download fails, if the data doesn't
+    /// match the expected size or checksum, or if there is a parsing error.
+    pub fn refresh(&self, snapshot: &Snapshot) -> Result<Library, RError> {
+        let data = snapshot.download()?;
+        let library = snapshot.parse(&data)?;
+
+        // write to a temporary file first, so that an interrupted write isn't mistaken for a
+        // stored snapshot
+        let path = self.path(snapshot);
+        let partial = path.with_extension("partial");
+        fs::create_dir_all(&self.dir)?;
+        fs::write(&partial, &data)?;
+        fs::rename(&partial, &path)?;
+        Ok(library)
+    }
+
+    /// Remove `snapshot` from the cache, if it is stored.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error.
+    pub fn remove(&self, snapshot: &Snapshot) -> Result<(), RError> {
+        match fs::remove_file(self.path(snapshot)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
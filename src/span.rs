@@ -0,0 +1,24 @@
+use std::fmt;
+
+/// The lines of a source that a set (or an error) came from, as returned by [`Iter::span`].
+///
+/// The lines are numbered from 1, and both ends are included.
+///
+/// [`Iter::span`]: crate::Iter::span
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Span {
+    /// The first line.
+    pub first_line: usize,
+    /// The last line.
+    pub last_line: usize,
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.first_line == self.last_line {
+            write!(f, "line {}", self.first_line)
+        } else {
+            write!(f, "lines {}-{}", self.first_line, self.last_line)
+        }
+    }
+}
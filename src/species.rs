@@ -0,0 +1,353 @@
+use crate::Nuclide;
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
+use arrayvec::ArrayVec;
+#[cfg(feature = "schemars")]
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "schemars")]
+use std::borrow::Cow;
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+};
+
+// the most nuclides on one side of a reaction in any of the standard chapters
+const INLINE: usize = 4;
+
+/// The nuclides on one side of a reaction, i.e. its reactants or its products.
+///
+/// Up to 4 nuclides, the most in any of the standard chapters, are stored inline without
+/// allocating.
+/// More than that are also allowed, and are stored on the heap, so that reactions from extended
+/// chapters (see [`IterBuilder::chapter`][crate::IterBuilder::chapter]) can be represented.
+/// It dereferences to a slice of [`Nuclide`]s, so it can be used like one.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Nuclide, Species};
+///
+/// let he4: Nuclide = "he4".parse().unwrap();
+/// let mut species = Species::new();
+/// species.push(he4);
+/// species.push("c12".parse().unwrap());
+/// assert_eq!(species.len(), 2);
+/// assert_eq!(species[0], he4);
+/// assert_eq!(species, [he4, "c12".parse().unwrap()]);
+/// ```
+#[derive(Clone, Default)]
+pub struct Species(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    Inline(ArrayVec<Nuclide, INLINE>),
+    Heap(Vec<Nuclide>),
+}
+
+impl Default for Repr {
+    fn default() -> Self {
+        Self::Inline(ArrayVec::new())
+    }
+}
+
+impl Species {
+    /// Creates an empty `Species`.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(Repr::Inline(ArrayVec::new_const()))
+    }
+
+    /// The nuclides, as a slice.
+    #[must_use]
+    pub fn as_slice(&self) -> &[Nuclide] {
+        match &self.0 {
+            Repr::Inline(v) => v,
+            Repr::Heap(v) => v,
+        }
+    }
+
+    /// The nuclides, as a mutable slice.
+    #[must_use]
+    pub fn as_mut_slice(&mut self) -> &mut [Nuclide] {
+        match &mut self.0 {
+            Repr::Inline(v) => v,
+            Repr::Heap(v) => v,
+        }
+    }
+
+    /// Add a nuclide to the end.
+    pub fn push(&mut self, nuclide: Nuclide) {
+        match &mut self.0 {
+            Repr::Inline(v) => {
+                if let Err(e) = v.try_push(nuclide) {
+                    let mut heap = Vec::with_capacity(INLINE * 2);
+                    heap.extend_from_slice(v);
+                    heap.push(e.element());
+                    self.0 = Repr::Heap(heap);
+                }
+            }
+            Repr::Heap(v) => v.push(nuclide),
+        }
+    }
+
+    /// Remove the last nuclide and return it, or `None` if there are none.
+    pub fn pop(&mut self) -> Option<Nuclide> {
+        match &mut self.0 {
+            Repr::Inline(v) => v.pop(),
+            Repr::Heap(v) => v.pop(),
+        }
+    }
+
+    /// Remove every nuclide.
+    pub fn clear(&mut self) {
+        self.0 = Repr::default();
+    }
+
+    /// Keep only the nuclides for which `f` returns `true`.
+    pub fn retain(&mut self, f: impl FnMut(&mut Nuclide) -> bool) {
+        match &mut self.0 {
+            Repr::Inline(v) => v.retain(f),
+            Repr::Heap(v) => v.retain_mut(f),
+        }
+    }
+
+    /// Whether the nuclides are stored inline, which is always the case for 4 or fewer.
+    #[must_use]
+    pub const fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline(_))
+    }
+}
+
+impl Deref for Species {
+    type Target = [Nuclide];
+
+    fn deref(&self) -> &[Nuclide] {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for Species {
+    fn deref_mut(&mut self) -> &mut [Nuclide] {
+        self.as_mut_slice()
+    }
+}
+
+impl AsRef<[Nuclide]> for Species {
+    fn as_ref(&self) -> &[Nuclide] {
+        self
+    }
+}
+
+// the comparisons are by the nuclides, no matter how they are stored
+impl PartialEq for Species {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for Species {}
+
+impl PartialEq<[Nuclide]> for Species {
+    fn eq(&self, other: &[Nuclide]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<const N: usize> PartialEq<[Nuclide; N]> for Species {
+    fn eq(&self, other: &[Nuclide; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl PartialOrd for Species {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Species {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl Hash for Species {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl fmt::Debug for Species {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<Nuclide> for Species {
+    fn from_iter<I: IntoIterator<Item = Nuclide>>(iter: I) -> Self {
+        let mut species = Self::new();
+        species.extend(iter);
+        species
+    }
+}
+
+impl Extend<Nuclide> for Species {
+    fn extend<I: IntoIterator<Item = Nuclide>>(&mut self, iter: I) {
+        for nuclide in iter {
+            self.push(nuclide);
+        }
+    }
+}
+
+impl<'a> Extend<&'a Nuclide> for Species {
+    fn extend<I: IntoIterator<Item = &'a Nuclide>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<'a> IntoIterator for &'a Species {
+    type Item = &'a Nuclide;
+    type IntoIter = std::slice::Iter<'a, Nuclide>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Species {
+    type Item = &'a mut Nuclide;
+    type IntoIter = std::slice::IterMut<'a, Nuclide>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl IntoIterator for Species {
+    type Item = Nuclide;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(match self.0 {
+            Repr::Inline(v) => IntoIterRepr::Inline(v.into_iter()),
+            Repr::Heap(v) => IntoIterRepr::Heap(v.into_iter()),
+        })
+    }
+}
+
+/// An iterator over the nuclides of a [`Species`], created by its `into_iter` method.
+#[derive(Clone, Debug)]
+pub struct IntoIter(IntoIterRepr);
+
+#[derive(Clone, Debug)]
+enum IntoIterRepr {
+    Inline(arrayvec::IntoIter<Nuclide, INLINE>),
+    Heap(std::vec::IntoIter<Nuclide>),
+}
+
+impl Iterator for IntoIter {
+    type Item = Nuclide;
+
+    fn next(&mut self) -> Option<Nuclide> {
+        match &mut self.0 {
+            IntoIterRepr::Inline(i) => i.next(),
+            IntoIterRepr::Heap(i) => i.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            IntoIterRepr::Inline(i) => i.size_hint(),
+            IntoIterRepr::Heap(i) => i.size_hint(),
+        }
+    }
+}
+
+impl DoubleEndedIterator for IntoIter {
+    fn next_back(&mut self) -> Option<Nuclide> {
+        match &mut self.0 {
+            IntoIterRepr::Inline(i) => i.next_back(),
+            IntoIterRepr::Heap(i) => i.next_back(),
+        }
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl From<ArrayVec<Nuclide, INLINE>> for Species {
+    fn from(v: ArrayVec<Nuclide, INLINE>) -> Self {
+        Self(Repr::Inline(v))
+    }
+}
+
+impl From<Vec<Nuclide>> for Species {
+    fn from(v: Vec<Nuclide>) -> Self {
+        if v.len() <= INLINE {
+            v.into_iter().collect()
+        } else {
+            Self(Repr::Heap(v))
+        }
+    }
+}
+
+impl From<&[Nuclide]> for Species {
+    fn from(v: &[Nuclide]) -> Self {
+        v.iter().copied().collect()
+    }
+}
+
+impl<const N: usize> From<[Nuclide; N]> for Species {
+    fn from(v: [Nuclide; N]) -> Self {
+        v.into_iter().collect()
+    }
+}
+
+impl From<Species> for Vec<Nuclide> {
+    fn from(species: Species) -> Self {
+        match species.0 {
+            Repr::Inline(v) => v.to_vec(),
+            Repr::Heap(v) => v,
+        }
+    }
+}
+
+// serialized as a sequence of nuclides, the same as an `ArrayVec` or a `Vec`
+#[cfg(feature = "serde")]
+impl Serialize for Species {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Species {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<Nuclide>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for Species {
+    fn schema_name() -> Cow<'static, str> {
+        "Species".into()
+    }
+
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        Vec::<Nuclide>::json_schema(generator)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for Species {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_iter()?.collect()
+    }
+}
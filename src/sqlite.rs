@@ -0,0 +1,223 @@
+use crate::{error::ReaclibError as RError, Library, Reaction, Set, Species};
+use arrayvec::ArrayString;
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use std::{collections::HashMap, path::Path};
+
+// the schema, which is created if it doesn't exist yet
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS reactions (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    chapter INTEGER
+);
+CREATE TABLE IF NOT EXISTS species (
+    reaction_id INTEGER NOT NULL REFERENCES reactions (id),
+    role TEXT NOT NULL CHECK (role IN ('reactant', 'product')),
+    position INTEGER NOT NULL,
+    nuclide TEXT NOT NULL,
+    PRIMARY KEY (reaction_id, role, position)
+);
+CREATE TABLE IF NOT EXISTS sets (
+    id INTEGER PRIMARY KEY,
+    reaction_id INTEGER NOT NULL REFERENCES reactions (id),
+    label TEXT NOT NULL,
+    resonance TEXT NOT NULL,
+    reverse INTEGER NOT NULL,
+    q_value REAL NOT NULL,
+    a0 REAL NOT NULL,
+    a1 REAL NOT NULL,
+    a2 REAL NOT NULL,
+    a3 REAL NOT NULL,
+    a4 REAL NOT NULL,
+    a5 REAL NOT NULL,
+    a6 REAL NOT NULL
+);
+";
+
+impl Library {
+    /// Write the library into the SQLite database `connection`.
+    ///
+    /// The tables are created if they don't exist yet, and the sets are added to any that are
+    /// already there:
+    ///
+    /// * `reactions`: a row for each reaction, with its `id`, its `name` (such as
+    ///   `he4 c12 -> o16`), and its `chapter` (null if it doesn't fit in one)
+    /// * `species`: a row for each nuclide in each reaction, with the `reaction_id`, its `role`
+    ///   (`reactant` or `product`), its `position` in the reaction, and the `nuclide` name
+    /// * `sets`: a row for each set, with its `reaction_id`, `label`, `resonance` flag (as it is
+    ///   written in reaclib), `reverse` flag, `q_value`, and parameters `a0` to `a6`
+    ///
+    /// For example, all of the sets that have c12 as a reactant can be found with:
+    ///
+    /// ```sql
+    /// SELECT reactions.name, sets.label FROM sets
+    /// JOIN reactions ON reactions.id = sets.reaction_id
+    /// JOIN species ON species.reaction_id = sets.reaction_id
+    /// WHERE species.nuclide = 'c12' AND species.role = 'reactant';
+    /// ```
+    ///
+    /// A set's row has a column for each of its fields in the reaclib format and nothing else.
+    /// Its [`raw_params`][Set::raw_params], [`raw_lines`][Set::raw_lines], and
+    /// [`validity`][Set::validity] have no columns, and neither does the library's
+    /// [metadata][Self::metadata], so they are lost in a round trip through
+    /// [`read_sqlite`][Self::read_sqlite].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is a database error.
+    pub fn write_sqlite(&self, connection: &Connection) -> Result<(), RError> {
+        let transaction = connection.unchecked_transaction()?;
+        transaction.execute_batch(SCHEMA)?;
+        {
+            let mut find_reaction =
+                transaction.prepare("SELECT id FROM reactions WHERE name = ?1")?;
+            let mut insert_reaction =
+                transaction.prepare("INSERT INTO reactions (name, chapter) VALUES (?1, ?2)")?;
+            let mut insert_species = transaction.prepare(
+                "INSERT INTO species (reaction_id, role, position, nuclide) VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            let mut insert_set = transaction.prepare(
+                "INSERT INTO sets (reaction_id, label, resonance, reverse, q_value, \
+                 a0, a1, a2, a3, a4, a5, a6) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            )?;
+
+            let mut ids = HashMap::new();
+            for set in self.sets() {
+                let reaction = Reaction::from(set);
+                let id = if let Some(&id) = ids.get(&reaction) {
+                    id
+                } else {
+                    let name = reaction.to_string();
+                    let existing = find_reaction
+                        .query_row([&name], |row| row.get::<_, i64>(0))
+                        .optional()?;
+                    let id = if let Some(id) = existing {
+                        id
+                    } else {
+                        let chapter = reaction.chapter().map(|c| c.number());
+                        insert_reaction.execute(params![name, chapter])?;
+                        let id = transaction.last_insert_rowid();
+                        let species = [
+                            ("reactant", reaction.reactants()),
+                            ("product", reaction.products()),
+                        ];
+                        for (role, nuclides) in species {
+                            for (position, nuclide) in (0_u8..).zip(nuclides) {
+                                insert_species.execute(params![
+                                    id,
+                                    role,
+                                    position,
+                                    nuclide.as_str()
+                                ])?;
+                            }
+                        }
+                        id
+                    };
+                    ids.insert(reaction, id);
+                    id
+                };
+
+                let [a0, a1, a2, a3, a4, a5, a6] = set.params;
+                insert_set.execute(params![
+                    id,
+                    set.label.as_str(),
+                    set.resonance.as_char().to_string(),
+                    set.reverse,
+                    set.q_value,
+                    a0,
+                    a1,
+                    a2,
+                    a3,
+                    a4,
+                    a5,
+                    a6,
+                ])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Read a library from an SQLite database that was written by
+    /// [`write_sqlite`][Self::write_sqlite].
+    ///
+    /// The sets are read in the order they were written.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is a database error, or if the database has invalid data.
+    pub fn read_sqlite(connection: &Connection) -> Result<Self, RError> {
+        let mut reactions: HashMap<i64, (Species, Species)> = HashMap::new();
+        let mut statement = connection.prepare(
+            "SELECT reaction_id, role, nuclide FROM species ORDER BY reaction_id, position",
+        )?;
+        let mut rows = statement.query([])?;
+        while let Some(row) = rows.next()? {
+            let (reactants, products) = reactions.entry(row.get(0)?).or_default();
+            let nuclides = match row.get_ref(1)?.as_str()? {
+                "reactant" => reactants,
+                "product" => products,
+                role => return Err(RError::Sqlite(format!("unknown role: {role}"))),
+            };
+            nuclides.push(row.get_ref(2)?.as_str()?.parse()?);
+        }
+
+        let mut statement = connection.prepare(
+            "SELECT reaction_id, label, resonance, reverse, q_value, a0, a1, a2, a3, a4, a5, a6 \
+             FROM sets ORDER BY id",
+        )?;
+        let mut rows = statement.query([])?;
+        let mut sets = Vec::new();
+        while let Some(row) = rows.next()? {
+            let reaction_id: i64 = row.get(0)?;
+            let (reactants, products) = reactions
+                .get(&reaction_id)
+                .ok_or_else(|| RError::Sqlite(format!("unknown reaction: {reaction_id}")))?;
+            let label = row.get_ref(1)?.as_str()?;
+            let mut params = [0.0; 7];
+            for (i, p) in params.iter_mut().enumerate() {
+                *p = row.get(5 + i)?;
+            }
+            sets.push(Set {
+                reactants: reactants.clone(),
+                products: products.clone(),
+                label: ArrayString::from(label)
+                    .map_err(|_| RError::Sqlite(format!("label too long: {label}")))?,
+                resonance: row.get_ref(2)?.as_str()?.parse()?,
+                reverse: row.get(3)?,
+                q_value: row.get(4)?,
+                params,
+                raw_params: None,
+                raw_lines: None,
+                validity: None,
+            });
+        }
+        Ok(sets.into_iter().collect())
+    }
+
+    /// Save the library to an SQLite database file at `path`.
+    ///
+    /// See [`write_sqlite`][Self::write_sqlite].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is a database error.
+    pub fn save_sqlite(&self, path: impl AsRef<Path>) -> Result<(), RError> {
+        self.write_sqlite(&Connection::open(path)?)
+    }
+
+    /// Load a library from an SQLite database file at `path`.
+    ///
+    /// See [`read_sqlite`][Self::read_sqlite].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is a database error, or if the database has invalid data.
+    pub fn load_sqlite(path: impl AsRef<Path>) -> Result<Self, RError> {
+        Self::read_sqlite(&Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )?)
+    }
+}
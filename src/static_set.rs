@@ -0,0 +1,130 @@
+use crate::{Reaction, Resonance, Set, Species};
+use arrayvec::ArrayString;
+
+/// A set that can be stored in a `static`, as created by [`include_reaclib`][crate::include_reaclib].
+///
+/// It has the same data as [`Set`], but with borrowed strings instead of
+/// [`Nuclide`][crate::Nuclide]s and [`ArrayString`]s.
+/// Converting it into a `Set` only copies the names, which were checked when the file was
+/// parsed, so it is cheap and can't fail.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct StaticSet {
+    reactants: &'static [&'static str],
+    products: &'static [&'static str],
+    label: &'static str,
+    resonance: Resonance,
+    reverse: bool,
+    q_value: f64,
+    params: [f64; 7],
+}
+
+impl StaticSet {
+    // used by the code that `include_reaclib` generates, where the names are at most 5 bytes
+    // and the label is at most 4 bytes, since they were read from their columns
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn new(
+        reactants: &'static [&'static str],
+        products: &'static [&'static str],
+        label: &'static str,
+        resonance: Resonance,
+        reverse: bool,
+        q_value: f64,
+        params: [f64; 7],
+    ) -> Self {
+        Self {
+            reactants,
+            products,
+            label,
+            resonance,
+            reverse,
+            q_value,
+            params,
+        }
+    }
+
+    /// The names of the nuclides going into a reaction.
+    #[must_use]
+    pub const fn reactants(&self) -> &'static [&'static str] {
+        self.reactants
+    }
+
+    /// The names of the nuclides resulting from a reaction.
+    #[must_use]
+    pub const fn products(&self) -> &'static [&'static str] {
+        self.products
+    }
+
+    /// A label denoting the source of the reaction.
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        self.label
+    }
+
+    /// The resonance flag for the reaction.
+    #[must_use]
+    pub const fn resonance(&self) -> Resonance {
+        self.resonance
+    }
+
+    /// A flag denoting whether the reaction rate was derived from the reverse rate using detailed
+    /// balance.
+    #[must_use]
+    pub const fn reverse(&self) -> bool {
+        self.reverse
+    }
+
+    /// The Q-value of the reaction.
+    #[must_use]
+    pub const fn q_value(&self) -> f64 {
+        self.q_value
+    }
+
+    /// The parameters of this reaction rate set.
+    #[must_use]
+    pub const fn params(&self) -> [f64; 7] {
+        self.params
+    }
+
+    /// The reaction that this set belongs to.
+    #[must_use]
+    pub fn reaction(&self) -> Reaction {
+        Reaction::new(nuclides(self.reactants), nuclides(self.products))
+    }
+
+    /// Calculate the rate, like [`Set::rate`].
+    #[must_use]
+    pub fn rate(&self, temperature: f64) -> f64 {
+        Set::from(self).rate(temperature)
+    }
+}
+
+impl From<&StaticSet> for Set {
+    fn from(set: &StaticSet) -> Self {
+        Self {
+            reactants: nuclides(set.reactants),
+            products: nuclides(set.products),
+            label: ArrayString::from(set.label).expect("the label is at most 4 bytes"),
+            resonance: set.resonance,
+            reverse: set.reverse,
+            q_value: set.q_value,
+            params: set.params,
+            raw_params: None,
+            raw_lines: None,
+            validity: None,
+        }
+    }
+}
+
+impl From<StaticSet> for Set {
+    fn from(set: StaticSet) -> Self {
+        Self::from(&set)
+    }
+}
+
+fn nuclides(names: &[&str]) -> Species {
+    names
+        .iter()
+        .map(|n| n.parse().expect("the names are at most 5 bytes"))
+        .collect()
+}
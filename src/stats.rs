@@ -0,0 +1,193 @@
+use crate::{
+    error::ReaclibError as RError, is_fatal, Chapter, Iter, Library, LineSource, Nuclide, Reaction,
+    Resonance, Set,
+};
+use arrayvec::ArrayString;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// A summary of a collection of sets, from [`Library::stats`].
+///
+/// This is meant for quick sanity checks, such as making sure that a downloaded file has the
+/// chapters and sources that are expected.
+/// A file can be summarized while it is read, without storing the sets, with [`Iter::stats`] or
+/// a [`StatsAccumulator`].
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Chapter, Format, Library};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+/// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+///
+/// let stats = library.stats();
+/// assert_eq!(stats.sets, 1);
+/// assert_eq!(stats.chapters[&Chapter::Chapter1], 1);
+/// assert_eq!(stats.labels["wc12"], 1);
+/// assert_eq!(stats.nuclides.len(), 2);
+/// assert_eq!(stats.max_q.unwrap().q_value, 7.82300e-01);
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+#[non_exhaustive]
+pub struct Stats {
+    /// The number of sets.
+    pub sets: usize,
+    /// The number of distinct reactions.
+    pub reactions: usize,
+    /// The number of sets in each chapter.
+    pub chapters: HashMap<Chapter, usize>,
+    /// The number of sets with each label.
+    pub labels: BTreeMap<ArrayString<4>, usize>,
+    /// The number of sets with each resonance flag.
+    pub resonances: HashMap<Resonance, usize>,
+    /// The number of sets with the reverse flag.
+    pub reverse: usize,
+    /// Every nuclide that appears in a set, in [canonical order][Nuclide::canonical_cmp].
+    pub nuclides: Vec<Nuclide>,
+    /// The set with the lowest Q-value, if there are any sets.
+    pub min_q: Option<Set>,
+    /// The set with the highest Q-value, if there are any sets.
+    pub max_q: Option<Set>,
+    /// A histogram of the Q-values, with the number of sets in each 1 MeV bin, keyed by the
+    /// lower end of the bin (the Q-value rounded down, in MeV).
+    ///
+    /// Sets whose Q-value isn't finite aren't counted.
+    pub q_histogram: BTreeMap<i32, usize>,
+    /// The number of sets that failed to parse, when the stats are gathered while reading.
+    pub errors: usize,
+}
+
+impl Stats {
+    /// Summarize `sets`.
+    pub fn from_sets<'a>(sets: impl IntoIterator<Item = &'a Set>) -> Self {
+        let mut accumulator = StatsAccumulator::new();
+        for set in sets {
+            accumulator.add(set);
+        }
+        accumulator.finish()
+    }
+}
+
+/// Gathers [`Stats`] one set at a time, so that a file can be summarized in a single pass
+/// without storing its sets.
+///
+/// Only the sets with the lowest and highest Q-values, and the distinct reactions and nuclides,
+/// are kept.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Iter, StatsAccumulator};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00
+/// 1
+///          t  he3                            wc12x     1.86000e-02
+/// -2.014560e+01 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+///
+/// let mut accumulator = StatsAccumulator::new();
+/// for result in Iter::new(reader, Format::Reaclib2) {
+///     accumulator.add_result(&result);
+/// }
+/// let stats = accumulator.finish();
+/// assert_eq!(stats.sets, 1);
+/// assert_eq!(stats.errors, 1);
+/// assert_eq!(stats.q_histogram[&0], 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StatsAccumulator {
+    stats: Stats,
+    reactions: HashSet<Reaction>,
+    nuclides: HashSet<Nuclide>,
+}
+
+impl StatsAccumulator {
+    /// Creates a new `StatsAccumulator`, with no sets.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `set` to the stats.
+    pub fn add(&mut self, set: &Set) {
+        let stats = &mut self.stats;
+        stats.sets += 1;
+        self.reactions.insert(set.reaction());
+        if let Some(chapter) = Chapter::from_counts(set.reactants.len(), set.products.len()) {
+            *stats.chapters.entry(chapter).or_default() += 1;
+        }
+        *stats.labels.entry(set.label).or_default() += 1;
+        *stats.resonances.entry(set.resonance).or_default() += 1;
+        stats.reverse += usize::from(set.reverse);
+        self.nuclides
+            .extend(set.reactants.iter().chain(&set.products).copied());
+
+        if stats.min_q.as_ref().is_none_or(|s| set.q_value < s.q_value) {
+            stats.min_q = Some(set.clone());
+        }
+        if stats.max_q.as_ref().is_none_or(|s| set.q_value > s.q_value) {
+            stats.max_q = Some(set.clone());
+        }
+        if set.q_value.is_finite() {
+            // Q-values are at most a few hundred MeV, so this can't truncate
+            #[allow(clippy::cast_possible_truncation)]
+            let bin = set.q_value.floor() as i32;
+            *stats.q_histogram.entry(bin).or_default() += 1;
+        }
+    }
+
+    /// Add a set that was read to the stats, or count it as an error.
+    pub fn add_result(&mut self, result: &Result<Set, RError>) {
+        match result {
+            Ok(set) => self.add(set),
+            Err(_) => self.stats.errors += 1,
+        }
+    }
+
+    /// The stats of all of the sets that have been added.
+    #[must_use]
+    pub fn finish(self) -> Stats {
+        let mut stats = self.stats;
+        stats.reactions = self.reactions.len();
+        stats.nuclides = self.nuclides.into_iter().collect();
+        stats.nuclides.sort_by(Nuclide::canonical_cmp);
+        stats
+    }
+}
+
+impl<R: LineSource> Iter<R> {
+    /// Read every set and summarize them, without storing them.
+    ///
+    /// Sets that fail to parse are counted in [`Stats::errors`].
+    /// Like [`collect_partial`][Self::collect_partial], reading stops at an io error (other than
+    /// invalid UTF-8), since it would likely repeat.
+    pub fn stats(self) -> Stats {
+        let mut accumulator = StatsAccumulator::new();
+        for result in self {
+            let fatal = result.as_ref().is_err_and(is_fatal);
+            accumulator.add_result(&result);
+            if fatal {
+                break;
+            }
+        }
+        accumulator.finish()
+    }
+}
+
+impl Library {
+    /// A summary of the sets in the library.
+    ///
+    /// See [`Stats`].
+    #[must_use]
+    pub fn stats(&self) -> Stats {
+        Stats::from_sets(self.sets())
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{log_grid, RateEvaluator, ReactionRate};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A rate given by a table of values, which is interpolated with a cubic spline.
+///
+/// The interpolation is done in `ln(rate)` against `ln(temperature)`, where rates are smooth.
+/// Outside of the table, the rate at the nearest end is used.
+/// A temperature that is NaN or isn't positive has no logarithm, so it gives NaN.
+/// Rates of 0 (or less) are treated as [`f64::MIN_POSITIVE`], since they can't be interpolated
+/// logarithmically.
+///
+/// This is useful for replacing the exponential fit in tight loops, by tabulating it once with
+/// [`ReactionRate::tabulate`], or for rates that only exist as tables.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Library};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"4
+///          p  c12  n13                       nacrn     1.94400e+00
+///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+///  4.443620e+00-3.158980e+00-6.666670e-01");
+/// let library = Library::from_reader(reader, Format::Reaclib2).unwrap();
+/// let reaction = library.reactions().next().unwrap();
+/// let rate = library.reaction_rate(reaction).unwrap();
+///
+/// let table = rate.tabulate(0.01, 10.0, 200);
+/// assert!((table.rate(0.15) / rate.rate(0.15) - 1.0).abs() < 1e-4);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TabulatedRate {
+    ln_temperatures: Vec<f64>,
+    ln_rates: Vec<f64>,
+    // the second derivatives of the spline at each point
+    second_derivatives: Vec<f64>,
+}
+
+impl TabulatedRate {
+    /// Creates a new `TabulatedRate` from `temperatures` (in GK) and the `rates` at those
+    /// temperatures.
+    ///
+    /// Returns `None` if the lengths are different, there are no points, or the temperatures
+    /// aren't finite, positive, and strictly increasing.
+    #[must_use]
+    pub fn new(temperatures: &[f64], rates: &[f64]) -> Option<Self> {
+        if temperatures.len() != rates.len()
+            || temperatures.is_empty()
+            || temperatures[0] <= 0.0
+            || temperatures.iter().any(|t| !t.is_finite())
+            || temperatures.windows(2).any(|w| w[0] >= w[1])
+        {
+            return None;
+        }
+
+        let ln_temperatures = temperatures.iter().map(|t| t.ln()).collect::<Vec<_>>();
+        let ln_rates = rates
+            .iter()
+            .map(|r| r.max(f64::MIN_POSITIVE).ln())
+            .collect::<Vec<_>>();
+        let second_derivatives = natural_spline(&ln_temperatures, &ln_rates);
+
+        Some(Self {
+            ln_temperatures,
+            ln_rates,
+            second_derivatives,
+        })
+    }
+
+    /// The temperatures (in GK) at which the rate is tabulated.
+    pub fn temperatures(&self) -> impl Iterator<Item = f64> + '_ {
+        self.ln_temperatures.iter().map(|t| t.exp())
+    }
+
+    /// The tabulated rates.
+    pub fn rates(&self) -> impl Iterator<Item = f64> + '_ {
+        self.ln_rates.iter().map(|r| r.exp())
+    }
+
+    /// Interpolate the rate at `temperature`.
+    #[must_use]
+    pub fn rate(&self, temperature: f64) -> f64 {
+        self.ln_rate(temperature).exp()
+    }
+
+    /// Interpolate the natural logarithm of the rate at `temperature`.
+    #[must_use]
+    #[allow(clippy::many_single_char_names)]
+    pub fn ln_rate(&self, temperature: f64) -> f64 {
+        let (x, y, y2) = (
+            &self.ln_temperatures,
+            &self.ln_rates,
+            &self.second_derivatives,
+        );
+        let n = x.len();
+        if temperature.is_nan() || temperature <= 0.0 {
+            return f64::NAN;
+        }
+        let t = temperature.ln();
+
+        if n == 1 || t <= x[0] {
+            return y[0];
+        }
+        if t >= x[n - 1] {
+            return y[n - 1];
+        }
+
+        // the index of the interval containing `t`
+        let i = x.partition_point(|&xi| xi <= t) - 1;
+        let h = x[i + 1] - x[i];
+        let a = (x[i + 1] - t) / h;
+        let b = (t - x[i]) / h;
+        a * y[i]
+            + b * y[i + 1]
+            + ((a * a * a - a) * y2[i] + (b * b * b - b) * y2[i + 1]) * h * h / 6.0
+    }
+}
+
+impl RateEvaluator for TabulatedRate {
+    fn ln_rate(&self, temperature: f64) -> f64 {
+        Self::ln_rate(self, temperature)
+    }
+}
+
+impl ReactionRate {
+    /// Tabulate the total rate on a logarithmic grid of `points` temperatures between `min` and
+    /// `max`, for fast interpolated evaluation.
+    ///
+    /// See [`TabulatedRate`] and [`log_grid`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid is invalid, i.e. if `min` isn't positive, or if `points` is 0 or `min`
+    /// isn't less than `max` when `points` is more than 1.
+    #[must_use]
+    pub fn tabulate(&self, min: f64, max: f64, points: usize) -> TabulatedRate {
+        let temperatures = log_grid(min, max, points);
+        let rates = temperatures
+            .iter()
+            .map(|&t| self.rate(t))
+            .collect::<Vec<_>>();
+        TabulatedRate::new(&temperatures, &rates).expect("the grid is invalid")
+    }
+}
+
+// solve for the second derivatives of a natural cubic spline through (x, y)
+#[allow(clippy::many_single_char_names)]
+fn natural_spline(x: &[f64], y: &[f64]) -> Vec<f64> {
+    let n = x.len();
+    let mut y2 = vec![0.0; n];
+    if n < 3 {
+        return y2;
+    }
+
+    // forward elimination of the tridiagonal system
+    let mut u = vec![0.0; n];
+    for i in 1..n - 1 {
+        let sig = (x[i] - x[i - 1]) / (x[i + 1] - x[i - 1]);
+        let p = sig.mul_add(y2[i - 1], 2.0);
+        y2[i] = (sig - 1.0) / p;
+        let d = (y[i + 1] - y[i]) / (x[i + 1] - x[i]) - (y[i] - y[i - 1]) / (x[i] - x[i - 1]);
+        u[i] = (6.0 * d / (x[i + 1] - x[i - 1]) - sig * u[i - 1]) / p;
+    }
+
+    // back substitution
+    y2[n - 1] = 0.0;
+    for i in (0..n - 1).rev() {
+        y2[i] = y2[i].mul_add(y2[i + 1], u[i]);
+    }
+    y2
+}
@@ -1,2 +1,55 @@
+#[cfg(feature = "arrow")]
+mod arrow;
+mod batch;
+mod bruslib;
+#[cfg(feature = "cache")]
+mod cache;
+mod changelog;
+mod codegen;
+#[cfg(feature = "download")]
+mod download;
+mod fit;
+mod fit_form;
+mod header;
+mod kinematics;
+mod latex;
+mod library;
+#[cfg(feature = "macros")]
+mod macros;
+mod mesa;
+mod network;
+mod nuclide;
+mod packed;
+mod rate;
+mod rate_table;
+#[cfg(feature = "schemars")]
+mod schemars;
+#[cfg(feature = "serde")]
+mod serde;
+mod set;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(feature = "tracing")]
+mod tracing;
+#[cfg(feature = "uom")]
+mod units;
 mod v1;
 mod v2;
+#[cfg(feature = "weak-tables")]
+mod weak_table;
+mod winvn;
+mod writer;
+
+use crate::{Format, Library, Species};
+use std::io::Cursor;
+
+// the library in `tests/library/network`, which many of the tests use
+fn network() -> Library {
+    let reader = Cursor::new(include_str!("tests/library/network"));
+    Library::from_reader(reader, Format::Reaclib2).unwrap()
+}
+
+// the nuclides with the reaclib names `names`
+fn nuclides(names: &[&str]) -> Species {
+    names.iter().map(|n| n.parse().unwrap()).collect()
+}
@@ -0,0 +1,94 @@
+use super::network;
+use crate::Library;
+use arrow_array::{
+    cast::AsArray,
+    types::{Float64Type, UInt8Type},
+    Array, RecordBatch,
+};
+
+#[test]
+fn record_batch() {
+    let library = network();
+    let batch = library.to_record_batch();
+    assert_eq!(batch.num_rows(), library.sets().len());
+    assert_eq!(batch.num_columns(), 14);
+    assert_eq!(batch.schema(), Library::arrow_schema());
+
+    let reactants = batch.column_by_name("reactants").unwrap().as_list::<i32>();
+    let products = batch.column_by_name("products").unwrap().as_list::<i32>();
+    let chapter = batch.column_by_name("chapter").unwrap();
+    let label = batch.column_by_name("label").unwrap().as_string::<i32>();
+    let resonance = batch
+        .column_by_name("resonance")
+        .unwrap()
+        .as_string::<i32>();
+    let reverse = batch.column_by_name("reverse").unwrap().as_boolean();
+    let q_value = batch
+        .column_by_name("q_value")
+        .unwrap()
+        .as_primitive::<Float64Type>();
+
+    for (i, set) in library.sets().iter().enumerate() {
+        let names = |list: &arrow_array::ListArray| {
+            list.value(i)
+                .as_string::<i32>()
+                .iter()
+                .map(|n| n.unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(reactants),
+            set.reactants
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            names(products),
+            set.products
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            chapter.as_primitive::<UInt8Type>().value(i),
+            crate::Reaction::from(set).chapter().unwrap().number()
+        );
+        assert_eq!(label.value(i), set.label.as_str());
+        assert_eq!(reverse.value(i), set.reverse);
+        assert_eq!(q_value.value(i), set.q_value);
+        for (j, p) in set.params.iter().enumerate() {
+            let column = batch.column_by_name(&format!("a{j}")).unwrap();
+            assert_eq!(column.as_primitive::<Float64Type>().value(i), *p);
+        }
+    }
+    assert_eq!(resonance.value(0), "n");
+    assert_eq!(chapter.null_count(), 0);
+}
+
+#[test]
+fn record_batches() {
+    let library = network();
+    let batches = library.record_batches(4).collect::<Vec<_>>();
+    assert_eq!(
+        batches
+            .iter()
+            .map(RecordBatch::num_rows)
+            .collect::<Vec<_>>(),
+        [4, library.sets().len() - 4]
+    );
+    let q_values = batches
+        .iter()
+        .flat_map(|b| {
+            let column = b.column_by_name("q_value").unwrap();
+            column.as_primitive::<Float64Type>().values().to_vec()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        q_values,
+        library.sets().iter().map(|s| s.q_value).collect::<Vec<_>>()
+    );
+
+    assert_eq!(Library::new().to_record_batch().num_rows(), 0);
+    assert_eq!(Library::new().record_batches(4).count(), 0);
+}
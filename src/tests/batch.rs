@@ -0,0 +1,67 @@
+use crate::{convert_dir, convert_dir_with, Format, Library, ReaclibError};
+use std::fs;
+
+#[test]
+fn convert() {
+    let dir = std::env::temp_dir().join(format!("reaclib-test-{}-batch", std::process::id()));
+    let (input, output) = (dir.join("input"), dir.join("output"));
+    fs::create_dir_all(input.join("nested")).unwrap();
+    fs::copy("src/tests/library/network", input.join("network")).unwrap();
+    fs::copy("src/tests/library/network", input.join("nested/network")).unwrap();
+    fs::write(input.join("broken"), "not a reaclib file\n").unwrap();
+
+    let manifest = convert_dir(&input, &output, Format::Reaclib2, Format::Reaclib1).unwrap();
+    assert_eq!(manifest.conversions.len(), 3);
+    assert!(!manifest.is_success());
+    assert_eq!(manifest.succeeded().count(), 2);
+    let failed = manifest.failed().collect::<Vec<_>>();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].input, input.join("broken"));
+    assert!(!output.join("broken").exists());
+
+    let expected = Library::from_reader(
+        fs::read("src/tests/library/network").unwrap().as_slice(),
+        Format::Reaclib2,
+    )
+    .unwrap();
+    for name in ["network", "nested/network"] {
+        let converted = Library::from_reader(
+            fs::read(output.join(name)).unwrap().as_slice(),
+            Format::Reaclib1,
+        )
+        .unwrap();
+        assert_eq!(converted, expected);
+    }
+
+    let mut tsv = Vec::new();
+    manifest.write(&mut tsv).unwrap();
+    let tsv = String::from_utf8(tsv).unwrap();
+    assert_eq!(tsv.lines().count(), 3);
+    assert!(tsv
+        .lines()
+        .any(|l| l.starts_with("ok\t") && l.ends_with("\t7")));
+
+    // a custom writer and extension, with the output inside of the input
+    let inner = input.join("converted");
+    let manifest = convert_dir_with(&input, &inner, Format::Reaclib2, Some("txt"), |sets, w| {
+        Ok(writeln!(w, "{}", sets.len())?)
+    })
+    .unwrap();
+    assert_eq!(manifest.conversions.len(), 3);
+    assert_eq!(
+        fs::read_to_string(inner.join("nested/network.txt")).unwrap(),
+        "7\n"
+    );
+
+    assert!(matches!(
+        convert_dir(
+            dir.join("missing"),
+            &output,
+            Format::Reaclib2,
+            Format::Reaclib2
+        ),
+        Err(ReaclibError::Io(_))
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
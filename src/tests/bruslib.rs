@@ -0,0 +1,55 @@
+use crate::{Bruslib, Reaction};
+use std::io::Cursor;
+
+const TABLE: &str = r"# rates in cm3/mol/s
+ Z=  26 A=  56
+ T9      (n,g)       (n,p)       (p,a)       (a,n)
+ 0.1     1.000E+07   0.000E+00   1.000E-20   0.000E+00
+ 0.5     2.000E+07   1.000E-10   1.000E-08   0.000E+00
+ 1.0     3.000E+07   1.000E-05   1.000E-04   1.000E-12
+ Z=2 A=4
+ T9      (a,g)
+ 0.1     1.000E-30
+ 1.0     1.000E-20
+";
+
+#[test]
+fn reactions() {
+    let bruslib = Bruslib::from_reader(Cursor::new(TABLE)).unwrap();
+    let reactions = bruslib
+        .reactions()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        reactions,
+        [
+            "n fe56 -> fe57",
+            "n fe56 -> p mn56",
+            "p fe56 -> he4 mn53",
+            "he4 fe56 -> n ni59",
+            "he4 he4 -> be8",
+        ]
+    );
+
+    let reaction = "n fe56 -> p mn56".parse::<Reaction>().unwrap();
+    let rate = bruslib.get(&reaction).unwrap();
+    assert!((rate.rate(0.5) / 1e-10 - 1.0).abs() < 1e-12);
+    // rates of 0 are kept as the smallest positive rate
+    assert!(rate.rate(0.1) < 1e-300);
+    assert_eq!(bruslib.iter().count(), 5);
+}
+
+#[test]
+fn invalid() {
+    let read = |s: &str| Bruslib::from_reader(Cursor::new(s));
+    assert_eq!(read("").unwrap().reactions().count(), 0);
+    // rates without a target or column names
+    assert!(read(" T9 (n,g)\n 1.0 1.0").is_err());
+    assert!(read(" Z= 26 A= 56\n 1.0 1.0").is_err());
+    // an unknown particle
+    assert!(read(" Z= 26 A= 56\n T9 (n,x)\n 1.0 1.0").is_err());
+    // too few columns
+    assert!(read(" Z= 26 A= 56\n T9 (n,g) (p,g)\n 1.0 1.0").is_err());
+    // decreasing temperatures
+    assert!(read(" Z= 26 A= 56\n T9 (n,g)\n 1.0 1.0\n 0.1 1.0").is_err());
+}
@@ -0,0 +1,69 @@
+use crate::{error::ReaclibError, Format, Iter, Library, LibraryMetadata, ValidityRange};
+use std::io::Cursor;
+
+fn network() -> Library {
+    let reader = Cursor::new(include_str!("library/network"));
+    Iter::with_raw_params(reader, Format::Reaclib2)
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+#[test]
+fn round_trip() {
+    let mut sets = network().sets().to_vec();
+    sets[0].validity = Some(ValidityRange::new(0.1, 10.0));
+    let metadata = LibraryMetadata {
+        name: Some("network".to_string()),
+        ..LibraryMetadata::default()
+    };
+    let library = sets
+        .into_iter()
+        .collect::<Library>()
+        .with_metadata(metadata);
+
+    let mut cache = Vec::new();
+    library.write_cache(&mut cache).unwrap();
+    let reread = Library::read_cache(Cursor::new(&cache)).unwrap();
+    assert_eq!(library, reread);
+    assert!(reread.sets()[0].raw_params.is_some());
+    assert_eq!(reread.metadata().name.as_deref(), Some("network"));
+}
+
+#[test]
+fn file() {
+    let library = network();
+    let path = std::env::temp_dir().join(format!("reaclib-test-{}.cache", std::process::id()));
+    library.save_cache(&path).unwrap();
+    let reread = Library::load_cache(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(reread.unwrap(), library);
+}
+
+#[test]
+fn invalid() {
+    let mut cache = Vec::new();
+    network().write_cache(&mut cache).unwrap();
+
+    // a different version is rejected
+    let mut other = cache.clone();
+    other[8] += 1;
+    assert_eq!(
+        Library::read_cache(Cursor::new(&other)),
+        Err(ReaclibError::CacheVersion(4))
+    );
+
+    // so is anything that isn't a cache
+    let text = include_str!("library/network").as_bytes();
+    assert_eq!(
+        Library::read_cache(Cursor::new(text)),
+        Err(ReaclibError::InvalidCache)
+    );
+    assert_eq!(
+        Library::read_cache(Cursor::new(&cache[..4])),
+        Err(ReaclibError::InvalidCache)
+    );
+    assert_eq!(
+        Library::read_cache(Cursor::new(&cache[..cache.len() - 1])),
+        Err(ReaclibError::InvalidCache)
+    );
+}
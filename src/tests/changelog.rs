@@ -0,0 +1,79 @@
+use super::network;
+use crate::{Changelog, Library};
+
+#[test]
+fn changelog() {
+    let old = network();
+    let mut sets = old
+        .sets()
+        .iter()
+        .filter(|set| set.label.as_str() != "ia08")
+        .cloned()
+        .collect::<Vec<_>>();
+    // a new rate for n13 -> c13, and a new reaction
+    sets[2].params[0] -= 2f64.ln();
+    let mut added = sets[0].clone();
+    added.products = ["n", "n13"].iter().map(|n| n.parse().unwrap()).collect();
+    sets.push(added);
+    let new = sets.into_iter().collect::<Library>();
+
+    let changelog = old.changelog(&new, 0.1, 10.0, 11);
+    assert_eq!(changelog.added.len(), 1);
+    assert_eq!(changelog.added[0].to_string(), "p c12 -> n n13");
+    assert_eq!(changelog.removed.len(), 1);
+    assert_eq!(changelog.removed[0].to_string(), "p o16 -> f17");
+    assert_eq!(changelog.refit.len(), 1);
+    assert_eq!(changelog.refit[0].reaction.to_string(), "n13 -> c13");
+    assert!((changelog.refit[0].mean_ratio - 0.5).abs() < 1e-12);
+    assert_eq!(changelog.unchanged, 4);
+    assert!(!changelog.is_empty());
+
+    assert_eq!(
+        changelog.to_string(),
+        "1 added, 1 removed, 1 refit, 4 unchanged
+
+Added:
+  + p c12 -> n n13
+
+Removed:
+  - p o16 -> f17
+
+Refit:
+  ~ n13 -> c13: up to a factor of 2.000 (at T9 = 10.000), mean ratio 0.500
+"
+    );
+
+    // without a grid, only the added and removed reactions are found
+    let no_grid = old.changelog(&new, 0.1, 10.0, 0);
+    assert_eq!(no_grid.added, changelog.added);
+    assert_eq!(no_grid.removed, changelog.removed);
+    assert!(no_grid.refit.is_empty());
+    assert_eq!(no_grid.unchanged, 0);
+
+    let unchanged = old.changelog(&old, 0.1, 10.0, 11);
+    assert!(unchanged.is_empty());
+    assert_eq!(unchanged.unchanged, 6);
+    assert_eq!(
+        unchanged,
+        Changelog {
+            unchanged: 6,
+            ..Changelog::default()
+        }
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    let old = network();
+    let new = old.sets()[1..].iter().cloned().collect::<Library>();
+    let changelog = old.changelog(&new, 0.1, 10.0, 11);
+    // p c12 -> n13 lost one of its two sets
+    assert_eq!(changelog.refit.len(), 1);
+
+    let json = serde_json::to_value(&changelog).unwrap();
+    assert_eq!(json["refit"][0]["reaction"]["reactants"][1], "c12");
+    assert_eq!(json["unchanged"], 5);
+    let back: Changelog = serde_json::from_value(json).unwrap();
+    assert_eq!(back, changelog);
+}
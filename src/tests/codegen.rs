@@ -0,0 +1,63 @@
+use super::network;
+use crate::{write_code, Language, Nuclide, Reaction, ReactionRate};
+
+fn rates() -> Vec<ReactionRate> {
+    let library = network();
+    let mut rates = library
+        .reactions()
+        .filter_map(|r| library.reaction_rate(r))
+        .collect::<Vec<_>>();
+    rates.sort_by_key(|r| r.reaction.to_string());
+    rates
+}
+
+fn code(rates: &[ReactionRate], language: Language) -> String {
+    let mut output = Vec::new();
+    write_code(&mut output, rates, language).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn c() {
+    let code = code(&rates(), Language::C);
+    assert!(code.starts_with("/* generated by reaclib-rs */\n#include <math.h>\n"));
+    assert_eq!(code.matches("double rate_").count(), 6);
+    // both sets of p + c12 are summed in one function
+    assert!(code.contains("/* p c12 -> n13 */\ndouble rate_p_c12_to_n13(double t9) {\n    static const double params[2][7] = {\n"));
+    // the parameters are written exactly
+    assert!(code.contains(
+        "        {6.96526e1, -1.39254e0, 5.89128e1, -1.48273e2, 9.08324e0, -5.41041e-1, 7.03554e1},\n"
+    ));
+}
+
+#[test]
+fn fortran() {
+    let code = code(&rates(), Language::Fortran);
+    assert!(code.starts_with("! generated by reaclib-rs\nmodule reaclib_rates\n"));
+    assert!(code.ends_with("end module reaclib_rates\n"));
+    assert_eq!(code.matches("  end function rate_").count(), 6);
+    assert!(code.contains("  function rate_o16_to_he4_c12(t9) result(rate)\n"));
+    assert!(code.contains(
+        "      6.96526e1_dp, -1.39254e0_dp, 5.89128e1_dp, -1.48273e2_dp, &\n      9.08324e0_dp, -5.41041e-1_dp, 7.03554e1_dp &\n      ], [7, 1])\n"
+    ));
+    // free-form lines can't be longer than 132 characters
+    assert!(code.lines().all(|l| l.len() <= 132));
+}
+
+// names that aren't valid identifiers are replaced, and rates without sets are 0
+#[test]
+fn special_cases() {
+    let nuclide = |s: &str| s.parse::<Nuclide>().unwrap();
+    let rate = ReactionRate {
+        reaction: Reaction::new(
+            [nuclide("al*6")].into_iter().collect(),
+            [nuclide("al-6")].into_iter().collect(),
+        ),
+        sets: Vec::new(),
+    };
+
+    let c = code(std::slice::from_ref(&rate), Language::C);
+    assert!(c.contains("double rate_al_6_to_al_6(double t9) {\n    return 0.0;\n}\n"));
+    let fortran = code(&[rate], Language::Fortran);
+    assert!(fortran.contains("    rate = 0.0_dp\n  end function rate_al_6_to_al_6\n"));
+}
@@ -0,0 +1,135 @@
+use crate::{error::ReaclibError, Format, Library, Snapshot, SnapshotCache};
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::Write as _,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    thread,
+};
+
+const NETWORK: &str = include_str!("library/network");
+
+// serve a single request with `status` and `body`, returning the url of the server
+fn serve(status: &'static str, body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(&stream);
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 2 {
+            line.clear();
+        }
+        write!(
+            &stream,
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+    });
+    format!("http://{address}/")
+}
+
+fn checksum(data: &str) -> String {
+    Sha256::digest(data).iter().fold(String::new(), |mut s, b| {
+        write!(s, "{b:02x}").unwrap();
+        s
+    })
+}
+
+#[test]
+fn fetch() {
+    let snapshot = Snapshot::new("v1", serve("200 OK", NETWORK) + "v1", Format::Reaclib2)
+        .with_size(NETWORK.len() as u64)
+        .with_sha256(&checksum(NETWORK))
+        .unwrap();
+    let library = snapshot.fetch().unwrap();
+    assert_eq!(
+        library.sets(),
+        Library::from_reader(NETWORK.as_bytes(), Format::Reaclib2)
+            .unwrap()
+            .sets()
+    );
+    assert_eq!(snapshot.name(), "v1");
+    assert_eq!(library.metadata().name.as_deref(), Some("v1"));
+    assert_eq!(library.metadata().source.as_deref(), Some(snapshot.url()));
+    assert_eq!(library.metadata().sha256, Some(checksum(NETWORK)));
+}
+
+#[test]
+fn http_error() {
+    let snapshot = Snapshot::new("v1", serve("404 Not Found", ""), Format::Reaclib2);
+    assert!(matches!(
+        snapshot.download(),
+        Err(ReaclibError::Download(_))
+    ));
+}
+
+#[test]
+fn verify() {
+    let snapshot = Snapshot::new("test", "", Format::Reaclib2);
+    assert!(snapshot.verify(NETWORK.as_bytes()).is_ok());
+
+    let sized = snapshot.clone().with_size(10);
+    assert_eq!(
+        sized.verify(NETWORK.as_bytes()),
+        Err(ReaclibError::SnapshotSize(10, NETWORK.len() as u64))
+    );
+
+    let checked = snapshot
+        .clone()
+        .with_sha256(&checksum(NETWORK).to_uppercase())
+        .unwrap();
+    assert!(checked.parse(NETWORK.as_bytes()).is_ok());
+    assert_eq!(
+        checked.parse(&NETWORK.as_bytes()[1..]),
+        Err(ReaclibError::SnapshotChecksum)
+    );
+
+    for invalid in ["", "00", &"g".repeat(64), &"é".repeat(32)] {
+        assert_eq!(
+            snapshot.clone().with_sha256(invalid),
+            Err(ReaclibError::InvalidChecksum(invalid.to_string()))
+        );
+    }
+}
+
+#[test]
+fn cache() {
+    let dir = std::env::temp_dir().join(format!("reaclib-test-{}-snapshots", std::process::id()));
+    let cache = SnapshotCache::new(&dir);
+    let expected = Library::from_reader(NETWORK.as_bytes(), Format::Reaclib2).unwrap();
+    let expected = expected.sets();
+
+    // the server only answers once, so the second fetch must come from the cache
+    let snapshot = Snapshot::new("jina/v1", serve("200 OK", NETWORK), Format::Reaclib2);
+    assert!(!cache.contains(&snapshot));
+    assert_eq!(cache.fetch(&snapshot).unwrap().sets(), expected);
+    assert!(cache.contains(&snapshot));
+    assert_eq!(cache.path(&snapshot), dir.join("jina_v1.reaclib"));
+    assert_eq!(cache.fetch(&snapshot).unwrap().sets(), expected);
+
+    // a stored copy that doesn't match the pinned size is downloaded again
+    let pinned = Snapshot::new("jina/v1", serve("200 OK", NETWORK), Format::Reaclib2)
+        .with_size(NETWORK.len() as u64);
+    std::fs::write(cache.path(&pinned), &NETWORK[1..]).unwrap();
+    assert_eq!(cache.fetch(&pinned).unwrap().sets(), expected);
+    assert_eq!(
+        std::fs::read_to_string(cache.path(&pinned)).unwrap(),
+        NETWORK
+    );
+
+    // so is a stored copy that doesn't parse, even if nothing is pinned
+    let unpinned = Snapshot::new("jina/v1", serve("200 OK", NETWORK), Format::Reaclib2);
+    std::fs::write(cache.path(&unpinned), &NETWORK[..NETWORK.len() / 2]).unwrap();
+    assert_eq!(cache.fetch(&unpinned).unwrap().sets(), expected);
+    assert_eq!(
+        std::fs::read_to_string(cache.path(&unpinned)).unwrap(),
+        NETWORK
+    );
+
+    cache.remove(&snapshot).unwrap();
+    assert!(!cache.contains(&snapshot));
+    cache.remove(&snapshot).unwrap();
+    std::fs::remove_dir(&dir).unwrap();
+}
@@ -0,0 +1,76 @@
+use super::network;
+use crate::{
+    fit_params, log_grid, write_rate_table, Format, Header, Iter, Nuclide, Reaction, Set,
+    TabulatedRate,
+};
+use std::io::Cursor;
+
+#[test]
+fn round_trip() {
+    let library = network();
+    let nuclide = |s: &str| s.parse::<Nuclide>().unwrap();
+    let reaction = Reaction::new(
+        [nuclide("p"), nuclide("c13")].into_iter().collect(),
+        [nuclide("n14")].into_iter().collect(),
+    );
+    let rate = library.reaction_rate(&reaction).unwrap();
+    let set = library.get(&reaction).next().unwrap().clone();
+
+    let temperatures = log_grid(0.1, 10.0, 31);
+    let mut written = Vec::new();
+    write_rate_table(&mut written, &rate, &temperatures).unwrap();
+    let table = TabulatedRate::from_reader(Cursor::new(written), 0).unwrap();
+    let fit = Set::from_header(Header::from(set.clone()), table.fit().unwrap());
+
+    assert_eq!(fit.reaction(), set.reaction());
+    assert_eq!(fit.label, set.label);
+    for t in temperatures {
+        assert!((fit.rate(t) / set.rate(t) - 1.0).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn exact() {
+    let params = [
+        17.1482, 0.0, -13.692, -0.230_881, 4.44362, -3.15898, -0.666_667,
+    ];
+    let reader = Cursor::new(include_str!("library/network"));
+    let first = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    let set = Set::from_header(Header::from(first), params);
+    let temperatures = log_grid(0.05, 5.0, 12);
+    let rates = temperatures
+        .iter()
+        .map(|&t| set.rate(t))
+        .collect::<Vec<_>>();
+    let fit = fit_params(&temperatures, &rates).unwrap();
+    for (a, b) in fit.iter().zip(params) {
+        assert!((a - b).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn too_few_points() {
+    let temperatures = [0.1, 0.2, 0.5, 1.0, 2.0, 5.0, 10.0];
+    assert!(fit_params(&temperatures, &[1.0; 7]).is_some());
+    assert!(fit_params(&temperatures[1..], &[1.0; 6]).is_none());
+    assert!(fit_params(&temperatures, &[1.0; 6]).is_none());
+    // non-positive rates are left out
+    let rates = [0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+    assert!(fit_params(&temperatures, &rates).is_none());
+    assert!(fit_params(&[1.0; 7], &[1.0; 7]).is_none());
+}
+
+#[test]
+fn columns() {
+    let table = r"# T9  (p,g)  (p,n)
+0.1  1e-10  1e-20
+1.0  1e-05  1e-08
+";
+    let rates = TabulatedRate::from_reader(Cursor::new(table), 1).unwrap();
+    for (a, b) in rates.rates().zip([1e-20, 1e-8]) {
+        assert!((a / b - 1.0).abs() < 1e-12);
+    }
+    assert!(TabulatedRate::from_reader(Cursor::new(table), 2).is_err());
+    assert!(TabulatedRate::from_reader(Cursor::new("1.0 1\n0.1 1"), 0).is_err());
+    assert!(TabulatedRate::from_reader(Cursor::new("# T9 rate"), 0).is_err());
+}
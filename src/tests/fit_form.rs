@@ -0,0 +1,81 @@
+use crate::{
+    error::ReaclibError, FitForm, Format, Header, Iter, ParseError, RateBasis, ReaclibFit,
+};
+use std::io::Cursor;
+
+// the reaclib form, reading the parameters with the default `parse_params`
+struct DefaultLayout;
+
+impl FitForm for DefaultLayout {
+    fn param_lines(&self) -> usize {
+        2
+    }
+
+    fn ln_rate(&self, params: &[f64], temperature: f64) -> f64 {
+        ReaclibFit.ln_rate(params, temperature)
+    }
+}
+
+// a form with a third parameter line
+struct ExtraLine;
+
+impl FitForm for ExtraLine {
+    fn param_lines(&self) -> usize {
+        3
+    }
+
+    fn ln_rate(&self, params: &[f64], _temperature: f64) -> f64 {
+        params.iter().sum()
+    }
+}
+
+// reading standard files with the reaclib form should match `Iter`
+#[test]
+fn matches_sets() {
+    for (input, format) in [
+        (include_str!("v1/multi"), Format::Reaclib1),
+        (include_str!("v1/multi_chapter"), Format::Reaclib1),
+        (include_str!("v2/multi"), Format::Reaclib2),
+    ] {
+        let sets = Iter::new(Cursor::new(input), format)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        for form in [&ReaclibFit as &dyn FitForm, &DefaultLayout] {
+            let fit_sets = Iter::builder(Cursor::new(input))
+                .format(format)
+                .fit_form(form)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(fit_sets.len(), sets.len());
+            for (set, fit_set) in sets.iter().zip(&fit_sets) {
+                assert_eq!(Header::from(set.clone()), fit_set.header);
+                assert_eq!(set.params[..], fit_set.params[..]);
+                for t in [0.1, 1.0, 5.0] {
+                    assert_eq!(set.rate(t), fit_set.rate(&form, t));
+                }
+            }
+        }
+    }
+}
+
+// the rate of the reaclib form is only defined with 7 parameters
+#[test]
+fn reaclib_param_count() {
+    assert!(ReaclibFit.ln_rate(&[1.0; 8], 1.0).is_nan());
+    assert_eq!(
+        ReaclibFit.ln_rate(&[1.0; 7], 2.0),
+        RateBasis::new(2.0).values().iter().sum::<f64>()
+    );
+}
+
+#[test]
+fn too_few_lines() {
+    let mut iter = Iter::builder(Cursor::new(include_str!("v2/single")))
+        .format(Format::Reaclib2)
+        .fit_form(ExtraLine);
+    assert!(matches!(
+        iter.next().unwrap(),
+        Err(ReaclibError::Invalid(ParseError::TooFewLines))
+    ));
+    assert!(iter.next().is_none());
+}
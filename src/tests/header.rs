@@ -0,0 +1,35 @@
+use crate::{Format, Header, HeaderIter, Iter, ParseError};
+use std::io::Cursor;
+
+// the headers should match the corresponding parts of the full sets
+#[test]
+fn matches_sets() {
+    for (input, format) in [
+        (include_str!("v1/multi"), Format::Reaclib1),
+        (include_str!("v2/multi"), Format::Reaclib2),
+    ] {
+        let sets = Iter::new(Cursor::new(input), format)
+            .map(|s| s.map(Header::from))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let headers = HeaderIter::new(Cursor::new(input), format)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(sets, headers);
+    }
+}
+
+// the parameter lines aren't parsed, so errors in them aren't reported
+#[test]
+fn skips_params() {
+    let reader = Cursor::new(include_str!("v1/parse_float_error_2"));
+    let mut iter = HeaderIter::new(reader, Format::Reaclib1);
+    assert!(iter.next().unwrap().is_ok());
+
+    let reader = Cursor::new(include_str!("v1/parse_float_error_1"));
+    let mut iter = HeaderIter::new(reader, Format::Reaclib1);
+    assert!(matches!(
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(ParseError::ParseFloat(_))
+    ));
+}
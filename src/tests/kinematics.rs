@@ -0,0 +1,57 @@
+use crate::{EntranceChannel, Format, GamowWindow, Iter};
+use std::io::Cursor;
+
+// compare against the usual approximate formulas
+#[test]
+fn gamow_window() {
+    let (z1, a1, z2, a2, t) = (1, 1.0, 6, 12.0, 0.03);
+    let mu: f64 = a1 * a2 / (a1 + a2);
+    let zz = f64::from(z1 * z2).powi(2);
+    let peak = 0.1220 * (zz * mu * t * t).powf(1.0 / 3.0);
+    let width = 0.2368 * (zz * mu * t.powi(5)).powf(1.0 / 6.0);
+
+    let window = GamowWindow::new(z1, a1, z2, a2, t);
+    assert!((window.peak / peak - 1.0).abs() < 1e-3);
+    assert!((window.width / width - 1.0).abs() < 1e-3);
+    assert!(window.lower() < window.peak && window.peak < window.upper());
+}
+
+#[test]
+fn set_gamow_window() {
+    let reader = Cursor::new(include_str!("library/network"));
+    let sets = Iter::new(reader, Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    // p + c12
+    assert_eq!(
+        sets[0].gamow_window(0.03),
+        Some(GamowWindow::new(1, 1.0, 6, 12.0, 0.03))
+    );
+    // a decay doesn't have a Gamow window
+    assert_eq!(sets[2].gamow_window(0.03), None);
+}
+
+// compare against `Z1 Z2 e² / R` with `e² = 1.44 MeV fm`, and the usual formula for `η`
+#[test]
+fn entrance_channel() {
+    let channel = EntranceChannel::new(2, 4.0, 6, 12.0);
+    let radius = 1.2 * (4.0_f64.cbrt() + 12.0_f64.cbrt());
+    assert!((channel.radius() - radius).abs() < 1e-12);
+    assert!((channel.coulomb_barrier() / (12.0 * 1.44 / radius) - 1.0).abs() < 1e-3);
+    let eta = 0.157_49 * 12.0 * (3.0_f64 / 0.3).sqrt();
+    assert!((channel.sommerfeld_parameter(0.3) / eta - 1.0).abs() < 1e-3);
+    assert_eq!(
+        channel.gamow_window(0.2),
+        GamowWindow::new(2, 4.0, 6, 12.0, 0.2)
+    );
+
+    let reader = Cursor::new(include_str!("library/network"));
+    let sets = Iter::new(reader, Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    // he4 + c12
+    assert_eq!(sets[4].entrance_channel(), Some(channel));
+    // a decay doesn't have an entrance channel
+    assert_eq!(sets[2].entrance_channel(), None);
+}
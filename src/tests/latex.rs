@@ -0,0 +1,64 @@
+use super::network;
+use crate::LatexTable;
+use arrayvec::ArrayString;
+
+#[test]
+fn table() {
+    let library = network();
+    let sets = &library.sets()[..2];
+
+    let table = LatexTable::new(sets).to_string();
+    assert_eq!(
+        table,
+        r"\begin{tabular}{llr}
+\hline
+Reaction & Label & $Q$ (MeV) \\
+\hline
+$\mathrm{p} + {}^{12}\mathrm{C} \rightarrow {}^{13}\mathrm{N}$ & nacr & 1.944 \\
+$\mathrm{p} + {}^{12}\mathrm{C} \rightarrow {}^{13}\mathrm{N}$ & nacr & 1.944 \\
+\hline
+\end{tabular}
+"
+    );
+
+    let table = LatexTable::new(sets).with_rates(&[1.0, 2.0]).to_string();
+    let lines = table.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], r"\begin{tabular}{llrrr}");
+    assert_eq!(
+        lines[2],
+        r"Reaction & Label & $Q$ (MeV) & $T_9 = 1$ & $T_9 = 2$ \\"
+    );
+    // the rates are like `$1.234 \times 10^{-5}$`
+    let cells = lines[4]
+        .trim_end_matches(r" \\")
+        .split(" & ")
+        .collect::<Vec<_>>();
+    for (cell, t) in cells[3..].iter().zip([1.0, 2.0]) {
+        let (mantissa, exponent) = cell
+            .trim_matches('$')
+            .trim_end_matches('}')
+            .split_once(r" \times 10^{")
+            .unwrap();
+        let value = format!("{mantissa}e{exponent}").parse::<f64>().unwrap();
+        assert!((value / sets[0].rate(t) - 1.0).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn nuclides() {
+    let mut set = network().sets()[0].clone();
+    // this test's name shadows the shared fixture
+    let nuclides = super::nuclides;
+    set.reactants = nuclides(&["he4", "he4", "he4"]);
+    set.products = nuclides(&["c12"]);
+    let mut other = set.clone();
+    other.reactants = nuclides(&["al*6"]);
+    other.products = nuclides(&["mg26", "xx"]);
+    other.label = ArrayString::from("a_&b").unwrap();
+
+    let table = LatexTable::new([&set, &other]).to_string();
+    assert!(table.contains(r"$\alpha + \alpha + \alpha \rightarrow {}^{12}\mathrm{C}$ & nacr"));
+    assert!(table.contains(
+        r"${}^{26}\mathrm{Al}^{m} \rightarrow {}^{26}\mathrm{Mg} + \mathrm{xx}$ & a\_\&b"
+    ));
+}
@@ -0,0 +1,481 @@
+use super::{network, nuclides};
+use crate::{Chapter, Format, Grouping, Iter, Library, Nuclide, ParseError, Reaction, Resonance};
+use std::{collections::HashMap, io::Cursor};
+
+#[test]
+fn index() {
+    let library = network();
+    assert_eq!(library.sets().len(), 7);
+    assert_eq!(library.reactions().count(), 6);
+
+    let reaction = Reaction::new(nuclides(&["p", "c12"]), nuclides(&["n13"]));
+    assert_eq!(library.get(&reaction).count(), 2);
+    let reaction = Reaction::new(nuclides(&["c12", "p"]), nuclides(&["n13"]));
+    assert_eq!(library.get(&reaction).count(), 0);
+}
+
+// the sets can be used like a slice, in the order they were added
+#[test]
+fn slice_access() {
+    let library = network();
+    assert_eq!(library.len(), 7);
+    assert!(!library.is_empty());
+    assert!(Library::new().is_empty());
+    assert_eq!(&library[0], &library.sets()[0]);
+    assert_eq!(&library[1..3], &library.sets()[1..3]);
+    assert_eq!(library.as_ref(), library.sets());
+    assert!(library.iter().eq(library.sets()));
+
+    let labels: Vec<_> = (&library).into_iter().map(|s| s.label).collect();
+    let sets: Vec<_> = library.clone().into_iter().collect();
+    assert_eq!(sets, library.sets());
+    assert_eq!(labels[0], sets[0].label);
+}
+
+// after each edit, the index is the same as if the library had been built from its sets
+#[test]
+fn mutation() {
+    let rebuilt = |l: &Library| l.iter().cloned().collect::<Library>();
+    let mut library = network();
+    let pc12 = Reaction::new(nuclides(&["p", "c12"]), nuclides(&["n13"]));
+
+    let removed = library.remove(&pc12);
+    assert_eq!(removed, network().sets()[..2]);
+    assert_eq!(library.len(), 5);
+    assert_eq!(library.get(&pc12).count(), 0);
+    assert_eq!(library, rebuilt(&library));
+    assert!(library.remove(&pc12).is_empty());
+
+    library.insert(removed[1].clone());
+    assert_eq!(library.get(&pc12).collect::<Vec<_>>(), [&removed[1]]);
+    assert_eq!(library, rebuilt(&library));
+
+    let mut replacement = removed[0].clone();
+    replacement.params[0] += 1.0;
+    let replaced = library.replace_label(&pc12, "nacr", [replacement.clone()]);
+    assert_eq!(replaced, removed[1..]);
+    assert_eq!(library.get(&pc12).collect::<Vec<_>>(), [&replacement]);
+    assert_eq!(library, rebuilt(&library));
+
+    let replaced = library.replace_label(&pc12, "xxxx", []);
+    assert!(replaced.is_empty());
+    assert_eq!(library.len(), 6);
+}
+
+// only `SortedProducts` groups the sets with their products swapped, and only `Canonical` also
+// groups the ones with their reactants swapped
+#[test]
+fn group_by() {
+    let mut sets = network().sets().to_vec();
+    let mut swapped = sets[5].clone();
+    swapped.products.swap(0, 1);
+    sets.push(swapped);
+    let mut swapped = sets[0].clone();
+    swapped.reactants.swap(0, 1);
+    sets.push(swapped);
+    let library = sets.into_iter().collect::<Library>();
+
+    assert_eq!(library.group_by(Grouping::Exact).len(), 8);
+    let grouped = library.group_by(Grouping::SortedProducts);
+    assert_eq!(grouped.len(), 7);
+    let reaction = Reaction::new(nuclides(&["o16"]), nuclides(&["he4", "c12"]));
+    assert_eq!(grouped[&reaction].len(), 2);
+    assert_eq!(library.group_by(Grouping::Canonical).len(), 6);
+}
+
+#[test]
+fn trim() {
+    let library = network();
+    let seeds = nuclides(&["p", "he4", "c12"]);
+
+    assert_eq!(library.trim(&seeds, 0).sets().len(), 0);
+    assert_eq!(library.trim(&seeds, 1).sets().len(), 3);
+    assert_eq!(library.trim(&seeds, 2).sets().len(), 6);
+    assert_eq!(library.trim(&seeds, usize::MAX).sets().len(), 7);
+
+    // without protons, only the alpha capture and its reverse are reachable
+    let trimmed = library.trim(&nuclides(&["he4", "c12"]), usize::MAX);
+    assert_eq!(trimmed.sets().len(), 2);
+    assert_eq!(trimmed.reactions().count(), 2);
+}
+
+#[test]
+fn rate_pair() {
+    let library = network();
+    let capture = Reaction::new(nuclides(&["he4", "c12"]), nuclides(&["o16"]));
+    let pair = library.rate_pair(&capture).unwrap();
+    assert_eq!(pair.forward.reaction, capture);
+    assert_eq!(pair.reverse.reaction, capture.reversed());
+
+    let t = pair.equality_temperature(0.1, 10.0).unwrap();
+    assert!((pair.forward.rate(t) / pair.reverse.rate(t) - 1.0).abs() < 1e-9);
+
+    let curve = pair.ratio_curve(0.1, 10.0, 11);
+    assert_eq!(curve.reaction, capture);
+    assert_eq!(curve.rates[3], pair.ratio(curve.temperatures[3]));
+    // the ratio only crosses 1 once
+    assert_eq!(
+        curve
+            .rates
+            .windows(2)
+            .filter(|w| (w[0] < 1.0) != (w[1] < 1.0))
+            .count(),
+        1
+    );
+
+    // there is no reverse reaction for p + c13
+    let reaction = Reaction::new(nuclides(&["p", "c13"]), nuclides(&["n14"]));
+    assert!(library.rate_pair(&reaction).is_none());
+}
+
+#[test]
+fn equilibrium_temperature() {
+    let library = network();
+    let capture = Reaction::new(nuclides(&["he4", "c12"]), nuclides(&["o16"]));
+    let pair = library.rate_pair(&capture).unwrap();
+    let nuclide = |s: &str| s.parse::<Nuclide>().unwrap();
+    let abundances = HashMap::from([
+        (nuclide("he4"), 0.1),
+        (nuclide("c12"), 0.002),
+        (nuclide("o16"), 0.001),
+    ]);
+
+    // the fluxes include the density and abundances that the rates don't
+    let ratio = pair.flux_ratio(1e5, 2.0, &abundances);
+    let expected = pair.ratio(2.0) * 0.001 / (1e5 * 0.1 * 0.002);
+    assert!((ratio / expected - 1.0).abs() < 1e-12);
+
+    let t = pair
+        .equilibrium_temperature(1e5, &abundances, 0.1, 10.0)
+        .unwrap();
+    assert!((pair.flux_ratio(1e5, t, &abundances) - 1.0).abs() < 1e-9);
+    let forward = pair.forward.flux(1e5, t, &abundances);
+    assert!(pair.net_flux(1e5, t, &abundances).abs() < 1e-9 * forward);
+    assert!(pair.net_flux(1e5, t / 2.0, &abundances) > 0.0);
+    // without the density and abundances, the balance is somewhere else
+    assert!((t / pair.equality_temperature(0.1, 10.0).unwrap() - 1.0).abs() > 0.01);
+
+    // without any oxygen, there is no photodisintegration to balance the capture
+    let mut no_oxygen = abundances.clone();
+    no_oxygen.remove(&nuclide("o16"));
+    assert_eq!(
+        pair.equilibrium_temperature(1e5, &no_oxygen, 0.1, 10.0),
+        None
+    );
+}
+
+// the flux of a reaction is the sum of the molar rates of its sets
+#[test]
+fn flux() {
+    let library = network();
+    let capture = Reaction::new(nuclides(&["p", "c12"]), nuclides(&["n13"]));
+    let rate = library.reaction_rate(&capture).unwrap();
+    let nuclide = |s: &str| s.parse::<Nuclide>().unwrap();
+    let abundances = HashMap::from([(nuclide("p"), 0.7), (nuclide("c12"), 0.002)]);
+
+    let flux = rate.flux(100.0, 0.05, &abundances);
+    let expected = rate
+        .sets
+        .iter()
+        .map(|s| s.molar_rate(100.0, 0.05, &abundances))
+        .sum::<f64>();
+    assert_eq!(rate.sets.len(), 2);
+    assert!((flux / expected - 1.0).abs() < 1e-12);
+    assert_eq!(rate.flux(100.0, 0.05, &HashMap::new()), 0.0);
+}
+
+#[test]
+fn inverse_of() {
+    let library = network();
+    let capture = Reaction::new(nuclides(&["he4", "c12"]), nuclides(&["o16"]));
+    let inverse = library.inverse_of(&capture).collect::<Vec<_>>();
+    assert_eq!(inverse.len(), 1);
+    assert_eq!(inverse[0].reaction(), capture.reversed());
+
+    // the order of the nuclides doesn't matter
+    let swapped = Reaction::new(nuclides(&["c12", "he4"]), nuclides(&["o16"]));
+    assert_eq!(library.inverse_of(&swapped).count(), 1);
+    let photodisintegration = Reaction::new(nuclides(&["o16"]), nuclides(&["c12", "he4"]));
+    assert_eq!(library.inverse_of(&photodisintegration).count(), 1);
+
+    let reaction = Reaction::new(nuclides(&["p", "c13"]), nuclides(&["n14"]));
+    assert_eq!(library.inverse_of(&reaction).count(), 0);
+}
+
+#[test]
+fn get_canonical() {
+    let library = network();
+    let capture = Reaction::new(nuclides(&["he4", "c12"]), nuclides(&["o16"]));
+    let swapped = Reaction::new(nuclides(&["c12", "he4"]), nuclides(&["o16"]));
+    assert_eq!(
+        library.get_canonical(&swapped).collect::<Vec<_>>(),
+        library.get(&capture).collect::<Vec<_>>()
+    );
+    assert_eq!(library.get(&swapped).count(), 0);
+    assert_eq!(library.get_canonical(&swapped.reversed()).count(), 1);
+}
+
+#[test]
+fn pair_reverses() {
+    let library = network();
+    let report = library.pair_reverses();
+    let capture = Reaction::new(nuclides(&["he4", "c12"]), nuclides(&["o16"]));
+    assert_eq!(report.pairs, [(capture.clone(), capture.reversed())]);
+    assert!(report.orphans.is_empty());
+
+    // without the forward reaction, the reverse set is an orphan
+    let library = library
+        .sets()
+        .iter()
+        .filter(|s| s.reaction() != capture)
+        .cloned()
+        .collect::<Library>();
+    let report = library.pair_reverses();
+    assert!(report.pairs.is_empty());
+    assert_eq!(report.orphans.len(), 1);
+    assert_eq!(report.orphans[0].reaction(), capture.reversed());
+}
+
+#[test]
+fn q_between() {
+    let library = network();
+    // everything but the photodisintegration is exothermic
+    assert_eq!(library.q_between(0.0, f64::INFINITY).count(), 6);
+    assert!(library.q_between(0.0, f64::INFINITY).all(|s| !s.reverse));
+    // near-threshold reactions
+    let q_values = library
+        .q_between(-1.0, 2.0)
+        .map(|s| s.q_value)
+        .collect::<Vec<_>>();
+    assert_eq!(q_values, [1.944, 1.944, 0.6]);
+    assert_eq!(library.q_between(100.0, 200.0).count(), 0);
+}
+
+#[test]
+fn faster_than() {
+    let library = network();
+    let t = 0.1;
+    let fast = library.faster_than(t, 1e-10);
+    assert!(fast.reactions().count() < library.reactions().count());
+    for reaction in library.reactions() {
+        let rate = library.reaction_rate(reaction).unwrap().rate(t);
+        assert_eq!(fast.get(reaction).count() > 0, rate > 1e-10);
+    }
+    // both sets of p + c12 are kept together
+    let reaction = Reaction::new(nuclides(&["p", "c12"]), nuclides(&["n13"]));
+    assert_eq!(fast.get(&reaction).count(), 2);
+
+    assert_eq!(library.faster_than(t, f64::INFINITY).sets().len(), 0);
+}
+
+#[test]
+fn restrict_to() {
+    let library = network();
+    let restricted = library.restrict_to(&nuclides(&["p", "c12", "n13", "c13"]));
+    let mut reactions = restricted
+        .reactions()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    reactions.sort();
+    assert_eq!(reactions, ["n13 -> c13", "p c12 -> n13"]);
+    assert_eq!(restricted.sets().len(), 3);
+
+    assert_eq!(library.restrict_to(&[]).sets().len(), 0);
+}
+
+#[test]
+fn reaction_from_str() {
+    let reaction = Reaction::new(nuclides(&["he4", "c12"]), nuclides(&["o16"]));
+    assert_eq!("he4 c12 -> o16".parse::<Reaction>(), Ok(reaction.clone()));
+    assert_eq!("  he4  c12->o16 ".parse::<Reaction>(), Ok(reaction.clone()));
+    assert_eq!(reaction.to_string().parse::<Reaction>(), Ok(reaction));
+
+    assert!(matches!(
+        "he4 c12 o16".parse::<Reaction>(),
+        Err(ParseError::InvalidReaction(_))
+    ));
+    // more nuclides than any chapter has are allowed
+    let many = "n n n n n -> n".parse::<Reaction>().unwrap();
+    assert_eq!(many.reactants().len(), 5);
+    assert_eq!(many.chapter(), None);
+    assert!(matches!(
+        "toolong -> n".parse::<Reaction>(),
+        Err(ParseError::NuclideTooLong(_))
+    ));
+
+    // aliases are resolved, and photons and leptons are dropped
+    let reaction = Reaction::new(nuclides(&["he4", "c12"]), nuclides(&["o16"]));
+    assert_eq!("a c12 -> o16 g".parse::<Reaction>(), Ok(reaction.clone()));
+    assert_eq!(
+        "alpha + c12 -> o16 + gamma".parse::<Reaction>(),
+        Ok(reaction.clone())
+    );
+    assert_eq!(reaction.chapter(), Some(Chapter::Chapter4));
+    let pp = Reaction::new(nuclides(&["p", "p"]), nuclides(&["d"]));
+    assert_eq!("h1 + h1 -> h2 + e+ + nu".parse::<Reaction>(), Ok(pp));
+    let decay = Reaction::new(nuclides(&["n"]), nuclides(&["p"]));
+    assert_eq!("n1 -> p e- nubar".parse::<Reaction>(), Ok(decay));
+    let photo = Reaction::new(nuclides(&["he3"]), nuclides(&["p", "d"]));
+    assert_eq!("g he3 -> p d".parse::<Reaction>(), Ok(photo));
+    assert!(matches!(
+        "g -> g".parse::<Reaction>(),
+        Err(ParseError::InvalidReaction(_))
+    ));
+}
+
+#[test]
+fn reaction_compact() {
+    let compact = |r: &str| r.parse::<Reaction>().unwrap().compact();
+    assert_eq!(compact("c12 p -> n13"), "c12(p,g)n13");
+    assert_eq!(compact("o16 -> he4 c12"), "o16(g,a)c12");
+    assert_eq!(compact("he4 he4 he4 -> c12"), "he4(a+a,g)c12");
+    assert_eq!(compact("p p -> d"), "p(p,e+ nu)d");
+    assert_eq!(compact("n -> p"), "n(,e- nubar)p");
+    assert_eq!(compact("al*6 -> al-6"), "al*6(,g)al-6");
+    assert_eq!(compact("he3 he3 -> p p he4"), "he3(he3,p+p)he4");
+
+    // the compact notation parses back into the same reaction
+    for set in network().sets() {
+        let reaction = set.reaction();
+        let parsed = reaction.compact().parse::<Reaction>().unwrap();
+        assert_eq!(parsed.canonical(), reaction.canonical());
+    }
+}
+
+#[test]
+fn reaction_from_compact_str() {
+    let capture = Reaction::new(nuclides(&["p", "c12"]), nuclides(&["n13"]));
+    assert_eq!("c12(p,g)n13".parse::<Reaction>(), Ok(capture.clone()));
+    assert_eq!("c12(p, g)n13".parse::<Reaction>(), Ok(capture.clone()));
+    assert_eq!("n13(g,p)c12".parse::<Reaction>(), Ok(capture.reversed()));
+
+    let alpha = Reaction::new(nuclides(&["he4", "c12"]), nuclides(&["o16"]));
+    assert_eq!("c12(a,g)o16".parse::<Reaction>(), Ok(alpha));
+
+    let decay = Reaction::new(nuclides(&["n13"]), nuclides(&["c13"]));
+    assert_eq!("n13(,e+ nu)c13".parse::<Reaction>(), Ok(decay));
+
+    let triple = Reaction::new(nuclides(&["he4", "he4", "he4"]), nuclides(&["c12"]));
+    assert_eq!("he4(a+a,g)c12".parse::<Reaction>(), Ok(triple));
+
+    let library = network();
+    let rate = library.reaction_rate(&"c13(p,g)n14".parse().unwrap());
+    assert_eq!(rate.map(|r| r.sets.len()), Some(1));
+
+    let many = "c12(n+n+n+n,g)c16".parse::<Reaction>().unwrap();
+    assert_eq!(many.reactants().len(), 5);
+
+    for s in ["(p,g)n13", "c12(p g)n13", "c12(p,g n13"] {
+        assert!(matches!(
+            s.parse::<Reaction>(),
+            Err(ParseError::InvalidReaction(_))
+        ));
+    }
+}
+
+#[test]
+fn diff() {
+    let library = network();
+    assert!(library.diff(&library).is_empty());
+
+    let mut sets = library.sets().to_vec();
+    let removed = sets.remove(1);
+    sets[0].params[0] += 1.0;
+    sets.push(sets[2].clone());
+    let other = sets.into_iter().collect::<Library>();
+
+    let diff = library.diff(&other);
+    assert_eq!(diff.removed, [&library.sets()[0], &removed]);
+    assert_eq!(diff.added, [&other.sets()[0], &other.sets()[6]]);
+}
+
+#[test]
+fn stats() {
+    let library = network();
+    let stats = library.stats();
+    assert_eq!(stats.sets, 7);
+    assert_eq!(stats.reactions, 6);
+    assert_eq!(stats.chapters[&Chapter::Chapter4], 5);
+    assert_eq!(stats.chapters.values().sum::<usize>(), 7);
+    assert_eq!(stats.labels["nacr"], 3);
+    assert_eq!(stats.resonances[&Resonance::NonResonant], 5);
+    assert_eq!(stats.resonances.values().sum::<usize>(), 7);
+    assert_eq!(stats.reverse, 1);
+    assert_eq!(
+        stats
+            .nuclides
+            .iter()
+            .map(Nuclide::as_str)
+            .collect::<Vec<_>>(),
+        ["p", "he4", "c12", "c13", "n13", "n14", "o16", "f17"]
+    );
+    assert_eq!(stats.min_q.as_ref().unwrap().q_value, -7.162);
+    assert_eq!(stats.max_q.as_ref().unwrap().q_value, 7.551);
+    assert_eq!(stats.q_histogram.values().sum::<usize>(), 7);
+    assert_eq!(stats.q_histogram[&-8], 1);
+    assert_eq!(stats.q_histogram[&7], 2);
+    assert_eq!(stats.errors, 0);
+
+    // the same stats can be gathered while reading
+    let reader = Cursor::new(include_str!("library/network"));
+    assert_eq!(Iter::new(reader, Format::Reaclib2).stats(), stats);
+
+    let empty = Library::new().stats();
+    assert_eq!(empty.sets, 0);
+    assert!(empty.min_q.is_none());
+}
+
+#[test]
+fn compare_rates() {
+    let old = network();
+    let new = old
+        .sets()
+        .iter()
+        .filter(|set| set.label.as_str() != "ia08")
+        .map(|set| {
+            let mut set = set.clone();
+            match set.label.as_str() {
+                // a factor of exp(T9), which is largest at the highest temperature
+                "nac2" if set.reverse => set.params[4] += 1.0,
+                "wc12" => set.params[0] -= 2f64.ln(),
+                _ => {}
+            }
+            set
+        })
+        .collect::<Library>();
+
+    let comparisons = old.compare_rates(&new, 0.1, 10.0, 21);
+    // p + o16 is only in the first library
+    assert_eq!(comparisons.len(), 5);
+    let reactions = comparisons
+        .iter()
+        .map(|c| c.reaction.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        reactions,
+        [
+            "o16 -> he4 c12",
+            "n13 -> c13",
+            "he4 c12 -> o16",
+            "p c12 -> n13",
+            "p c13 -> n14"
+        ]
+    );
+
+    assert!((comparisons[0].max_factor / 10f64.exp() - 1.0).abs() < 1e-9);
+    assert!((comparisons[0].max_temperature - 10.0).abs() < 1e-12);
+    // the reverse rate underflows to 0 in both libraries at low temperatures, where they agree
+    assert!(comparisons[0].mean_ratio > 1.0);
+
+    // the factor is the same whichever rate is larger
+    assert!((comparisons[1].max_factor - 2.0).abs() < 1e-12);
+    assert!((comparisons[1].mean_ratio - 0.5).abs() < 1e-12);
+
+    for c in &comparisons[2..] {
+        assert_eq!(c.max_factor, 1.0);
+        assert_eq!(c.mean_ratio, 1.0);
+    }
+
+    // an empty grid has nothing to compare
+    assert!(old.compare_rates(&new, 0.1, 10.0, 0).is_empty());
+}
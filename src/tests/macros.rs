@@ -0,0 +1,50 @@
+use crate::{include_reaclib, Chapter, Format, Iter, Set, StaticSet};
+
+static NETWORK: &[StaticSet] = include_reaclib!("src/tests/library/network");
+static MULTI_V1: &[StaticSet] = include_reaclib!("src/tests/v1/multi", Reaclib1);
+static EMPTY: &[StaticSet] = include_reaclib!("src/tests/v2/empty", Reaclib2);
+static FORTRAN: &[StaticSet] = include_reaclib!("src/tests/v2/fortran_exponent");
+
+fn parse(input: &str, format: Format) -> Vec<Set> {
+    Iter::new(input.as_bytes(), format)
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+// the sets are the same as they would be when parsed at runtime
+#[test]
+fn include_reaclib() {
+    let network = NETWORK.iter().map(Set::from).collect::<Vec<_>>();
+    assert_eq!(
+        network,
+        parse(include_str!("library/network"), Format::Reaclib2)
+    );
+    let multi = MULTI_V1.iter().map(Set::from).collect::<Vec<_>>();
+    assert_eq!(multi, parse(include_str!("v1/multi"), Format::Reaclib1));
+    assert!(EMPTY.is_empty());
+    let fortran = FORTRAN.iter().map(Set::from).collect::<Vec<_>>();
+    assert_eq!(
+        fortran,
+        parse(include_str!("v2/fortran_exponent"), Format::Reaclib2)
+    );
+
+    for (set, parsed) in NETWORK.iter().zip(&network) {
+        assert_eq!(set.reaction(), parsed.reaction());
+        assert_eq!(set.rate(2.0), parsed.rate(2.0));
+        assert_eq!(set.label(), parsed.label.as_str());
+        assert_eq!(set.q_value(), parsed.q_value);
+        assert_eq!(set.params(), parsed.params);
+    }
+}
+
+// the macro finds the number of nuclides in a chapter with `reaclib_core`, which agrees with
+// `Chapter`
+#[test]
+fn chapter_counts() {
+    for number in 0..=u8::MAX {
+        let counts = Chapter::try_from(number)
+            .ok()
+            .map(|c| (c.num_reactants(), c.num_products()));
+        assert_eq!(reaclib_core::chapter_counts(number), counts);
+    }
+}
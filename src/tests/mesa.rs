@@ -0,0 +1,52 @@
+use super::{network, nuclides};
+use crate::{mesa_rate_name, write_mesa_table, Reaction};
+
+#[test]
+fn table() {
+    let library = network();
+    let reaction = Reaction::new(nuclides(&["p", "c12"]), nuclides(&["n13"]));
+    let rate = library.reaction_rate(&reaction).unwrap();
+
+    let mut table = Vec::new();
+    write_mesa_table(&mut table, &rate, 0.1, 10.0, 3).unwrap();
+    let table = String::from_utf8(table).unwrap();
+    let lines = table.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "! p c12 -> n13");
+    assert_eq!(lines[1], "       3   ! number of lines of data");
+    assert_eq!(lines[2], "              T8            rate");
+    assert_eq!(lines.len(), 6);
+
+    // the temperatures are in units of 1e8 K
+    for (line, t) in lines[3..].iter().zip([0.1, 1.0, 10.0]) {
+        let values = line
+            .split_whitespace()
+            .map(|v| v.parse::<f64>().unwrap())
+            .collect::<Vec<_>>();
+        assert!((values[0] / (10.0 * t) - 1.0).abs() < 1e-8);
+        assert!((values[1] / rate.rate(t) - 1.0).abs() < 1e-8);
+    }
+}
+
+#[test]
+fn names() {
+    let name = |r: &[&str], p: &[&str]| mesa_rate_name(&Reaction::new(nuclides(r), nuclides(p)));
+
+    assert_eq!(name(&["p", "c12"], &["n13"]), "r_c12_pg_n13");
+    assert_eq!(name(&["c13", "he4"], &["n", "o16"]), "r_c13_an_o16");
+    assert_eq!(name(&["o16"], &["he4", "c12"]), "r_o16_ga_c12");
+    assert_eq!(name(&["n13"], &["c13"]), "r_n13_to_c13");
+    assert_eq!(
+        name(&["c12", "c12"], &["he4", "ne20"]),
+        "r_c12_c12_to_he4_ne20"
+    );
+    assert_eq!(
+        name(&["he4", "he4", "he4"], &["c12"]),
+        "r_he4_he4_he4_to_c12"
+    );
+
+    // the nuclides have MESA's names
+    assert_eq!(name(&["p", "p"], &["d"]), "r_h1_pg_h2");
+    assert_eq!(name(&["n", "p"], &["d"]), "r_h1_ng_h2");
+    assert_eq!(name(&["d", "t"], &["n", "he4"]), "r_h2_h3_to_neut_he4");
+    assert_eq!(name(&["n"], &["p"]), "r_neut_to_h1");
+}
@@ -0,0 +1,46 @@
+use crate::{nuclide_z_a, Format, Iter, Nuclide};
+use std::{collections::HashMap, io::Cursor};
+
+// the contributions should agree with `molar_rate`, and conserve the number of nucleons
+#[test]
+fn contributions() {
+    let reader = Cursor::new(include_str!("library/network"));
+    let sets = Iter::new(reader, Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let species = ["p", "he4", "c12", "c13", "n13", "n14", "o16", "f17"]
+        .map(|n| n.parse::<Nuclide>().unwrap());
+    let abundances = [0.7, 0.07, 1e-3, 1e-4, 1e-5, 1e-3, 1e-3, 1e-6];
+    let map = species
+        .iter()
+        .copied()
+        .zip(abundances)
+        .collect::<HashMap<_, _>>();
+    let (density, temperature) = (100.0, 0.5);
+
+    let mut dydt = [0.0; 8];
+    for set in &sets {
+        let contributions = set.contributions(&species).unwrap();
+        let flux = contributions.flux(set.rate(temperature), density, &abundances);
+        let molar_rate = set.molar_rate(density, temperature, &map);
+        assert!((flux / molar_rate - 1.0).abs() < 1e-12);
+        contributions.apply(set.rate(temperature), density, &abundances, &mut dydt);
+    }
+
+    let nucleons = species
+        .iter()
+        .zip(dydt)
+        .map(|(n, d)| f64::from(nuclide_z_a(n).unwrap().1) * d)
+        .sum::<f64>();
+    let scale = dydt.iter().map(|d| d.abs()).fold(0.0, f64::max);
+    assert!(nucleons.abs() < 1e-12 * scale);
+    // p is only destroyed
+    assert!(dydt[0] < 0.0);
+
+    // p + c12 -> n13
+    let contributions = sets[0].contributions(&species).unwrap();
+    assert_eq!(contributions.abundances, [0, 2]);
+    assert_eq!(contributions.changes, [(0, -1), (2, -1), (4, 1)]);
+    // n13 isn't in the species
+    assert_eq!(sets[0].contributions(&species[..4]), None);
+}
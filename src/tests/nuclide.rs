@@ -0,0 +1,133 @@
+use crate::{Nuclide, ParseError};
+
+#[test]
+fn classification() {
+    let n = |s: &str| s.parse::<Nuclide>().unwrap();
+
+    assert!(n("n").is_neutron());
+    assert!(!n("n14").is_neutron());
+    assert_eq!(n("n14").z_a(), Some((7, 14)));
+    assert!(n("p").is_proton());
+    assert!(!n("p31").is_proton());
+    assert_eq!(n("p31").z_a(), Some((15, 31)));
+    assert!(n("he4").is_alpha());
+    assert!(!n("he3").is_alpha());
+    assert_eq!(n("d").z_a(), Some((1, 2)));
+    assert_eq!(n("al-6").a(), Some(26));
+    assert_eq!(n("og294").z(), Some(118));
+    assert_eq!(n("c").z_a(), None);
+    assert_eq!(n("c0").z_a(), None);
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        "he44444".parse::<Nuclide>(),
+        Err(ParseError::NuclideTooLong("he44444".to_string()))
+    );
+    assert_eq!(format!("{:>5}", "he4".parse::<Nuclide>().unwrap()), "  he4");
+}
+
+#[test]
+fn canonical_order() {
+    let nuclide = |s: &str| s.parse::<Nuclide>().unwrap();
+    let mut nuclides = ["xx1", "c12", "al*6", "al-6", "d", "he4", "p", "n", "aa1"].map(nuclide);
+    nuclides.sort_by(Nuclide::canonical_cmp);
+    // unknown names go last, sorted by name
+    assert_eq!(
+        nuclides.map(|n| n.to_string()),
+        ["n", "p", "d", "he4", "c12", "al-6", "al*6", "aa1", "xx1"]
+    );
+}
+
+#[test]
+fn name_maps() {
+    use crate::NameMap;
+
+    let nuclide = |s: &str| s.parse::<Nuclide>().unwrap();
+    let names = ["n", "p", "d", "t", "he4", "c12", "fe56", "u238"].map(nuclide);
+
+    // every name can be translated and back
+    for map in [NameMap::new(), NameMap::mesa(), NameMap::nugrid()] {
+        for n in names {
+            assert_eq!(map.from_other(&map.to_other(&n)), Some(n));
+        }
+    }
+
+    let nugrid = NameMap::nugrid();
+    assert_eq!(
+        names.map(|n| nugrid.to_other(&n)),
+        ["NEUT", "PROT", "H   2", "H   3", "HE  4", "C  12", "FE 56", "U 238"]
+    );
+    assert_eq!(nugrid.from_other("XX 12"), None);
+
+    let mesa = NameMap::mesa();
+    assert_eq!(
+        names[..4]
+            .iter()
+            .map(|n| mesa.to_other(n))
+            .collect::<Vec<_>>(),
+        ["neut", "h1", "h2", "h3"]
+    );
+
+    // inserting replaces the old exception in both directions
+    let mut map = NameMap::mesa();
+    map.insert(nuclide("p"), "prot");
+    assert_eq!(map.to_other(&nuclide("p")), "prot");
+    assert_eq!(map.from_other("prot"), Some(nuclide("p")));
+    assert_eq!(map.from_other("h1"), Some(nuclide("h1")));
+}
+
+#[test]
+fn well_formed() {
+    let n = |s: &str| s.parse::<Nuclide>().unwrap();
+    for name in [
+        "n", "p", "d", "t", "he3", "he4", "c12", "al-6", "al*6", "h7", "pb208", "u238",
+    ] {
+        assert!(n(name).is_well_formed(), "{name}");
+    }
+    // unknown symbols, implausible or padded mass numbers, and names split across fields
+    for name in [
+        "", "e4", "xx12", "c", "c1", "c0", "c012", "he27", "12c", "c12x", "2 o16",
+    ] {
+        assert!(!n(name).is_well_formed(), "{name}");
+    }
+}
+
+// every nuclide in the test files is well-formed, so validating doesn't change them
+#[test]
+fn validate_nuclides() {
+    use crate::{Format, Iter};
+    use std::io::Cursor;
+
+    for (input, format) in [
+        (include_str!("v1/multi"), Format::Reaclib1),
+        (include_str!("v2/multi"), Format::Reaclib2),
+        (include_str!("library/network"), Format::Reaclib2),
+    ] {
+        let sets = Iter::builder(Cursor::new(input))
+            .format(format)
+            .validate_nuclides(true)
+            .build()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert!(sets.iter().all(|s| s.malformed_nuclides().next().is_none()));
+    }
+
+    let input = include_str!("library/network").replace("  c13  n14", "  c1  3n14");
+    let error = Iter::builder(Cursor::new(&input))
+        .validate_nuclides(true)
+        .build()
+        .nth(3)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(
+        error.parse_error(),
+        Some(&ParseError::MalformedNuclide("c1".to_string()))
+    );
+    // without validating, the set is read as it is
+    let set = Iter::new(Cursor::new(&input), Format::Reaclib2)
+        .nth(3)
+        .unwrap();
+    assert_eq!(set.unwrap().malformed_nuclides().count(), 2);
+}
@@ -0,0 +1,104 @@
+use super::network;
+use crate::{error::ReaclibError, Library, PackedLibrary, Reaction, ValidityRange};
+
+fn write(library: &Library) -> Vec<u8> {
+    let mut data = Vec::new();
+    library.write_packed(&mut data).unwrap();
+    data
+}
+
+#[test]
+fn round_trip() {
+    let library = network();
+    let data = write(&library);
+    let packed = PackedLibrary::new(&data[..]).unwrap();
+    assert_eq!(packed.len(), library.sets().len());
+    assert_eq!(packed.reaction_count(), library.reactions().count());
+
+    for reaction in library.reactions() {
+        let sets = library.get(reaction).cloned().collect::<Vec<_>>();
+        assert_eq!(packed.get(reaction).unwrap(), sets);
+        assert_eq!(
+            packed.reaction_rate(reaction).unwrap(),
+            library.reaction_rate(reaction)
+        );
+        for t in [0.1, 1.0, 10.0] {
+            assert_eq!(
+                packed.rate(reaction, t),
+                Some(library.reaction_rate(reaction).unwrap().rate(t))
+            );
+        }
+    }
+
+    let mut sets = packed.to_library().unwrap().sets().to_vec();
+    let mut expected = library.sets().to_vec();
+    let key = |s: &crate::Set| (Reaction::from(s).to_string(), s.label, s.q_value.to_bits());
+    sets.sort_by_key(key);
+    expected.sort_by_key(key);
+    assert_eq!(sets, expected);
+}
+
+#[test]
+fn missing() {
+    let packed = PackedLibrary::new(write(&network())).unwrap();
+    let reaction = "fe56 -> fe56".parse().unwrap();
+    assert_eq!(packed.get(&reaction), Ok(Vec::new()));
+    assert_eq!(packed.reaction_rate(&reaction), Ok(None));
+    assert_eq!(packed.rate(&reaction, 1.0), None);
+
+    let empty = PackedLibrary::new(write(&Library::new())).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(empty.rate(&reaction, 1.0), None);
+}
+
+#[test]
+fn metadata_not_stored() {
+    let mut sets = network().sets().to_vec();
+    sets[0].validity = Some(ValidityRange::new(0.1, 10.0));
+    let library = sets.into_iter().collect::<Library>();
+    let packed = PackedLibrary::new(write(&library)).unwrap();
+    assert!(packed.sets().all(|s| s.unwrap().validity.is_none()));
+}
+
+#[test]
+fn invalid() {
+    let data = write(&network());
+
+    // a different version is rejected
+    let mut other = data.clone();
+    other[8] += 1;
+    assert_eq!(
+        PackedLibrary::new(other).unwrap_err(),
+        ReaclibError::PackedVersion(2)
+    );
+
+    // data that isn't a packed library is rejected
+    assert_eq!(
+        PackedLibrary::new(&b"not a packed library"[..]).unwrap_err(),
+        ReaclibError::InvalidPacked
+    );
+
+    // truncated data is rejected
+    assert_eq!(
+        PackedLibrary::new(&data[..data.len() - 1]).unwrap_err(),
+        ReaclibError::InvalidPacked
+    );
+
+    // an index entry pointing past the table is rejected
+    let mut other = data.clone();
+    let last = other.len() - 8;
+    other[last..last + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert_eq!(
+        PackedLibrary::new(other).unwrap_err(),
+        ReaclibError::InvalidPacked
+    );
+
+    // a bad record is only found when it is decoded
+    let mut other = data;
+    other[24 + 44] = 9;
+    let packed = PackedLibrary::new(other).unwrap();
+    assert_eq!(
+        packed.to_library().unwrap_err(),
+        ReaclibError::InvalidPacked
+    );
+}
@@ -0,0 +1,357 @@
+use super::{network, nuclides};
+use crate::{
+    error::ReaclibError, Format, Iter, Nuclide, ParseError, RateBasis, RateEvaluator, Reaction,
+    Resonance, SetError, TabulatedRate,
+};
+use std::{cell::RefCell, io::Cursor};
+
+// the screening function should see the charges of the entrance channel
+#[test]
+fn screening_charges() {
+    let library = network();
+    let seen = RefCell::new(Vec::new());
+    let screening = |_t: f64, _rho: f64, z1: u8, z2: u8| {
+        seen.borrow_mut().push((z1, z2));
+        2.0
+    };
+
+    let reaction = Reaction::new(nuclides(&["he4", "c12"]), nuclides(&["o16"]));
+    let rate = library.reaction_rate(&reaction).unwrap();
+    let screened = rate.screened_rate(1.0, 1.0e4, &screening).unwrap();
+    assert!((screened / rate.rate(1.0) - 2.0).abs() < 1e-12);
+    assert_eq!(*seen.borrow(), [(2, 6)]);
+
+    // a decay isn't screened
+    seen.borrow_mut().clear();
+    let reaction = Reaction::new(nuclides(&["n13"]), nuclides(&["c13"]));
+    let rate = library.reaction_rate(&reaction).unwrap();
+    let screened = rate.screened_rate(1.0, 1.0e4, &screening).unwrap();
+    assert!((screened - rate.rate(1.0)).abs() < 1e-12);
+    assert!(seen.borrow().is_empty());
+}
+
+#[test]
+fn screening_unknown_nuclide() {
+    let reader = Cursor::new(include_str!("v2/multi"));
+    let sets = Iter::new(reader, Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let mut set = sets[0].clone();
+    set.reactants[0] = "xx1".parse::<Nuclide>().unwrap();
+    assert_eq!(
+        set.screened_rate(1.0, 1.0, &|_, _, _, _| 1.0),
+        Err(ReaclibError::Invalid(ParseError::UnknownNuclide(
+            "xx1".to_string()
+        )))
+    );
+}
+
+#[test]
+fn curves() {
+    let library = network();
+    let reaction = Reaction::new(nuclides(&["p", "c12"]), nuclides(&["n13"]));
+    let missing = Reaction::new(nuclides(&["p", "n14"]), nuclides(&["o15"]));
+
+    let curves = library.curves([&reaction, &missing], 0.01, 10.0, 4);
+    assert_eq!(curves.len(), 1);
+    let curve = &curves[0];
+    assert_eq!(curve.reaction, reaction);
+    assert_eq!(curve.temperatures.first(), Some(&0.01));
+    assert_eq!(curve.temperatures.last(), Some(&10.0));
+    assert!((curve.temperatures[1] - 0.1).abs() < 1e-12);
+    let rate = library.reaction_rate(&reaction).unwrap();
+    for (t, r) in curve.temperatures.iter().zip(&curve.rates) {
+        assert_eq!(rate.rate(*t), *r);
+    }
+}
+
+#[test]
+fn temperature_for_rate() {
+    let library = network();
+    let reaction = Reaction::new(nuclides(&["p", "c12"]), nuclides(&["n13"]));
+    let rate = library.reaction_rate(&reaction).unwrap();
+
+    let target = rate.rate(0.5);
+    let t = rate.temperature_for_rate(target, 0.01, 1.0).unwrap();
+    assert!((t - 0.5).abs() < 1e-9);
+
+    // the target isn't reached in the range
+    assert_eq!(rate.temperature_for_rate(target, 0.01, 0.1), None);
+}
+
+// compare against the formula written out in full
+#[test]
+fn rate_formula() {
+    let reader = Cursor::new(include_str!("library/network"));
+    let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    let a = set.params;
+    let t: f64 = 2.0;
+    let expected = f64::exp(
+        a[0] + a[1] / t
+            + a[2] * t.powf(-1.0 / 3.0)
+            + a[3] * t.powf(1.0 / 3.0)
+            + a[4] * t
+            + a[5] * t.powf(5.0 / 3.0)
+            + a[6] * t.ln(),
+    );
+    assert!((set.rate(t) / expected - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn rate_basis() {
+    let reader = Cursor::new(include_str!("library/network"));
+    let sets = Iter::new(reader, Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let basis = RateBasis::new(1.5);
+    assert_eq!(basis.temperature(), 1.5);
+    for set in &sets {
+        assert_eq!(set.rate_with_basis(&basis), set.rate(1.5));
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn rates_par() {
+    let reader = Cursor::new(include_str!("library/network"));
+    let sets = Iter::new(reader, Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let temperatures = [0.1, 1.0, 3.0];
+    let rates = crate::rates_par(&sets, &temperatures);
+    assert_eq!(rates.len(), sets.len());
+    for (set, row) in sets.iter().zip(&rates) {
+        for (t, r) in temperatures.iter().zip(row) {
+            assert_eq!(set.rate(*t), *r);
+        }
+    }
+}
+
+#[cfg(feature = "num-traits")]
+#[test]
+fn rate_generic() {
+    let library = network();
+    for set in library.sets() {
+        assert!((set.rate_generic(1.5_f64) / set.rate(1.5) - 1.0).abs() < 1e-12);
+        let rate = f64::from(set.rate_generic(1.5_f32));
+        assert!((rate / set.rate(1.5) - 1.0).abs() < 1e-4);
+    }
+
+    let reaction = Reaction::new(nuclides(&["p", "c12"]), nuclides(&["n13"]));
+    let rate = library.reaction_rate(&reaction).unwrap();
+    assert!((rate.rate_generic(1.5_f64) / rate.rate(1.5) - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn compiled_rate() {
+    let library = network();
+    for reaction in library.reactions() {
+        let rate = library.reaction_rate(reaction).unwrap();
+        let compiled = rate.compile();
+        assert_eq!(compiled.len(), rate.sets.len());
+        for t in [0.01, 0.1, 1.0, 5.0] {
+            let (a, b) = (compiled.rate(t), rate.rate(t));
+            assert!((a - b).abs() <= 1e-12 * b.abs());
+        }
+    }
+
+    let empty = crate::Rate::new(&[]);
+    assert!(empty.is_empty());
+    assert_eq!(empty.rate(1.0), 0.0);
+}
+
+#[test]
+fn tabulated_rate() {
+    let library = network();
+    let reaction = Reaction::new(nuclides(&["he4", "c12"]), nuclides(&["o16"]));
+    let rate = library.reaction_rate(&reaction).unwrap();
+
+    let table = rate.tabulate(0.1, 10.0, 100);
+    assert_eq!(table.temperatures().count(), 100);
+    for t in [0.1, 0.123, 0.5, 2.7, 10.0] {
+        assert!((table.rate(t) / rate.rate(t) - 1.0).abs() < 1e-3);
+    }
+    // outside the table, the nearest end is used
+    assert_eq!(table.rate(0.01), table.rate(0.1));
+    assert_eq!(table.rate(20.0), table.rate(10.0));
+
+    // the points must be increasing
+    assert!(TabulatedRate::new(&[1.0, 1.0], &[1.0, 2.0]).is_none());
+    assert!(TabulatedRate::new(&[1.0, 2.0], &[1.0]).is_none());
+    assert!(TabulatedRate::new(&[], &[]).is_none());
+    assert!(TabulatedRate::new(&[1.0, f64::NAN], &[1.0, 2.0]).is_none());
+    assert!(TabulatedRate::new(&[1.0, f64::INFINITY], &[1.0, 2.0]).is_none());
+
+    // a straight line in log-log space is reproduced exactly
+    let table = TabulatedRate::new(&[1.0, 2.0, 4.0, 8.0], &[1.0, 4.0, 16.0, 64.0]).unwrap();
+    assert!((table.rate(3.0) - 9.0).abs() < 1e-12);
+
+    // a temperature that isn't positive has no rate
+    for t in [0.0, -1.0, f64::NAN] {
+        assert!(table.rate(t).is_nan());
+    }
+}
+
+// all of the rate sources give the same rate through the trait
+#[test]
+fn rate_evaluator() {
+    fn check(evaluator: &dyn RateEvaluator, expected: f64) {
+        let rate = evaluator.rate(0.3);
+        assert!((rate / expected - 1.0).abs() < 1e-3);
+        assert!((evaluator.ln_rate(0.3) - rate.ln()).abs() < 1e-12);
+    }
+
+    let library = network();
+    let reaction = Reaction::new(nuclides(&["p", "c12"]), nuclides(&["n13"]));
+    let rate = library.reaction_rate(&reaction).unwrap();
+    let expected = rate.rate(0.3);
+
+    let evaluators: Vec<Box<dyn RateEvaluator>> = vec![
+        Box::new(rate.clone()),
+        Box::new(rate.compile()),
+        Box::new(rate.tabulate(0.01, 10.0, 200)),
+    ];
+    for evaluator in &evaluators {
+        check(evaluator, expected);
+    }
+
+    let set = &rate.sets[0];
+    check(set, set.rate(0.3));
+}
+
+// the analytic derivative matches a finite difference
+#[test]
+fn log_derivative() {
+    let library = network();
+    let numeric = |f: &dyn Fn(f64) -> f64, t: f64| {
+        let h = 1e-5;
+        (f(t * (1.0 + h)).ln() - f(t * (1.0 - h)).ln()) / ((1.0 + h).ln() - (1.0 - h).ln())
+    };
+
+    for reaction in library.reactions() {
+        let rate = library.reaction_rate(reaction).unwrap();
+        for t in [0.2, 0.5, 1.0, 4.0] {
+            let expected = numeric(&|t| rate.rate(t), t);
+            assert!((rate.log_derivative(t) - expected).abs() < 1e-5 * expected.abs().max(1.0));
+            for set in &rate.sets {
+                let expected = numeric(&|t| set.rate(t), t);
+                assert!((set.log_derivative(t) - expected).abs() < 1e-5 * expected.abs().max(1.0));
+            }
+        }
+    }
+}
+
+// the analytic second derivative matches a finite difference of the first derivative
+#[test]
+fn log_second_derivative() {
+    let library = network();
+    let numeric = |f: &dyn Fn(f64) -> f64, t: f64| {
+        let h = 1e-5;
+        (f(t * (1.0 + h)) - f(t * (1.0 - h))) / ((1.0 + h).ln() - (1.0 - h).ln())
+    };
+
+    for reaction in library.reactions() {
+        let rate = library.reaction_rate(reaction).unwrap();
+        for t in [0.2, 0.5, 1.0, 4.0] {
+            let expected = numeric(&|t| rate.log_derivative(t), t);
+            let actual = rate.log_second_derivative(t);
+            assert!((actual - expected).abs() < 1e-4 * expected.abs().max(1.0));
+            for set in &rate.sets {
+                let expected = numeric(&|t| set.log_derivative(t), t);
+                let actual = set.log_second_derivative(t);
+                assert!((actual - expected).abs() < 1e-4 * expected.abs().max(1.0));
+            }
+        }
+    }
+}
+
+#[test]
+fn checked_rate() {
+    let library = network();
+    let reaction = Reaction::new(nuclides(&["p", "c12"]), nuclides(&["n13"]));
+    let rate = library.reaction_rate(&reaction).unwrap();
+
+    assert_eq!(rate.checked_rate(0.5), Ok(rate.rate(0.5)));
+    for t in [0.0, -1.0, f64::NAN] {
+        assert_eq!(
+            rate.checked_rate(t),
+            Err(ReaclibError::Set(SetError::NonPositiveTemperature))
+        );
+        assert_eq!(
+            rate.sets[0].checked_rate(t),
+            Err(ReaclibError::Set(SetError::NonPositiveTemperature))
+        );
+    }
+
+    // the rate overflows with a large parameter
+    let mut set = rate.sets[0].clone();
+    set.params[4] = 1000.0;
+    assert!(set.rate(1.0).is_infinite());
+    assert_eq!(
+        set.checked_rate(1.0),
+        Err(ReaclibError::Set(SetError::NonFiniteRate))
+    );
+}
+
+// the components always add up to the total rate
+#[test]
+fn components() {
+    let library = network();
+
+    for reaction in library.reactions() {
+        let rate = library.reaction_rate(reaction).unwrap();
+        let components = rate.components();
+        assert_eq!(
+            components.iter().map(|c| c.sets.len()).sum::<usize>(),
+            rate.sets.len()
+        );
+        for t in [0.1, 1.0, 5.0] {
+            let sum = components.iter().map(|c| c.rate(t)).sum::<f64>();
+            assert!((sum - rate.rate(t)).abs() <= 1e-12 * rate.rate(t));
+        }
+    }
+
+    let reaction = Reaction::new(nuclides(&["p", "c12"]), nuclides(&["n13"]));
+    let components = library.reaction_rate(&reaction).unwrap().components();
+    let flags = components
+        .iter()
+        .map(|c| (c.label.as_str(), c.resonance))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        flags,
+        [
+            ("nacr", Resonance::NonResonant),
+            ("nacr", Resonance::Resonant)
+        ]
+    );
+}
+
+#[test]
+fn summary() {
+    let library = network();
+    let summary = |r: &str| {
+        library
+            .reaction_rate(&r.parse().unwrap())
+            .unwrap()
+            .summary()
+    };
+
+    assert_eq!(
+        summary("p c12 -> n13"),
+        "c12(p,g)n13 — Q=1.944 MeV, 2 sets (nacr), non-resonant+resonant"
+    );
+    assert_eq!(
+        summary("n13 -> c13"),
+        "n13(,e+ nu)c13 — Q=2.220 MeV, 1 set (wc12), weak"
+    );
+    assert_eq!(
+        summary("o16 -> he4 c12"),
+        "o16(g,a)c12 — Q=-7.162 MeV, 1 set (nac2), non-resonant, reverse"
+    );
+
+    let empty = crate::ReactionRate {
+        reaction: "p c12 -> n13".parse().unwrap(),
+        sets: Vec::new(),
+    };
+    assert_eq!(empty.summary(), "c12(p,g)n13 — 0 sets");
+}
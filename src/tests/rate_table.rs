@@ -0,0 +1,27 @@
+use super::network;
+use crate::{write_rate_table, Nuclide, Reaction};
+
+#[test]
+fn table() {
+    let library = network();
+    let nuclide = |s: &str| s.parse::<Nuclide>().unwrap();
+    let reaction = Reaction::new(
+        [nuclide("p"), nuclide("c12")].into_iter().collect(),
+        [nuclide("n13")].into_iter().collect(),
+    );
+    let rate = library.reaction_rate(&reaction).unwrap();
+
+    let temperatures = [0.01, 0.15, 2.0, 10.0];
+    let mut table = Vec::new();
+    write_rate_table(&mut table, &rate, &temperatures).unwrap();
+    let table = String::from_utf8(table).unwrap();
+    let lines = table.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "T9  rate");
+    assert_eq!(lines.len(), 5);
+
+    for (line, t) in lines[1..].iter().zip(temperatures) {
+        let (temperature, value) = line.split_once("  ").unwrap();
+        assert_eq!(temperature, t.to_string());
+        assert!((value.parse::<f64>().unwrap() / rate.rate(t) - 1.0).abs() < 1e-5);
+    }
+}
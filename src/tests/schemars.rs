@@ -0,0 +1,58 @@
+use crate::{Chapter, Library, Set, SetList};
+use schemars::schema_for;
+use serde_json::json;
+
+#[test]
+fn library() {
+    let schema = schema_for!(Library).to_value();
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["required"], json!(["sets"]));
+    assert_eq!(
+        schema["properties"]["metadata"]["$ref"],
+        "#/$defs/LibraryMetadata"
+    );
+    let sets = &schema["properties"]["sets"];
+    assert_eq!(sets["type"], "array");
+    assert_eq!(sets["items"]["$ref"], "#/$defs/Set");
+
+    let set = &schema["$defs"]["Set"];
+    assert_eq!(
+        set["required"],
+        json!([
+            "reactants",
+            "products",
+            "label",
+            "resonance",
+            "reverse",
+            "q_value",
+            "params"
+        ])
+    );
+    assert_eq!(set["properties"]["params"]["minItems"], 7);
+    assert_eq!(set["properties"]["reactants"]["type"], "array");
+    assert_eq!(
+        set["properties"]["reactants"]["items"]["$ref"],
+        "#/$defs/Nuclide"
+    );
+    assert_eq!(
+        schema["$defs"]["Resonance"]["enum"],
+        json!(["NonResonant", "Resonant", "Weak", "S"])
+    );
+    assert_eq!(schema["$defs"]["Nuclide"]["type"], "string");
+}
+
+#[test]
+fn others() {
+    assert_eq!(schema_for!(Set).to_value()["type"], "object");
+    assert_eq!(
+        schema_for!(SetList).to_value()["properties"]["set"]["items"]["$ref"],
+        "#/$defs/Set"
+    );
+    let chapter = schema_for!(Chapter).to_value();
+    // the 11 standard chapters, and `Extended`
+    assert_eq!(chapter["oneOf"].as_array().unwrap().len(), 12);
+    assert_eq!(
+        chapter["oneOf"][11]["properties"]["Extended"]["type"],
+        "object"
+    );
+}
@@ -0,0 +1,218 @@
+use super::network;
+use crate::{Format, Iter, Library, LibraryMetadata, Reaction, Set, SetList, ValidityRange};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Cursor;
+
+#[test]
+fn toml_round_trip() {
+    let library = network();
+    let mut list = SetList::from(&library);
+    list.set[0].validity = Some(ValidityRange::new(0.1, 10.0));
+
+    let s = toml::to_string(&list).unwrap();
+    assert_eq!(s.matches("[[set]]").count(), 7);
+    let reread: SetList = toml::from_str(&s).unwrap();
+    assert_eq!(list, reread);
+}
+
+// the documented mapping can be written by hand, leaving out the optional fields
+#[test]
+fn toml_by_hand() {
+    let s = r#"
+[[set]]
+reactants = ["p", "c12"]
+products = ["n13"]
+label = "nacr"
+resonance = "NonResonant"
+reverse = false
+q_value = 1.944
+params = [17.1482, 0.0, -13.692, -0.230881, 4.44362, -3.15898, -0.666667]
+"#;
+    let list: SetList = toml::from_str(s).unwrap();
+    let library = Library::from(list);
+    assert_eq!(&library.sets()[..1], &network().sets()[..1]);
+
+    let empty: SetList = toml::from_str("").unwrap();
+    assert!(empty.set.is_empty());
+}
+
+// species and labels from other tools may be padded or uppercase
+#[test]
+fn json_untidy_names() {
+    let s = r#"{
+        "reactants": ["   P", "c12  "],
+        "products": [" N13"],
+        "label": "nacr ",
+        "resonance": "NonResonant",
+        "reverse": false,
+        "q_value": 1.944,
+        "params": [17.1482, 0.0, -13.692, -0.230881, 4.44362, -3.15898, -0.666667]
+    }"#;
+    let set: Set = serde_json::from_str(s).unwrap();
+    assert_eq!(set, network().sets()[0]);
+
+    let long = s.replace("nacr ", "nacrx");
+    assert!(serde_json::from_str::<Set>(&long).is_err());
+    let long = s.replace(" N13", "n13xxxx");
+    assert!(serde_json::from_str::<Set>(&long).is_err());
+}
+
+#[test]
+fn yaml_library() {
+    let library = network();
+    let s = serde_yaml::to_string(&library).unwrap();
+    let reread: Library = serde_yaml::from_str(&s).unwrap();
+    assert_eq!(library, reread);
+    // the index is rebuilt
+    assert_eq!(reread.reactions().count(), 6);
+}
+
+// a library is always written as a struct of its metadata and sets
+#[test]
+fn json_library_metadata() {
+    let metadata = LibraryMetadata {
+        name: Some("network".to_string()),
+        notes: vec!["from the test network".to_string()],
+        ..LibraryMetadata::default()
+    };
+    let library = network().with_metadata(metadata);
+
+    let s = serde_json::to_string(&library).unwrap();
+    assert!(s.starts_with(r#"{"metadata":{"name":"network","source":null,"#));
+    let reread: Library = serde_json::from_str(&s).unwrap();
+    assert_eq!(library, reread);
+    assert_eq!(reread.reactions().count(), 6);
+
+    let s = serde_json::to_string(&network()).unwrap();
+    assert!(s.starts_with(r#"{"metadata":{"#));
+    let reread: Library = serde_json::from_str(&s).unwrap();
+    assert!(reread.metadata().is_empty());
+
+    let reread: Library = serde_json::from_str(r#"{"sets": []}"#).unwrap();
+    assert_eq!(reread, Library::new());
+    assert!(serde_json::from_str::<Library>(r#"{"sets": [], "other": 1}"#).is_err());
+
+    // a plain list of sets is read as sets
+    let s = serde_json::to_string(network().sets()).unwrap();
+    assert!(serde_json::from_str::<Library>(&s).is_err());
+    let reread = serde_json::from_str::<Vec<Set>>(&s)
+        .unwrap()
+        .into_iter()
+        .collect::<Library>();
+    assert_eq!(reread, network());
+}
+
+// postcard can't tell what comes next in the data, so the library must have a fixed shape
+#[test]
+fn postcard_library() {
+    let mut metadata = LibraryMetadata::default();
+    metadata.notes.push("from the test network".to_string());
+    let library = network().with_metadata(metadata);
+
+    let data = postcard::to_stdvec(&library).unwrap();
+    let reread: Library = postcard::from_bytes(&data).unwrap();
+    assert_eq!(library, reread);
+}
+
+// with string keys, the map can be written by any yaml tool, and by formats like json
+#[test]
+fn reaction_map() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Network {
+        #[serde(with = "crate::reaction_map")]
+        rates: HashMap<Reaction, Vec<Set>>,
+    }
+
+    let reader = Cursor::new(include_str!("library/network"));
+    let network = Network {
+        rates: crate::to_hash_map(reader, Format::Reaclib2).unwrap(),
+    };
+
+    let s = serde_yaml::to_string(&network).unwrap();
+    assert!(s.contains("\n  he4 c12 -> o16:\n"));
+    assert!(!s.contains("? "));
+    let reread: Network = serde_yaml::from_str(&s).unwrap();
+    assert_eq!(network, reread);
+
+    let s = serde_json::to_string(&network).unwrap();
+    let reread: Network = serde_json::from_str(&s).unwrap();
+    assert_eq!(network, reread);
+
+    let invalid = serde_yaml::from_str::<Network>("rates:\n  he4 c12 o16: []\n");
+    assert!(invalid.is_err());
+}
+
+// every field of a set is written, so structs can be written as maps or as arrays
+#[test]
+fn msgpack_library() {
+    let mut sets = network().sets().to_vec();
+    sets[0].validity = Some(ValidityRange::new(0.1, 10.0));
+    let library = sets.into_iter().collect::<Library>();
+
+    let data = rmp_serde::to_vec_named(&library).unwrap();
+    let reread: Library = rmp_serde::from_slice(&data).unwrap();
+    assert_eq!(library, reread);
+    assert_eq!(
+        reread.sets()[0].validity,
+        Some(ValidityRange::new(0.1, 10.0))
+    );
+
+    let data = rmp_serde::to_vec(library.sets()).unwrap();
+    let reread: Vec<Set> = rmp_serde::from_slice(&data).unwrap();
+    assert_eq!(library.sets(), reread);
+}
+
+// postcard isn't self-describing, so it relies on every field being written
+#[test]
+fn postcard_set() {
+    let reader = Cursor::new(include_str!("library/network"));
+    let mut sets = Iter::builder(reader)
+        .format(Format::Reaclib2)
+        .raw_params(true)
+        .raw_lines(true)
+        .build()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    sets[1].validity = Some(ValidityRange::new(0.1, 10.0));
+
+    let data = postcard::to_stdvec(&sets).unwrap();
+    let reread: Vec<Set> = postcard::from_bytes(&data).unwrap();
+    assert_eq!(sets, reread);
+    assert_eq!(sets[0].raw_params, reread[0].raw_params);
+    assert_eq!(sets[0].raw_lines, reread[0].raw_lines);
+    assert_eq!(reread[0].validity, None);
+
+    let plain = network().sets()[0].clone();
+    let reread: Set = postcard::from_bytes(&postcard::to_stdvec(&plain).unwrap()).unwrap();
+    assert_eq!(reread.raw_params, None);
+}
+
+#[test]
+fn msgpack_reaction_map() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Network {
+        #[serde(with = "crate::reaction_map")]
+        rates: HashMap<Reaction, Vec<Set>>,
+    }
+
+    let reader = Cursor::new(include_str!("library/network"));
+    let network = Network {
+        rates: crate::to_hash_map(reader, Format::Reaclib2).unwrap(),
+    };
+
+    let data = rmp_serde::to_vec_named(&network).unwrap();
+    let reread: Network = rmp_serde::from_slice(&data).unwrap();
+    assert_eq!(network, reread);
+
+    // the keys are plain strings, so other msgpack implementations can read them
+    let raw: HashMap<String, HashMap<String, Vec<IgnoredAny>>> =
+        rmp_serde::from_slice(&data).unwrap();
+    assert!(raw["rates"].contains_key("he4 c12 -> o16"));
+
+    // without `reaction_map`, reactions are written as maps, which msgpack also allows as keys
+    let rates = network.rates;
+    let data = rmp_serde::to_vec_named(&rates).unwrap();
+    let reread: HashMap<Reaction, Vec<Set>> = rmp_serde::from_slice(&data).unwrap();
+    assert_eq!(rates, reread);
+}
@@ -0,0 +1,359 @@
+use crate::{
+    error::ReaclibError, Extrapolation, Format, Iter, Nuclide, Resonance, SetError, SetKey,
+    ValidityRange,
+};
+use arrayvec::ArrayString;
+use std::{
+    collections::{HashMap, HashSet},
+    io::Cursor,
+};
+
+#[test]
+fn approx_eq() {
+    let reader = Cursor::new(include_str!("v2/single"));
+    let set = Iter::with_raw_params(reader, Format::Reaclib2)
+        .next()
+        .unwrap()
+        .unwrap();
+
+    // raw params are ignored
+    let mut other = set.clone();
+    other.raw_params = None;
+    assert!(set.approx_eq(&other, 0.0));
+
+    // the tolerance is relative for large values
+    other.q_value = set.q_value + 1e-7;
+    other.params[0] = set.params[0] * (1.0 + 1e-7);
+    assert!(set.approx_eq(&other, 1e-6));
+    assert!(!set.approx_eq(&other, 1e-8));
+
+    // the tolerance is absolute for small values
+    other = set.clone();
+    other.params[1] = 1e-7;
+    assert!(set.approx_eq(&other, 1e-6));
+    assert!(!set.approx_eq(&other, 1e-8));
+
+    // everything else must be exactly the same
+    other = set.clone();
+    other.resonance = Resonance::Resonant;
+    assert!(!set.approx_eq(&other, 1.0));
+}
+
+// the raw text doesn't take part in equality, but everything else does
+#[test]
+fn eq_ignores_raw() {
+    let input = include_str!("library/network");
+    let raw = Iter::builder(Cursor::new(input))
+        .format(Format::Reaclib2)
+        .raw_params(true)
+        .raw_lines(true)
+        .build()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let plain = Iter::new(Cursor::new(input), Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(raw[0].raw_lines.is_some());
+    assert_eq!(raw, plain);
+
+    let mut other = plain[0].clone();
+    other.validity = Some(ValidityRange::new(0.1, 10.0));
+    assert_ne!(other, plain[0]);
+}
+
+#[test]
+fn set_key() {
+    let reader = Cursor::new(include_str!("library/network"));
+    let sets = Iter::with_raw_params(reader, Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let mut unique = sets
+        .iter()
+        .cloned()
+        .chain(sets.iter().cloned())
+        .map(SetKey)
+        .collect::<HashSet<_>>();
+    assert_eq!(unique.len(), sets.len());
+
+    // raw params are ignored
+    let mut set = sets[0].clone();
+    set.raw_params = None;
+    assert!(!unique.insert(SetKey(set.clone())));
+
+    // zeros of different signs are different
+    set.params[1] = -0.0;
+    assert!(unique.insert(SetKey(set)));
+}
+
+#[test]
+fn is_weak() {
+    let reader = Cursor::new(include_str!("v2/multi"));
+    let sets = Iter::new(reader, Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    // n -> p, flagged as weak
+    assert!(sets[0].is_weak());
+    // d -> n p, not weak
+    assert!(!sets[10].is_weak());
+
+    // the label and the change in charge are enough without the flag
+    let mut set = sets[0].clone();
+    set.resonance = Resonance::NonResonant;
+    assert!(set.is_weak());
+    set.label = ArrayString::from("xxxx").unwrap();
+    assert!(set.is_weak());
+    set.products = set.reactants.clone();
+    assert!(!set.is_weak());
+}
+
+#[test]
+fn kind() {
+    use crate::RateKind as K;
+
+    let reader = Cursor::new(include_str!("library/network"));
+    let kinds = Iter::new(reader, Format::Reaclib2)
+        .map(|s| s.unwrap().kind())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            K::Capture,
+            K::Capture,
+            K::Decay,
+            K::Capture,
+            K::Capture,
+            K::Photodisintegration,
+            K::Capture
+        ]
+    );
+
+    let reader = Cursor::new(include_str!("v1/multi"));
+    let kinds = Iter::new(reader, Format::Reaclib1)
+        .map(|s| s.unwrap().kind())
+        .collect::<Vec<_>>();
+    assert!(kinds.contains(&K::Decay));
+    assert!(kinds.contains(&K::Photodisintegration));
+}
+
+#[test]
+fn source_kind() {
+    use crate::SourceKind as S;
+
+    let reader = Cursor::new(include_str!("library/network"));
+    let sources = Iter::new(reader, Format::Reaclib2)
+        .map(|s| s.unwrap().source_kind())
+        .collect::<Vec<_>>();
+    let evaluation = Some(S::Evaluation);
+    assert_eq!(
+        sources,
+        [
+            evaluation,
+            evaluation,
+            Some(S::Experimental),
+            evaluation,
+            evaluation,
+            evaluation,
+            // ia08 isn't known
+            None
+        ]
+    );
+}
+
+#[test]
+fn half_life() {
+    let reader = Cursor::new(include_str!("library/network"));
+    let sets = Iter::new(reader, Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    // n13 has a half-life of about 10 minutes
+    let half_life = sets[2].half_life().unwrap();
+    assert!((half_life - 598.0).abs() < 1.0);
+    // p + c12 isn't a decay
+    assert_eq!(
+        sets[0].half_life(),
+        Err(ReaclibError::Set(SetError::NotDecay))
+    );
+    // o16 -> he4 c12 is a photodisintegration
+    assert_eq!(
+        sets[5].half_life(),
+        Err(ReaclibError::Set(SetError::NotDecay))
+    );
+
+    let mut set = sets[2].clone();
+    set.params[6] = 1.0;
+    assert_eq!(
+        set.half_life(),
+        Err(ReaclibError::Set(SetError::TemperatureDependent))
+    );
+}
+
+#[test]
+fn molar_rate() {
+    let reader = Cursor::new(include_str!("library/network"));
+    let sets = Iter::new(reader, Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let n = |s: &str| s.parse::<Nuclide>().unwrap();
+    let abundances = HashMap::from([(n("p"), 0.5), (n("c12"), 0.01), (n("n13"), 0.001)]);
+
+    // decays don't depend on density
+    assert_eq!(sets[2].density_factor(1e3), 1.0);
+    assert_eq!(
+        sets[2].molar_rate(1e3, 0.1, &abundances),
+        sets[2].rate(0.1) * 0.001
+    );
+
+    assert_eq!(sets[0].density_factor(1e3), 1e3);
+    let molar_rate = sets[0].molar_rate(1e3, 0.1, &abundances);
+    assert!((molar_rate / (1e3 * sets[0].rate(0.1) * 0.5 * 0.01) - 1.0).abs() < 1e-12);
+
+    // there is no he4
+    assert_eq!(sets[4].molar_rate(1e3, 0.1, &abundances), 0.0);
+}
+
+#[test]
+fn symmetry_factor() {
+    let reader = Cursor::new(include_str!("library/network"));
+    let mut set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    let n = |s: &str| s.parse::<Nuclide>().unwrap();
+    let abundances = HashMap::from([(n("he4"), 0.1), (n("p"), 0.5)]);
+
+    assert_eq!(set.symmetry_factor(), 1.0);
+
+    set.reactants = [n("p"), n("p")].into_iter().collect();
+    assert_eq!(set.symmetry_factor(), 0.5);
+    let molar_rate = set.molar_rate(1e3, 0.1, &abundances);
+    assert!((molar_rate / (0.5 * 1e3 * set.rate(0.1) * 0.25) - 1.0).abs() < 1e-12);
+
+    set.reactants = [n("he4"), n("he4"), n("he4")].into_iter().collect();
+    assert!((set.symmetry_factor() - 1.0 / 6.0).abs() < 1e-15);
+
+    set.reactants = [n("p"), n("he4"), n("p"), n("he4")].into_iter().collect();
+    assert_eq!(set.symmetry_factor(), 0.25);
+}
+
+// each set can have its own range, and the policy decides what happens outside of it
+#[test]
+fn validity() {
+    let reader = Cursor::new(include_str!("library/network"));
+    let mut set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    assert_eq!(set.validity_range(), ValidityRange::REACLIB);
+    assert_eq!(
+        set.rate_with_policy(0.001, Extrapolation::default()),
+        Ok(set.rate(0.001))
+    );
+
+    set.validity = Some(ValidityRange::new(0.1, 1.0));
+    assert_eq!(
+        set.rate_with_policy(0.5, Extrapolation::Error),
+        Ok(set.rate(0.5))
+    );
+    assert_eq!(
+        set.rate_with_policy(0.05, Extrapolation::Error),
+        Err(ReaclibError::Set(SetError::TemperatureOutOfRange))
+    );
+    assert_eq!(
+        set.rate_with_policy(0.05, Extrapolation::ClampToBoundary),
+        Ok(set.rate(0.1))
+    );
+    assert_eq!(
+        set.rate_with_policy(2.0, Extrapolation::ClampToBoundary),
+        Ok(set.rate(1.0))
+    );
+    assert_eq!(
+        set.rate_with_policy(2.0, Extrapolation::Extrapolate),
+        Ok(set.rate(2.0))
+    );
+
+    // an empty range is an error, instead of a panic when clamping
+    let mut inverted = set.clone();
+    for range in [
+        ValidityRange::new(10.0, 1.0),
+        ValidityRange::new(f64::NAN, 1.0),
+        ValidityRange::new(0.1, f64::NAN),
+    ] {
+        assert!(!range.is_valid());
+        inverted.validity = Some(range);
+        for policy in [
+            Extrapolation::Error,
+            Extrapolation::ClampToBoundary,
+            Extrapolation::Extrapolate,
+        ] {
+            assert_eq!(
+                inverted.rate_with_policy(5.0, policy),
+                Err(ReaclibError::Set(SetError::InvalidValidityRange))
+            );
+        }
+    }
+
+    // the validity isn't part of the set's identity
+    let mut other = set.clone();
+    other.validity = None;
+    assert!(set.approx_eq(&other, 0.0));
+    assert_eq!(SetKey(set), SetKey(other));
+}
+
+// sets with nuclides in a different order share a canonical key
+#[test]
+fn canonical_hash_map() {
+    let reader = Cursor::new(include_str!("library/network"));
+    let mut sets = Iter::new(reader, Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    sets[1].reactants.swap(0, 1);
+    let mut input = Vec::new();
+    crate::write_sets(&mut input, &sets, Format::Reaclib2).unwrap();
+
+    let map = crate::to_hash_map(Cursor::new(&input), Format::Reaclib2).unwrap();
+    assert_eq!(map.len(), 7);
+    let map = crate::to_canonical_hash_map(Cursor::new(&input), Format::Reaclib2).unwrap();
+    assert_eq!(map.len(), 6);
+    assert_eq!(map[&sets[0].reaction()].len(), 2);
+    assert!(map.keys().all(|r| *r == r.canonical()));
+}
+
+// without extended chapters, each reaction is only in one chapter
+#[test]
+fn chapter_map() {
+    let reader = Cursor::new(include_str!("library/network"));
+    let map = Iter::new(reader, Format::Reaclib2).chapter_map().unwrap();
+    assert_eq!(map.len(), 6);
+    assert!(map.keys().all(|(c, r)| r.chapter() == Some(*c)));
+
+    let reader = Cursor::new(include_str!("library/network"));
+    let mut iter = Iter::new(reader, Format::Reaclib2);
+    assert_eq!(iter.chapter(), None);
+    iter.nth(2).unwrap().unwrap();
+    assert_eq!(iter.chapter(), Some(crate::Chapter::Chapter1));
+}
+
+// the reactions are in the order they first appear
+#[cfg(feature = "indexmap")]
+#[test]
+fn index_map() {
+    let input = include_str!("library/network");
+    let map = crate::to_index_map(Cursor::new(input), Format::Reaclib2).unwrap();
+    let sets = Iter::new(Cursor::new(input), Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let mut reactions = sets.iter().map(crate::Set::reaction).collect::<Vec<_>>();
+    reactions.dedup();
+    assert_eq!(map.keys().cloned().collect::<Vec<_>>(), reactions);
+    assert_eq!(map[0].len(), 2);
+    assert_eq!(map.values().map(Vec::len).sum::<usize>(), sets.len());
+}
+
+#[test]
+fn resonance_flag() {
+    for resonance in [
+        Resonance::NonResonant,
+        Resonance::Resonant,
+        Resonance::Weak,
+        Resonance::S,
+    ] {
+        let flag = resonance.as_char().to_string();
+        assert_eq!(flag.parse(), Ok(resonance));
+    }
+}
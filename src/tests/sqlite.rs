@@ -0,0 +1,87 @@
+use super::network;
+use crate::{error::ReaclibError, Library, ParseError};
+use rusqlite::Connection;
+
+#[test]
+fn round_trip() {
+    let library = network();
+    let connection = Connection::open_in_memory().unwrap();
+    library.write_sqlite(&connection).unwrap();
+    assert_eq!(Library::read_sqlite(&connection).unwrap(), library);
+
+    // writing again adds the sets to the existing reactions
+    library.write_sqlite(&connection).unwrap();
+    let reread = Library::read_sqlite(&connection).unwrap();
+    assert_eq!(reread.sets().len(), 2 * library.sets().len());
+    assert_eq!(reread.reactions().count(), library.reactions().count());
+}
+
+#[test]
+fn queries() {
+    let library = network();
+    let connection = Connection::open_in_memory().unwrap();
+    library.write_sqlite(&connection).unwrap();
+
+    let count = |sql: &str| {
+        let count = connection.query_row(sql, [], |row| row.get::<_, i64>(0));
+        usize::try_from(count.unwrap()).unwrap()
+    };
+    assert_eq!(count("SELECT COUNT(*) FROM sets"), library.sets().len());
+    assert_eq!(
+        count("SELECT COUNT(*) FROM reactions"),
+        library.reactions().count()
+    );
+    assert_eq!(
+        count(
+            "SELECT COUNT(*) FROM sets \
+             JOIN species ON species.reaction_id = sets.reaction_id \
+             WHERE species.nuclide = 'c12' AND species.role = 'reactant'"
+        ),
+        library
+            .sets()
+            .iter()
+            .filter(|s| s.reactants.iter().any(|n| n.as_str() == "c12"))
+            .count()
+    );
+    let chapter: u8 = connection
+        .query_row(
+            "SELECT chapter FROM reactions WHERE name = 'he4 c12 -> o16'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(chapter, 4);
+}
+
+#[test]
+fn file() {
+    let library = network();
+    let path = std::env::temp_dir().join(format!("reaclib-test-{}.sqlite", std::process::id()));
+    library.save_sqlite(&path).unwrap();
+    let reread = Library::load_sqlite(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(reread.unwrap(), library);
+
+    assert!(Library::load_sqlite(&path).is_err());
+    assert!(!path.exists());
+}
+
+#[test]
+fn invalid() {
+    let connection = Connection::open_in_memory().unwrap();
+    assert!(matches!(
+        Library::read_sqlite(&connection),
+        Err(ReaclibError::Sqlite(_))
+    ));
+
+    network().write_sqlite(&connection).unwrap();
+    connection
+        .execute("UPDATE sets SET resonance = 'x' WHERE id = 1", [])
+        .unwrap();
+    assert_eq!(
+        Library::read_sqlite(&connection),
+        Err(ReaclibError::Invalid(ParseError::UnknownResonance(
+            "x".to_string()
+        )))
+    );
+}
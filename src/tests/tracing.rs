@@ -0,0 +1,95 @@
+use crate::{Format, Iter};
+use ::tracing::{
+    field::{Field, Visit},
+    span, Event, Metadata, Subscriber,
+};
+use std::{
+    fmt::{Debug, Write},
+    sync::{Arc, Mutex},
+};
+
+// a subscriber that records each event as its level followed by its fields
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        write!(self.0, " {}={value:?}", field.name()).unwrap();
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(event.metadata().level().to_string());
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+fn events(input: &str, format: Format) -> Vec<String> {
+    let recorder = Recorder::default();
+    ::tracing::subscriber::with_default(recorder.clone(), || {
+        Iter::new(input.as_bytes(), format).for_each(drop);
+    });
+    let events = recorder.0.lock().unwrap().clone();
+    events
+}
+
+#[test]
+fn chapters() {
+    let events = events(include_str!("v1/multi_chapter"), Format::Reaclib1);
+    let chapters = events
+        .iter()
+        .filter(|e| e.contains("new chapter"))
+        .collect::<Vec<_>>();
+    assert_eq!(chapters.len(), 3);
+    assert_eq!(chapters[0], "DEBUG message=new chapter chapter=11 set=0");
+}
+
+#[test]
+fn errors() {
+    let events = events(include_str!("v2/unknown_resonance"), Format::Reaclib2);
+    assert_eq!(
+        events,
+        [
+            "DEBUG message=new chapter chapter=1 set=0",
+            "WARN message=failed to read a set set=5 error=line 22, resonance flag: unknown resonance: x",
+        ]
+    );
+}
+
+#[test]
+fn progress() {
+    let set = "1
+         n    p                            wc12w     7.82300e-01
+-6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+ 0.000000e+00 0.000000e+00 0.000000e+00
+";
+    let events = events(&set.repeat(20_001), Format::Reaclib2);
+    assert_eq!(
+        events,
+        [
+            "DEBUG message=new chapter chapter=1 set=0",
+            "INFO message=read sets sets=10000",
+            "INFO message=read sets sets=20000",
+        ]
+    );
+}
@@ -0,0 +1,45 @@
+use super::network;
+use crate::TypedRate;
+use uom::si::{f64::ThermodynamicTemperature, frequency::hertz, thermodynamic_temperature::kelvin};
+
+#[test]
+fn typed_rate() {
+    let library = network();
+    let temperature = ThermodynamicTemperature::new::<kelvin>(2e9);
+    let rate = |r: &str| library.reaction_rate(&r.parse().unwrap()).unwrap();
+
+    let decay = rate("n13 -> c13");
+    match decay.typed_rate(temperature) {
+        Some(TypedRate::Decay(r)) => assert_eq!(r.get::<hertz>(), decay.rate(2.0)),
+        r => panic!("expected a decay rate, got {r:?}"),
+    }
+
+    // the values are in SI base units, so m³ instead of cm³
+    let capture = rate("p c12 -> n13");
+    match capture.typed_rate(temperature) {
+        Some(TypedRate::TwoBody(r)) => {
+            assert!((r.value * 1e6 / capture.rate(2.0) - 1.0).abs() < 1e-12);
+        }
+        r => panic!("expected a two-body rate, got {r:?}"),
+    }
+    let set = &capture.sets[0];
+    assert!(matches!(
+        set.typed_rate(temperature),
+        Some(TypedRate::TwoBody(_))
+    ));
+
+    let mut triple = set.clone();
+    triple.reactants = ["he4", "he4", "he4"]
+        .iter()
+        .map(|n| n.parse().unwrap())
+        .collect();
+    match triple.typed_rate(temperature) {
+        Some(TypedRate::ThreeBody(r)) => {
+            assert!((r.value * 1e12 / triple.rate(2.0) - 1.0).abs() < 1e-12);
+        }
+        r => panic!("expected a three-body rate, got {r:?}"),
+    }
+
+    triple.reactants.clear();
+    assert_eq!(triple.typed_rate(temperature), None);
+}
@@ -1,4 +1,4 @@
-use crate::{error::ReaclibError, Format, Iter};
+use crate::{error::ReaclibError, Chapter, Field, Format, Iter, ParseError};
 use std::io::{self, Cursor};
 
 // if the file is empty, that's not an error, there are just no items
@@ -22,7 +22,10 @@ fn single() {
 fn chapter_unset() {
     let reader = Cursor::new(include_str!("v1/chapter_unset"));
     let mut iter = Iter::new(reader, Format::Reaclib1);
-    assert_eq!(iter.next().unwrap(), Err(ReaclibError::ChapterUnset));
+    assert_eq!(
+        iter.next().unwrap(),
+        Err(ReaclibError::Invalid(ParseError::ChapterUnset))
+    );
     assert!(iter.next().is_none());
 }
 
@@ -80,12 +83,18 @@ fn unfinished_line() {
 
     let reader = Cursor::new(include_str!("v1/unfinished_line_4"));
     let mut iter = Iter::new(reader, Format::Reaclib1);
-    assert_eq!(iter.next().unwrap(), Err(ReaclibError::TooShortLine));
+    assert_eq!(
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(&ParseError::TooShortLine)
+    );
     assert!(iter.next().is_none());
 
     let reader = Cursor::new(include_str!("v1/unfinished_line_5"));
     let mut iter = Iter::new(reader, Format::Reaclib1);
-    assert_eq!(iter.next().unwrap(), Err(ReaclibError::TooShortLine));
+    assert_eq!(
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(&ParseError::TooShortLine)
+    );
     assert!(iter.next().is_none());
 
     // the end spaces don't matter
@@ -101,7 +110,10 @@ fn too_few_lines() {
     let reader = Cursor::new(include_str!("v1/too_few_lines"));
     let mut iter = Iter::new(reader, Format::Reaclib1);
     assert!(iter.next().unwrap().is_ok());
-    assert_eq!(iter.next().unwrap(), Err(ReaclibError::TooFewLines));
+    assert_eq!(
+        iter.next().unwrap(),
+        Err(ReaclibError::Invalid(ParseError::TooFewLines))
+    );
     assert!(iter.next().is_none());
 }
 
@@ -111,15 +123,18 @@ fn str_index() {
     // the char spans a slice boundary, so we get an indexing error
     let reader = Cursor::new(include_str!("v1/str_index_1"));
     let mut iter = Iter::new(reader, Format::Reaclib1);
-    assert_eq!(iter.next().unwrap(), Err(ReaclibError::StrIndex));
+    assert_eq!(
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(&ParseError::StrIndex)
+    );
     assert!(iter.next().is_none());
 
     // the char is within a slice, so we get a parsing error
     let reader = Cursor::new(include_str!("v1/str_index_2"));
     let mut iter = Iter::new(reader, Format::Reaclib1);
     assert!(matches!(
-        iter.next().unwrap(),
-        Err(ReaclibError::ParseFloat(_))
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(ParseError::ParseFloat(_))
     ));
     assert!(iter.next().is_none());
 }
@@ -131,7 +146,7 @@ fn non_utf8() {
     let mut iter = Iter::new(reader, Format::Reaclib1);
     assert_eq!(
         iter.next().unwrap(),
-        Err(ReaclibError::Io(io::ErrorKind::InvalidData))
+        Err(ReaclibError::Io(io::ErrorKind::InvalidData.into()))
     );
 }
 
@@ -140,7 +155,10 @@ fn unknown_chapter() {
     let reader = Cursor::new(include_str!("v1/unknown_chapter"));
     let mut iter = Iter::new(reader, Format::Reaclib1);
     assert!(iter.next().unwrap().is_ok());
-    assert_eq!(iter.next().unwrap(), Err(ReaclibError::UnknownChapter(12)));
+    let error = iter.next().unwrap().unwrap_err();
+    assert_eq!(error.parse_error(), Some(&ParseError::UnknownChapter(12)));
+    // the unknown chapter number is on line 10, after two chapter headers
+    assert_eq!(error.field(), Some((10, Field::Chapter)));
 }
 
 #[test]
@@ -155,8 +173,8 @@ fn unknown_resonance() {
     assert_eq!(iter.next().unwrap().unwrap().resonance, R::Weak);
     assert_eq!(iter.next().unwrap().unwrap().resonance, R::S);
     assert!(matches!(
-        iter.next().unwrap(),
-        Err(ReaclibError::UnknownResonance(_))
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(ParseError::UnknownResonance(_))
     ));
 }
 
@@ -178,24 +196,24 @@ fn parse_error() {
     let reader = Cursor::new(include_str!("v1/parse_int_error"));
     let mut iter = Iter::new(reader, Format::Reaclib1);
     assert!(matches!(
-        iter.next().unwrap(),
-        Err(ReaclibError::ParseInt(_))
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(ParseError::ParseInt(_))
     ));
 
     // fails to parse a float in the q-value
     let reader = Cursor::new(include_str!("v1/parse_float_error_1"));
     let mut iter = Iter::new(reader, Format::Reaclib1);
     assert!(matches!(
-        iter.next().unwrap(),
-        Err(ReaclibError::ParseFloat(_))
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(ParseError::ParseFloat(_))
     ));
 
     // fails to parse a float in the params
     let reader = Cursor::new(include_str!("v1/parse_float_error_2"));
     let mut iter = Iter::new(reader, Format::Reaclib1);
     assert!(matches!(
-        iter.next().unwrap(),
-        Err(ReaclibError::ParseFloat(_))
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(ParseError::ParseFloat(_))
     ));
 }
 
@@ -203,7 +221,7 @@ fn parse_error() {
 fn multi() {
     let reader = Cursor::new(include_str!("v1/multi"));
     let iter = Iter::new(reader, Format::Reaclib1);
-    assert!(matches!(iter.collect::<Result<Vec<_>, _>>(), Ok(_)));
+    assert!(iter.collect::<Result<Vec<_>, _>>().is_ok());
 }
 
 // This should fail when trying to parse a v2 file
@@ -211,5 +229,125 @@ fn multi() {
 fn multi_v2() {
     let reader = Cursor::new(include_str!("v2/multi"));
     let iter = Iter::new(reader, Format::Reaclib1);
-    assert!(matches!(iter.collect::<Result<Vec<_>, _>>(), Err(_)));
+    assert!(iter.collect::<Result<Vec<_>, _>>().is_err());
+}
+
+// chapter headers aren't part of a set's span
+#[test]
+fn spans() {
+    let reader = Cursor::new(include_str!("v1/multi_chapter"));
+    let mut iter = Iter::builder(reader)
+        .format(Format::Reaclib1)
+        .with_spans(true)
+        .build();
+    iter.next().unwrap().unwrap();
+    assert_eq!(iter.span().unwrap().to_string(), "lines 10-12");
+}
+
+// the count includes errors, but not chapter headers
+#[test]
+fn sized() {
+    for input in [
+        &include_bytes!("v1/multi_chapter")[..],
+        include_bytes!("v1/empty"),
+        include_bytes!("v1/non_utf8"),
+        include_bytes!("v1/unknown_chapter"),
+        include_bytes!("v1/too_few_lines"),
+        include_bytes!("v1/chapter_unset"),
+    ] {
+        let iter = crate::SizedIter::new(Cursor::new(input), Format::Reaclib1).unwrap();
+        let expected = Iter::new(Cursor::new(input), Format::Reaclib1).count();
+        assert_eq!(iter.len(), expected);
+        assert_eq!(iter.count(), expected);
+    }
+}
+
+// seeking follows the chapter headers, and doesn't change the sets that are returned
+#[test]
+fn seek_to_chapter() {
+    let input = include_str!("v1/multi_chapter");
+    let mut iter = Iter::builder(Cursor::new(input))
+        .format(Format::Reaclib1)
+        .with_spans(true)
+        .build();
+    assert!(iter.seek_to_chapter(Chapter::Chapter1).unwrap());
+    assert!(iter.seek_to_chapter(Chapter::Chapter1).unwrap());
+    let set = iter.next().unwrap().unwrap();
+    assert_eq!(set.label.as_str(), "wc12");
+    assert_eq!(iter.span().unwrap().to_string(), "lines 22-24");
+
+    let expected = Iter::new(Cursor::new(input), Format::Reaclib1)
+        .skip(4)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(iter.seek_to_chapter(Chapter::Chapter7).unwrap());
+    assert_eq!(iter.collect::<Result<Vec<_>, _>>().unwrap(), expected);
+
+    let mut iter = Iter::new(Cursor::new(input), Format::Reaclib1);
+    assert!(!iter.seek_to_chapter(Chapter::Chapter2).unwrap());
+    assert!(iter.next().is_none());
+}
+
+// lenient parsing skips comments and blank lines between sets, but not in chapter headers
+#[test]
+fn lenient_comments() {
+    let input = include_str!("v1/comments");
+    let expected = Iter::new(
+        Cursor::new(include_str!("v1/multi_chapter")),
+        Format::Reaclib1,
+    )
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+    let build = || {
+        Iter::builder(Cursor::new(input))
+            .format(Format::Reaclib1)
+            .lenient(true)
+    };
+    let sets = build().build().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(sets, expected);
+    assert_eq!(build().build_sized().unwrap().len(), expected.len());
+
+    let mut iter = build().build();
+    assert!(iter.seek_to_chapter(Chapter::Chapter1).unwrap());
+    assert_eq!(iter.next().unwrap().unwrap(), expected[3]);
+}
+
+// the blank lines of chapter headers survive other line endings
+#[test]
+fn line_endings() {
+    let input = include_str!("v1/multi_chapter");
+    let expected = Iter::new(Cursor::new(input), Format::Reaclib1)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    for ending in ["\r\n", "\r"] {
+        let converted = input.replace('\n', ending);
+        let sets = Iter::new(Cursor::new(&converted), Format::Reaclib1)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(sets, expected, "{ending:?}");
+    }
+}
+
+// with old Mac line endings, the empty lines of chapter headers are each a line of their own
+#[test]
+fn cr_only() {
+    let input = include_str!("v1/multi_chapter");
+    let expected = Iter::new(Cursor::new(input), Format::Reaclib1)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let converted = input
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\r");
+    assert!(converted.contains("1\r\r\r"));
+    let sets = Iter::new(Cursor::new(&converted), Format::Reaclib1)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(sets, expected);
+    let reader = io::BufReader::with_capacity(3, Cursor::new(&converted));
+    let sets = Iter::new(reader, Format::Reaclib1)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(sets, expected);
 }
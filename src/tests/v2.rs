@@ -1,4 +1,4 @@
-use crate::{error::ReaclibError, Format, Iter};
+use crate::{error::ReaclibError, Chapter, Field, Format, Iter, Lines, ParseError, SetError};
 use std::io::{self, Cursor};
 
 // if the file is empty, that's not an error, there are just no items
@@ -29,12 +29,18 @@ fn unfinished_line() {
 
     let reader = Cursor::new(include_str!("v2/unfinished_line_2"));
     let mut iter = Iter::new(reader, Format::Reaclib2);
-    assert_eq!(iter.next().unwrap(), Err(ReaclibError::TooShortLine));
+    assert_eq!(
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(&ParseError::TooShortLine)
+    );
     assert!(iter.next().is_none());
 
     let reader = Cursor::new(include_str!("v2/unfinished_line_3"));
     let mut iter = Iter::new(reader, Format::Reaclib2);
-    assert_eq!(iter.next().unwrap(), Err(ReaclibError::TooShortLine));
+    assert_eq!(
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(&ParseError::TooShortLine)
+    );
     assert!(iter.next().is_none());
 
     // the end spaces don't matter
@@ -50,14 +56,20 @@ fn too_few_lines() {
     // if we don't have a chapter
     let reader = Cursor::new(include_str!("v2/too_few_lines_1"));
     let mut iter = Iter::new(reader, Format::Reaclib2);
-    assert_eq!(iter.next().unwrap(), Err(ReaclibError::TooFewLines));
+    assert_eq!(
+        iter.next().unwrap(),
+        Err(ReaclibError::Invalid(ParseError::TooFewLines))
+    );
     assert!(iter.next().is_none());
 
     // if we don't have the rest of a set
     let reader = Cursor::new(include_str!("v2/too_few_lines_2"));
     let mut iter = Iter::new(reader, Format::Reaclib2);
     assert!(iter.next().unwrap().is_ok());
-    assert_eq!(iter.next().unwrap(), Err(ReaclibError::TooFewLines));
+    assert_eq!(
+        iter.next().unwrap(),
+        Err(ReaclibError::Invalid(ParseError::TooFewLines))
+    );
     assert!(iter.next().is_none());
 }
 
@@ -67,15 +79,18 @@ fn str_index() {
     // the char spans a slice boundary, so we get an indexing error
     let reader = Cursor::new(include_str!("v2/str_index_1"));
     let mut iter = Iter::new(reader, Format::Reaclib2);
-    assert_eq!(iter.next().unwrap(), Err(ReaclibError::StrIndex));
+    assert_eq!(
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(&ParseError::StrIndex)
+    );
     assert!(iter.next().is_none());
 
     // the char is within a slice, so we get a parsing error
     let reader = Cursor::new(include_str!("v2/str_index_2"));
     let mut iter = Iter::new(reader, Format::Reaclib2);
     assert!(matches!(
-        iter.next().unwrap(),
-        Err(ReaclibError::ParseFloat(_))
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(ParseError::ParseFloat(_))
     ));
     assert!(iter.next().is_none());
 }
@@ -87,7 +102,7 @@ fn non_utf8() {
     let mut iter = Iter::new(reader, Format::Reaclib2);
     assert_eq!(
         iter.next().unwrap(),
-        Err(ReaclibError::Io(io::ErrorKind::InvalidData))
+        Err(ReaclibError::Io(io::ErrorKind::InvalidData.into()))
     );
 }
 
@@ -96,7 +111,10 @@ fn unknown_chapter() {
     let reader = Cursor::new(include_str!("v2/unknown_chapter"));
     let mut iter = Iter::new(reader, Format::Reaclib2);
     assert!(iter.next().unwrap().is_ok());
-    assert_eq!(iter.next().unwrap(), Err(ReaclibError::UnknownChapter(12)));
+    assert_eq!(
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(&ParseError::UnknownChapter(12))
+    );
 }
 
 #[test]
@@ -111,8 +129,8 @@ fn unknown_resonance() {
     assert_eq!(iter.next().unwrap().unwrap().resonance, R::Weak);
     assert_eq!(iter.next().unwrap().unwrap().resonance, R::S);
     assert!(matches!(
-        iter.next().unwrap(),
-        Err(ReaclibError::UnknownResonance(_))
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(ParseError::UnknownResonance(_))
     ));
 }
 
@@ -134,32 +152,32 @@ fn parse_error() {
     let reader = Cursor::new(include_str!("v2/parse_int_error_1"));
     let mut iter = Iter::new(reader, Format::Reaclib2);
     assert!(matches!(
-        iter.next().unwrap(),
-        Err(ReaclibError::ParseInt(_))
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(ParseError::ParseInt(_))
     ));
 
     // fails to parse an int in a chapter
     let reader = Cursor::new(include_str!("v2/parse_int_error_2"));
     let mut iter = Iter::new(reader, Format::Reaclib2);
     assert!(matches!(
-        iter.next().unwrap(),
-        Err(ReaclibError::ParseInt(_))
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(ParseError::ParseInt(_))
     ));
 
     // fails to parse a float in the q-value
     let reader = Cursor::new(include_str!("v2/parse_float_error_1"));
     let mut iter = Iter::new(reader, Format::Reaclib2);
     assert!(matches!(
-        iter.next().unwrap(),
-        Err(ReaclibError::ParseFloat(_))
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(ParseError::ParseFloat(_))
     ));
 
     // fails to parse a float in the params
     let reader = Cursor::new(include_str!("v2/parse_float_error_2"));
     let mut iter = Iter::new(reader, Format::Reaclib2);
     assert!(matches!(
-        iter.next().unwrap(),
-        Err(ReaclibError::ParseFloat(_))
+        iter.next().unwrap().unwrap_err().parse_error(),
+        Some(ParseError::ParseFloat(_))
     ));
 }
 
@@ -167,7 +185,7 @@ fn parse_error() {
 fn multi() {
     let reader = Cursor::new(include_str!("v2/multi"));
     let iter = Iter::new(reader, Format::Reaclib2);
-    assert!(matches!(iter.collect::<Result<Vec<_>, _>>(), Ok(_)));
+    assert!(iter.collect::<Result<Vec<_>, _>>().is_ok());
 }
 
 // This should fail when trying to parse a v1 file
@@ -175,5 +193,469 @@ fn multi() {
 fn multi_v2() {
     let reader = Cursor::new(include_str!("v1/multi"));
     let iter = Iter::new(reader, Format::Reaclib2);
-    assert!(matches!(iter.collect::<Result<Vec<_>, _>>(), Err(_)));
+    assert!(iter.collect::<Result<Vec<_>, _>>().is_err());
+}
+
+// lenient parsing fills in what is missing, instead of returning an error
+#[test]
+fn lenient() {
+    let lenient = |input| {
+        Iter::builder(Cursor::new(input))
+            .lenient(true)
+            .build()
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    // the Q-value is cut off
+    let sets = lenient(include_str!("v2/unfinished_line_2")).unwrap();
+    assert_eq!(sets[0].q_value, 0.0);
+    assert_eq!(sets[0].params[0], -6.78161);
+
+    // an unknown resonance flag and blank parameters
+    let input = include_str!("v2/unknown_resonance").replace("-6.781610e+00", "             ");
+    let sets = lenient(&input).unwrap();
+    assert_eq!(sets.len(), 6);
+    assert_eq!(sets[5].resonance, crate::Resonance::NonResonant);
+    assert_eq!(sets[5].params[0], 0.0);
+
+    // it isn't lenient by default
+    let mut iter = Iter::builder(Cursor::new(&input)).build();
+    assert_eq!(
+        iter.next().unwrap(),
+        Err(ReaclibError::Parse {
+            line: 3,
+            field: Field::Param(0),
+            kind: ParseError::ParseFloat("".parse::<f64>().unwrap_err()),
+        })
+    );
+
+    // other errors still happen
+    assert_eq!(
+        lenient(include_str!("v2/unknown_chapter"))
+            .unwrap_err()
+            .parse_error(),
+        Some(&ParseError::UnknownChapter(12))
+    );
+}
+
+#[test]
+fn spans() {
+    let reader = Cursor::new(include_str!("v2/multi"));
+    let mut iter = Iter::builder(reader)
+        .format(Format::Reaclib2)
+        .with_spans(true)
+        .build();
+    assert_eq!(iter.span(), None);
+    iter.next().unwrap().unwrap();
+    assert_eq!(iter.span().unwrap().to_string(), "lines 1-4");
+    iter.next().unwrap().unwrap();
+    assert_eq!(iter.span().unwrap().to_string(), "lines 5-8");
+
+    // spans aren't kept by default
+    let mut iter = Iter::new(Cursor::new(include_str!("v2/multi")), Format::Reaclib2);
+    iter.next();
+    assert_eq!(iter.span(), None);
+
+    // an error's span covers the lines that were read
+    let reader = Cursor::new(include_str!("v2/too_few_lines_2"));
+    let mut iter = Iter::builder(reader).with_spans(true).build();
+    let spans = std::iter::from_fn(|| iter.next().map(|_| iter.span().unwrap()))
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(spans, ["lines 1-4", "lines 5-7"]);
+}
+
+#[test]
+fn collect_partial() {
+    let reader = Cursor::new(include_str!("v2/unknown_resonance"));
+    let (sets, errors) = Iter::new(reader, Format::Reaclib2).collect_partial();
+    assert_eq!(sets.len(), 5);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0.to_string(), "lines 21-24");
+    assert_eq!(
+        errors[0].1,
+        ReaclibError::Parse {
+            line: 22,
+            field: Field::Resonance,
+            kind: ParseError::UnknownResonance("x".to_string()),
+        }
+    );
+
+    let reader = Cursor::new(include_bytes!("v2/non_utf8"));
+    let (_, errors) = Iter::new(reader, Format::Reaclib2).collect_partial();
+    assert_eq!(
+        errors[0].1,
+        ReaclibError::Io(io::ErrorKind::InvalidData.into())
+    );
+
+    // an io error that would repeat forever stops reading
+    let reader = std::io::BufReader::new(std::fs::File::open("src").unwrap());
+    let (sets, errors) = Iter::new(reader, Format::Reaclib2).collect_partial();
+    assert!(sets.is_empty());
+    assert_eq!(errors.len(), 1);
+}
+
+// the error says where the set that failed is, and what its reaction is, if it can be read
+#[test]
+fn to_hash_map_error() {
+    use std::error::Error;
+
+    let reader = Cursor::new(include_str!("v2/unknown_resonance"));
+    let error = crate::to_hash_map(reader, Format::Reaclib2).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "lines 21-24 (n -> p): line 22, resonance flag: unknown resonance: x"
+    );
+    assert_eq!(
+        error.source().unwrap().to_string(),
+        "line 22, resonance flag: unknown resonance: x"
+    );
+    assert_eq!(error.field(), Some((22, Field::Resonance)));
+
+    let reader = Cursor::new(include_str!("v2/too_few_lines_2"));
+    let error = crate::to_canonical_hash_map(reader, Format::Reaclib2).unwrap_err();
+    let ReaclibError::InSet {
+        span,
+        reaction,
+        source,
+    } = error
+    else {
+        panic!("the error doesn't have a location");
+    };
+    assert_eq!((span.first_line, span.last_line), (5, 7));
+    assert_eq!(reaction, None);
+    assert_eq!(*source, ReaclibError::Invalid(ParseError::TooFewLines));
+
+    // the reaction is still read if the header can't be
+    let input = include_str!("v2/single").replace("wc12w     7.82300e-01", "wc12w     7.8x300e-01");
+    let error = crate::to_hash_map(Cursor::new(input), Format::Reaclib2).unwrap_err();
+    assert!(error.to_string().starts_with("lines 1-4 (n -> p): "));
+}
+
+#[test]
+fn sized() {
+    for input in [
+        &include_bytes!("v2/multi")[..],
+        include_bytes!("v2/empty"),
+        include_bytes!("v2/non_utf8"),
+        include_bytes!("v2/too_few_lines_2"),
+        include_bytes!("v2/unknown_resonance"),
+    ] {
+        let mut iter = Iter::builder(Cursor::new(input))
+            .with_spans(true)
+            .build_sized()
+            .unwrap();
+        let expected = Iter::new(Cursor::new(input), Format::Reaclib2).count();
+        assert_eq!(iter.size_hint(), (expected, Some(expected)));
+        for remaining in (0..expected).rev() {
+            iter.next().unwrap().ok();
+            assert!(iter.span().is_some());
+            assert_eq!(iter.len(), remaining);
+        }
+        assert!(iter.next().is_none());
+    }
+
+    // counting starts from where the reader is
+    let input = include_str!("v2/multi");
+    let total = Iter::new(Cursor::new(input), Format::Reaclib2).count();
+    let mut reader = Cursor::new(input);
+    for _ in 0..4 {
+        std::io::BufRead::read_line(&mut reader, &mut String::new()).unwrap();
+    }
+    let iter = crate::SizedIter::new(reader, Format::Reaclib2).unwrap();
+    assert_eq!(iter.len(), total - 1);
+}
+
+#[test]
+fn seek_to_chapter() {
+    let input = include_str!("v2/multi");
+    let expected = Iter::new(Cursor::new(input), Format::Reaclib2)
+        .skip(10)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let mut iter = Iter::builder(Cursor::new(input)).with_spans(true).build();
+    assert!(iter.seek_to_chapter(Chapter::Chapter2).unwrap());
+    iter.next().unwrap().unwrap();
+    assert_eq!(iter.span().unwrap().to_string(), "lines 41-44");
+
+    let mut iter = Iter::new(Cursor::new(input), Format::Reaclib2);
+    assert!(iter.seek_to_chapter(Chapter::Chapter2).unwrap());
+    assert_eq!(iter.collect::<Result<Vec<_>, _>>().unwrap(), expected);
+
+    // sets that fail to parse are skipped
+    let mut iter = Iter::new(
+        Cursor::new(include_str!("v2/unknown_resonance")),
+        Format::Reaclib2,
+    );
+    assert!(!iter.seek_to_chapter(Chapter::Chapter2).unwrap());
+    assert!(iter.next().is_none());
+}
+
+// every kind of line ending is read the same, even when it is split between reads
+// the line buffers are reused, so nothing from a longer line should be left behind
+#[test]
+fn line_endings() {
+    let input = include_str!("v2/multi");
+    let expected = Iter::new(Cursor::new(input), Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    for ending in ["\r\n", "\r", "\x1a\n", "\0\r\n"] {
+        let converted = input.replace('\n', ending);
+        let sets = Iter::new(Cursor::new(&converted), Format::Reaclib2)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(sets, expected, "{ending:?}");
+        let reader = io::BufReader::with_capacity(3, Cursor::new(&converted));
+        let sets = Iter::new(reader, Format::Reaclib2)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(sets, expected, "{ending:?}");
+        let sized = crate::SizedIter::new(Cursor::new(&converted), Format::Reaclib2).unwrap();
+        assert_eq!(sized.len(), expected.len(), "{ending:?}");
+    }
+    let trimmed = input
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let sets = Iter::new(Cursor::new(trimmed), Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(sets, expected);
+}
+
+// lines from an iterator are parsed the same as lines from a reader
+#[test]
+fn lines() {
+    let input = include_str!("v2/multi");
+    let expected = Iter::new(Cursor::new(input), Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let sets = Iter::new(Lines::from_strs(input.lines()), Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(sets, expected);
+
+    // an error from the source is returned for the set that it was in
+    let lines = input.lines().enumerate().map(|(i, l)| {
+        if i == 5 {
+            Err(io::Error::new(io::ErrorKind::UnexpectedEof, "cut off"))
+        } else {
+            Ok(l.to_string())
+        }
+    });
+    let mut iter = Iter::builder(Lines::new(lines)).with_spans(true).build();
+    assert_eq!(iter.next().unwrap().unwrap(), expected[0]);
+    let error = iter.next().unwrap().unwrap_err();
+    assert_eq!(error, ReaclibError::Io(io::ErrorKind::UnexpectedEof.into()));
+    // the message of the original error is kept
+    assert_eq!(error.to_string(), "read error: cut off");
+    assert_eq!(iter.span().unwrap().to_string(), "lines 5-8");
+    assert_eq!(iter.next().unwrap().unwrap(), expected[2]);
+}
+
+// `D` exponents are read like `e` exponents, and written back out with `e`
+#[test]
+fn fortran_exponent() {
+    let input = include_str!("v2/fortran_exponent");
+    let expected = Iter::new(Cursor::new(include_str!("v2/multi")), Format::Reaclib2)
+        .take(2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let sets = Iter::with_raw_params(Cursor::new(input), Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(sets.len(), 2);
+    for (set, expected) in sets.iter().zip(&expected) {
+        assert_eq!(set.q_value, expected.q_value);
+        assert_eq!(set.params, expected.params);
+    }
+
+    let mut written = Vec::new();
+    crate::write_sets(&mut written, &sets, Format::Reaclib2).unwrap();
+    let written = String::from_utf8(written).unwrap();
+    assert!(!written.contains(['D', 'd']));
+}
+
+// lenient parsing skips comments and blank lines between sets
+#[test]
+fn lenient_comments() {
+    let input = include_str!("v2/comments");
+    let expected = Iter::new(Cursor::new(include_str!("v2/multi")), Format::Reaclib2)
+        .take(3)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let mut iter = Iter::builder(Cursor::new(input))
+        .lenient(true)
+        .with_spans(true)
+        .build();
+    assert_eq!(iter.next().unwrap().unwrap(), expected[0]);
+    assert_eq!(iter.span().unwrap().to_string(), "lines 3-6");
+    assert_eq!(iter.next().unwrap().unwrap(), expected[1]);
+    assert_eq!(iter.span().unwrap().to_string(), "lines 10-14");
+    assert_eq!(iter.next().unwrap().unwrap(), expected[2]);
+    assert!(iter.next().is_none());
+
+    let iter = Iter::builder(Cursor::new(input))
+        .lenient(true)
+        .build_sized()
+        .unwrap();
+    assert_eq!(iter.len(), 3);
+
+    assert!(Iter::new(Cursor::new(input), Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .is_err());
+}
+
+// nuclides in 6-column fields are read whole, and can't be written back in 5 columns
+#[test]
+fn species_width() {
+    let input = include_str!("v2/wide_species");
+    let sets = Iter::builder(Cursor::new(input))
+        .species_width(6)
+        .build()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(sets.len(), 2);
+    assert_eq!(sets[0].reaction().to_string(), "n ta180m -> ta181");
+    assert_eq!(sets[1].reaction().to_string(), "p ta180m -> n n p ta178m");
+    assert_eq!(sets[0].label.as_str(), "ka02");
+    assert_eq!(sets[1].q_value, -12.34);
+
+    let headers = Iter::builder(Cursor::new(input))
+        .species_width(6)
+        .build()
+        .headers()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(headers[1].products.len(), 4);
+
+    // with the usual width, the names are cut up
+    let set = Iter::new(Cursor::new(input), Format::Reaclib2)
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_ne!(set.reaction(), sets[0].reaction());
+
+    assert_eq!(
+        crate::write_sets(Vec::new(), &sets, Format::Reaclib2),
+        Err(ReaclibError::Invalid(ParseError::NuclideTooLong(
+            "ta180m".to_string()
+        )))
+    );
+}
+
+#[test]
+fn extended_chapter() {
+    let input = include_str!("v2/extended_chapter");
+
+    // without declaring the chapter, its set is an error, but the rest are read
+    let results = Iter::new(Cursor::new(input), Format::Reaclib2).collect::<Vec<_>>();
+    assert_eq!(results.len(), 2);
+    assert_eq!(
+        results[0],
+        Err(ReaclibError::Parse {
+            line: 1,
+            field: Field::Chapter,
+            kind: ParseError::UnknownChapter(12),
+        })
+    );
+    assert!(results[1].is_ok());
+
+    let builder = || {
+        Iter::builder(Cursor::new(input))
+            .format(Format::Reaclib2)
+            .chapter(12, 2, 5)
+    };
+    let sets = builder().build().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(sets.len(), 2);
+    assert_eq!(sets[0].reaction().to_string(), "n c12 -> n n n p b9");
+    assert!(!sets[0].products.is_inline());
+    assert_eq!(sets[0].reaction().chapter(), None);
+    assert_eq!(sets[1].reaction().to_string(), "n c12 -> p b12");
+    assert_eq!(sets[1].q_value, -12.6);
+
+    let mut iter = builder().build();
+    let chapter = Chapter::Extended {
+        number: 12,
+        reactants: 2,
+        products: 5,
+    };
+    assert!(iter.seek_to_chapter(chapter).unwrap());
+    assert_eq!(iter.next().unwrap().unwrap(), sets[0]);
+
+    assert_eq!(builder().build_sized().unwrap().len(), 2);
+
+    assert_eq!(
+        crate::write_sets(Vec::new(), &sets, Format::Reaclib2),
+        Err(ReaclibError::Set(SetError::NoMatchingChapter(2, 5)))
+    );
+}
+
+#[test]
+fn skip_preamble() {
+    let input = include_str!("v2/single_reaction");
+
+    let mut iter = Iter::builder(Cursor::new(input))
+        .format(Format::Reaclib2)
+        .skip_preamble(true)
+        .with_spans(true)
+        .build();
+    let first = iter.next().unwrap().unwrap();
+    assert_eq!(iter.preamble(), ["c12(p,g)n13", "nacr", ""]);
+    assert_eq!(iter.span().unwrap().to_string(), "lines 4-7");
+    let second = iter.next().unwrap().unwrap();
+    assert_eq!(second.reaction().to_string(), "p c12 -> n13");
+    assert!(iter.next().is_none());
+    let sets = vec![first, second];
+
+    let sized = Iter::builder(Cursor::new(input))
+        .format(Format::Reaclib2)
+        .skip_preamble(true)
+        .build_sized()
+        .unwrap();
+    assert_eq!(sized.len(), 2);
+    assert_eq!(sized.collect::<Result<Vec<_>, _>>().unwrap(), sets);
+
+    // without skipping, the title is read as a chapter
+    assert!(Iter::new(Cursor::new(input), Format::Reaclib2)
+        .next()
+        .unwrap()
+        .is_err());
+}
+
+// the lines are kept as they were read, even when lenient parsing changes them
+#[test]
+fn raw_lines() {
+    let input = include_str!("v2/unfinished_line_2");
+    let lines = input.lines().skip(1).map(String::from).collect::<Vec<_>>();
+    let set = Iter::builder(Cursor::new(input))
+        .format(Format::Reaclib2)
+        .lenient(true)
+        .raw_lines(true)
+        .build()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(set.raw_lines.as_deref().unwrap()[..], lines[..]);
+
+    // they aren't kept by default
+    let set = Iter::new(Cursor::new(include_str!("v2/single")), Format::Reaclib2)
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(set.raw_lines, None);
+
+    // the sets found by `seek_to_chapter` keep their lines too
+    let input = include_str!("v2/multi");
+    let mut iter = Iter::builder(Cursor::new(input))
+        .format(Format::Reaclib2)
+        .raw_lines(true)
+        .build();
+    assert!(iter.seek_to_chapter(Chapter::Chapter2).unwrap());
+    let set = iter.next().unwrap().unwrap();
+    let lines = set.raw_lines.unwrap();
+    assert!(input.contains(&lines.join("\n")));
+    assert_eq!(set.reactants.len(), 1);
+    assert_eq!(set.products.len(), 2);
 }
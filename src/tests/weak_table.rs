@@ -0,0 +1,113 @@
+use crate::{error::ReaclibError, ParseError, RateEvaluator, TabulatedWeakRate};
+use std::io::Cursor;
+
+// a table with a rate of 10^(t9 + lrhoye - 10), in an arbitrary order, with a header
+fn table(column: usize) -> Result<TabulatedWeakRate, ReaclibError> {
+    let reader = Cursor::new(
+        "# a comment
+  t9  lrhoye  lbeta-   leps+
+  2.0   8.0  0.0   0.0
+  1.0   8.0  -1.0   1.0
+  1.0   7.0  -2.0   1.0
+  2.0   7.0  -1.0   1.0
+  3.0   7.0  0.0   1.0
+  3.0   8.0  1.0   1.0
+",
+    );
+    TabulatedWeakRate::from_reader(reader, column)
+}
+
+#[test]
+fn read() {
+    let beta = table(0).unwrap();
+    assert_eq!(beta.temperatures().count(), 3);
+    assert_eq!(beta.log_densities(), [7.0, 8.0]);
+
+    let close = |a: f64, b: f64| (a / b - 1.0).abs() < 1e-12;
+    // on the grid
+    assert!(close(beta.rate(1.0, 1e7), 1e-2));
+    assert!(close(beta.rate(3.0, 1e8), 10.0));
+    // between densities, and clamped outside of the grid
+    assert!(close(beta.rate(2.0, 10f64.powf(7.5)), 10f64.powf(-0.5)));
+    assert!(close(beta.rate(0.1, 1.0), 1e-2));
+    assert!(close(beta.rate(10.0, 1e12), 10.0));
+    // between temperatures, linearly in ln(T9)
+    let t = 1.5f64;
+    let expected = -2.0 + t.ln() / 2f64.ln();
+    assert!(close(beta.rate(t, 1e7), 10f64.powf(expected)));
+    assert!(close(
+        beta.ln_rate(t, 1e7),
+        expected * std::f64::consts::LN_10
+    ));
+
+    let evaluator = beta.at_density(1e8);
+    assert!(close(evaluator.rate(2.0), 1.0));
+    assert!(close(evaluator.ln_rate(1.0), -std::f64::consts::LN_10));
+
+    let positron_capture = table(1).unwrap();
+    assert!(close(positron_capture.rate(2.0, 1e8), 1.0));
+    assert!(close(positron_capture.rate(3.0, 1e7), 10.0));
+}
+
+#[test]
+fn invalid() {
+    let error = |input: &str| TabulatedWeakRate::from_reader(Cursor::new(input), 0).unwrap_err();
+    assert!(matches!(
+        table(2),
+        Err(ReaclibError::Invalid(ParseError::InvalidTable(_)))
+    ));
+    assert!(matches!(
+        error("1.0 7.0 0.0\n1.0 8.0 0.0\n2.0 7.0 0.0\n"),
+        ReaclibError::Invalid(ParseError::InvalidTable(_))
+    ));
+    assert!(matches!(
+        error("1.0 7.0 0.0\n1.0 7.0 1.0\n"),
+        ReaclibError::Invalid(ParseError::InvalidTable(_))
+    ));
+    assert!(matches!(
+        error(""),
+        ReaclibError::Invalid(ParseError::InvalidTable(_))
+    ));
+    assert!(matches!(
+        error("0.0 7.0 0.0\n"),
+        ReaclibError::Invalid(ParseError::InvalidTable(_))
+    ));
+    assert!(matches!(
+        error("1.0 7.0 x\n"),
+        ReaclibError::Invalid(ParseError::ParseFloat(_))
+    ));
+
+    assert_eq!(
+        TabulatedWeakRate::new(&[1.0, 1.0], &[7.0], &[0.0, 0.0]),
+        None
+    );
+    assert_eq!(TabulatedWeakRate::new(&[1.0], &[7.0, 8.0], &[0.0]), None);
+}
+
+#[test]
+fn outside_domain() {
+    let beta = table(0).unwrap();
+    assert!(beta.rate(-1.0, 1e7).is_nan());
+    assert!(beta.rate(f64::NAN, 1e7).is_nan());
+    assert!(beta.rate(1.0, -5.0).is_nan());
+    assert!(beta.at_density(-5.0).rate(1.0).is_nan());
+    assert!(beta.at_density(1e7).rate(-1.0).is_nan());
+
+    assert_eq!(
+        TabulatedWeakRate::new(&[1.0, f64::NAN], &[7.0], &[0.0, 0.0]),
+        None
+    );
+    assert_eq!(
+        TabulatedWeakRate::new(&[1.0], &[7.0, f64::NAN], &[0.0, 0.0]),
+        None
+    );
+}
+
+// lines that start with a Fortran exponent are read, not skipped as comments
+#[test]
+fn d_exponent() {
+    let reader = Cursor::new("1.0D0 7.0 -2.0\n2.0D0 7.0 -1.0\n");
+    let table = TabulatedWeakRate::from_reader(reader, 0).unwrap();
+    assert_eq!(table.temperatures().count(), 2);
+    assert!((table.rate(2.0, 1e7) - 0.1).abs() < 1e-12);
+}
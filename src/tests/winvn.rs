@@ -0,0 +1,66 @@
+use crate::{Nuclide, ParseError, ReaclibError, Reaction, Winvn};
+use std::io::Cursor;
+
+fn nuclide(name: &str) -> Nuclide {
+    name.parse().unwrap()
+}
+
+#[test]
+fn read() {
+    let winvn = Winvn::from_reader(Cursor::new(include_str!("winvn/winvn"))).unwrap();
+    assert_eq!(winvn.temperatures(), &[0.1, 1.0, 10.0]);
+
+    let o16 = winvn.get(&nuclide("o16")).unwrap();
+    assert_eq!((o16.z, o16.n, o16.a()), (8, 8, 16));
+    assert_eq!(o16.spin, 0.0);
+    assert_eq!(o16.mass_excess, -4.737);
+    // the partition function can be split across lines
+    assert_eq!(o16.partition_function, vec![1.0, 1.0, 4.0]);
+
+    assert!(winvn.get(&nuclide("n")).is_none());
+}
+
+#[test]
+fn partition_function() {
+    let winvn = Winvn::from_reader(Cursor::new(include_str!("winvn/winvn"))).unwrap();
+    let o16 = nuclide("o16");
+
+    // interpolated in log space
+    let g = winvn.partition_function(&o16, 5.5).unwrap();
+    assert!((g - 2.0).abs() < 1e-12);
+    // clamped at the ends
+    assert_eq!(winvn.partition_function(&o16, 0.01), Some(1.0));
+    assert_eq!(winvn.partition_function(&o16, 20.0), Some(4.0));
+    assert!(winvn.partition_function(&o16, f64::NAN).unwrap().is_nan());
+    assert_eq!(winvn.partition_function(&nuclide("n"), 1.0), None);
+    assert_eq!(winvn.partition_function(&nuclide("n"), f64::NAN), None);
+}
+
+#[test]
+fn reverse_ratio() {
+    let winvn = Winvn::from_reader(Cursor::new(include_str!("winvn/winvn"))).unwrap();
+    let reaction = Reaction::new(
+        [nuclide("o16")].into_iter().collect(),
+        [nuclide("he4"), nuclide("c12")].into_iter().collect(),
+    );
+
+    assert_eq!(winvn.spin_factor(&reaction), Some(1.0));
+    assert_eq!(winvn.reverse_ratio(&reaction, 1.0), Some(1.0));
+    assert_eq!(winvn.reverse_ratio(&reaction, 10.0), Some(0.5));
+    assert_eq!(winvn.reverse_ratio(&reaction.reversed(), 10.0), Some(2.0));
+
+    let unknown = Reaction::new(
+        [nuclide("n")].into_iter().collect(),
+        [nuclide("p")].into_iter().collect(),
+    );
+    assert_eq!(winvn.reverse_ratio(&unknown, 1.0), None);
+}
+
+#[test]
+fn truncated() {
+    let input = "1\n 0.1 1.0\nn\nn 1.000 0 1 0.5 8.07132 ame16\n 1.0\n";
+    assert!(matches!(
+        Winvn::from_reader(Cursor::new(input)),
+        Err(ReaclibError::Invalid(ParseError::TooFewLines))
+    ));
+}
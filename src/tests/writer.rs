@@ -0,0 +1,87 @@
+use crate::{Format, Iter, Writer};
+use std::io::Cursor;
+
+fn round_trip(input: &str, format: Format) -> String {
+    let mut writer = Writer::new(Vec::new(), format);
+    for set in Iter::with_raw_params(Cursor::new(input), format) {
+        writer.write_set(&set.unwrap()).unwrap();
+    }
+    String::from_utf8(writer.into_inner()).unwrap()
+}
+
+// the test files are written in the same way as the writer, so they should be reproduced exactly
+#[test]
+fn round_trip_exact() {
+    // empty chapters aren't written
+    let input = include_str!("v1/multi_chapter");
+    assert_ne!(round_trip(input, Format::Reaclib1), input);
+    let input = include_str!("v1/multi");
+    assert_eq!(round_trip(input, Format::Reaclib1), input);
+    let input = include_str!("library/network");
+    assert_eq!(round_trip(input, Format::Reaclib2), input);
+}
+
+// floats aren't always written the same way (here, with a 3-digit exponent), so keeping the raw
+// strings matters
+#[test]
+fn raw_params() {
+    let input = include_str!("writer/float_format");
+    assert_eq!(round_trip(input, Format::Reaclib2), input);
+
+    // without the raw strings, the values are the same but the text is not
+    let sets = Iter::new(Cursor::new(input), Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let mut output = Vec::new();
+    crate::write_sets(&mut output, &sets, Format::Reaclib2).unwrap();
+    assert_ne!(output, input.as_bytes());
+    let reread = Iter::new(Cursor::new(output), Format::Reaclib2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(sets, reread);
+
+    // if a parameter changes, its raw string isn't used
+    let mut set = Iter::with_raw_params(Cursor::new(input), Format::Reaclib2)
+        .next()
+        .unwrap()
+        .unwrap();
+    set.params[1] = 1.5;
+    let mut output = Vec::new();
+    crate::write_sets(&mut output, [&set], Format::Reaclib2).unwrap();
+    let reread = Iter::new(Cursor::new(output), Format::Reaclib2)
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(reread.params[1], 1.5);
+}
+
+// a three-digit exponent doesn't fit in a column at the usual precision
+#[test]
+fn float_width() {
+    use crate::writer::format_float;
+    assert_eq!(format_float(1.2345, 13, 6), " 1.234500e+00");
+    assert_eq!(format_float(-5.10735e300, 13, 6), "-5.10735e+300");
+    assert_eq!(format_float(-1e-310, 12, 5), "-1.0000e-310");
+    assert_eq!(format_float(f64::NAN, 13, 6), "          NaN");
+}
+
+#[test]
+fn assert_round_trip() {
+    for input in [
+        include_str!("v1/multi"),
+        include_str!("v1/multi_chapter"),
+        include_str!("v1/parse_float_error_1"),
+    ] {
+        crate::assert_round_trip(input.as_bytes(), Format::Reaclib1);
+    }
+    for input in [
+        include_str!("library/network"),
+        include_str!("writer/float_format"),
+        include_str!("v2/too_few_lines_1"),
+    ] {
+        crate::assert_round_trip(input.as_bytes(), Format::Reaclib2);
+    }
+
+    let large = include_str!("library/network").replace("-1.369200e+01", "-5.107350e300");
+    crate::assert_round_trip(large.as_bytes(), Format::Reaclib2);
+}
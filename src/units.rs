@@ -0,0 +1,116 @@
+use crate::{ReactionRate, Set};
+use std::marker::PhantomData;
+use uom::{
+    si::{
+        f64::{Frequency, ThermodynamicTemperature},
+        thermodynamic_temperature::gigakelvin,
+        Quantity, ISQ, SI,
+    },
+    typenum::{N1, N2, N3, P3, P6, P9, Z0},
+};
+
+/// The rate of a reaction with two reactants, `N_A ⟨σv⟩`, with dimensions of volume / (amount of
+/// substance · time), usually given in cm³ mol⁻¹ s⁻¹.
+pub type TwoBodyRate = Quantity<ISQ<P3, Z0, N1, Z0, Z0, N1, Z0>, SI<f64>, f64>;
+
+/// The rate of a reaction with three reactants, `N_A² ⟨σvv⟩`, with dimensions of volume² /
+/// (amount of substance² · time), usually given in cm⁶ mol⁻² s⁻¹.
+pub type ThreeBodyRate = Quantity<ISQ<P6, Z0, N1, Z0, Z0, N2, Z0>, SI<f64>, f64>;
+
+/// The rate of a reaction with four reactants, with dimensions of volume³ / (amount of
+/// substance³ · time), usually given in cm⁹ mol⁻³ s⁻¹.
+pub type FourBodyRate = Quantity<ISQ<P9, Z0, N1, Z0, Z0, N3, Z0>, SI<f64>, f64>;
+
+/// A rate with the dimensions that it has for its number of reactants, from
+/// [`Set::typed_rate`] or [`ReactionRate::typed_rate`].
+///
+/// Reaclib rates are in s⁻¹ for one reactant, and in cm³ mol⁻¹ s⁻¹ (with another factor of
+/// cm³ mol⁻¹ for each additional reactant) otherwise.
+/// Keeping the dimensions in the type means that e.g. a decay rate can't be used where a capture
+/// rate is expected without the compiler noticing.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Iter, TypedRate};
+/// use std::io::Cursor;
+/// use uom::si::{
+///     f64::ThermodynamicTemperature, frequency::hertz, thermodynamic_temperature::gigakelvin,
+/// };
+///
+/// let reader = Cursor::new(r"1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00");
+/// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+///
+/// let temperature = ThermodynamicTemperature::new::<gigakelvin>(1.0);
+/// let Some(TypedRate::Decay(rate)) = set.typed_rate(temperature) else {
+///     panic!("n -> p is a decay");
+/// };
+/// assert_eq!(rate.get::<hertz>(), set.rate(1.0));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum TypedRate {
+    /// The rate of a reaction with one reactant, such as a decay or photodisintegration.
+    Decay(Frequency),
+    /// The rate of a reaction with two reactants.
+    TwoBody(TwoBodyRate),
+    /// The rate of a reaction with three reactants.
+    ThreeBody(ThreeBodyRate),
+    /// The rate of a reaction with four reactants.
+    FourBody(FourBodyRate),
+}
+
+impl TypedRate {
+    // `rate` is in the usual reaclib units, based on cm³ mol⁻¹
+    fn new(reactants: usize, rate: f64) -> Option<Self> {
+        // each cm³ is 1e-6 m³
+        match reactants {
+            1 => Some(Self::Decay(quantity(rate))),
+            2 => Some(Self::TwoBody(quantity(rate * 1e-6))),
+            3 => Some(Self::ThreeBody(quantity(rate * 1e-12))),
+            4 => Some(Self::FourBody(quantity(rate * 1e-18))),
+            _ => None,
+        }
+    }
+}
+
+// a quantity from its value in SI base units
+const fn quantity<D: uom::si::Dimension + ?Sized>(value: f64) -> Quantity<D, SI<f64>, f64> {
+    Quantity {
+        dimension: PhantomData,
+        units: PhantomData,
+        value,
+    }
+}
+
+impl Set {
+    /// Calculate the rate at `temperature`, with the dimensions that it has for the number of
+    /// reactants.
+    ///
+    /// See [`TypedRate`].
+    /// Returns `None` if the set has no reactants or more than 4, which no standard chapter has.
+    #[must_use]
+    pub fn typed_rate(&self, temperature: ThermodynamicTemperature) -> Option<TypedRate> {
+        TypedRate::new(
+            self.reactants.len(),
+            self.rate(temperature.get::<gigakelvin>()),
+        )
+    }
+}
+
+impl ReactionRate {
+    /// Calculate the total rate at `temperature`, with the dimensions that it has for the number
+    /// of reactants.
+    ///
+    /// See [`Set::typed_rate`].
+    #[must_use]
+    pub fn typed_rate(&self, temperature: ThermodynamicTemperature) -> Option<TypedRate> {
+        TypedRate::new(
+            self.reaction.reactants().len(),
+            self.rate(temperature.get::<gigakelvin>()),
+        )
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{error::ReaclibError as RError, ReactionRate, Set, SetError};
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The range of temperatures (in GK) over which a rate's fit is valid.
+///
+/// Reaclib fits are only meant to be used between 0.01 GK and 100 GK, and many of them behave
+/// badly outside of that range.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ValidityRange {
+    /// The lowest valid temperature.
+    pub min: f64,
+    /// The highest valid temperature.
+    pub max: f64,
+}
+
+impl ValidityRange {
+    /// The range that reaclib fits are valid over, from 0.01 GK to 100 GK.
+    pub const REACLIB: Self = Self::new(1e-2, 1e2);
+
+    /// Creates a new `ValidityRange` from `min` to `max` (inclusive).
+    #[must_use]
+    pub const fn new(min: f64, max: f64) -> Self {
+        Self { min, max }
+    }
+
+    /// Whether the range contains any temperatures, which it doesn't if `min` is greater than
+    /// `max` or either of them is NaN.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.min <= self.max
+    }
+
+    /// Whether `temperature` is inside the range.
+    #[must_use]
+    pub fn contains(&self, temperature: f64) -> bool {
+        (self.min..=self.max).contains(&temperature)
+    }
+
+    /// The temperature that a rate should be evaluated at, according to `policy`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `temperature` is outside of the range and `policy` is
+    /// [`Extrapolation::Error`], or if the range is empty because `min` is greater than `max` or
+    /// either of them is NaN.
+    pub fn apply(&self, temperature: f64, policy: Extrapolation) -> Result<f64, RError> {
+        if !self.is_valid() {
+            return Err(SetError::InvalidValidityRange.into());
+        }
+        if self.contains(temperature) {
+            return Ok(temperature);
+        }
+        match policy {
+            Extrapolation::Error => Err(SetError::TemperatureOutOfRange.into()),
+            Extrapolation::ClampToBoundary => Ok(temperature.clamp(self.min, self.max)),
+            Extrapolation::Extrapolate => Ok(temperature),
+        }
+    }
+}
+
+impl Default for ValidityRange {
+    fn default() -> Self {
+        Self::REACLIB
+    }
+}
+
+/// What to do when a rate is evaluated outside of its [`ValidityRange`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Extrapolation {
+    /// Return an error.
+    Error,
+    /// Evaluate the rate at the nearest end of the range.
+    ClampToBoundary,
+    /// Evaluate the fit anyway, which is what [`Set::rate`] does.
+    #[default]
+    Extrapolate,
+}
+
+impl Set {
+    /// The range of temperatures that this set is valid over.
+    ///
+    /// This is [`validity`][Self::validity] if it is set, and [`ValidityRange::REACLIB`]
+    /// otherwise.
+    #[must_use]
+    pub fn validity_range(&self) -> ValidityRange {
+        self.validity.unwrap_or_default()
+    }
+
+    /// Calculate the rate, handling temperatures outside of the
+    /// [`validity_range`][Self::validity_range] according to `policy`.
+    ///
+    /// ```
+    /// use reaclib::{Extrapolation, Format, Iter};
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(r"4
+    ///          p  c12  n13                       nacrn     1.94400e+00
+    ///  1.714820e+01 0.000000e+00-1.369200e+01-2.308810e-01
+    ///  4.443620e+00-3.158980e+00-6.666670e-01");
+    /// let set = Iter::new(reader, Format::Reaclib2).next().unwrap().unwrap();
+    ///
+    /// assert!(set.rate_with_policy(200.0, Extrapolation::Error).is_err());
+    /// assert_eq!(set.rate_with_policy(200.0, Extrapolation::ClampToBoundary), Ok(set.rate(100.0)));
+    /// assert_eq!(set.rate_with_policy(200.0, Extrapolation::Extrapolate), Ok(set.rate(200.0)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `temperature` is outside of the range and `policy` is
+    /// [`Extrapolation::Error`].
+    pub fn rate_with_policy(&self, temperature: f64, policy: Extrapolation) -> Result<f64, RError> {
+        Ok(self.rate(self.validity_range().apply(temperature, policy)?))
+    }
+}
+
+impl ReactionRate {
+    /// Calculate the total rate, handling temperatures outside of each set's validity range
+    /// according to `policy`.
+    ///
+    /// See [`Set::rate_with_policy`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `temperature` is outside of any set's range and `policy` is
+    /// [`Extrapolation::Error`].
+    pub fn rate_with_policy(&self, temperature: f64, policy: Extrapolation) -> Result<f64, RError> {
+        self.sets
+            .iter()
+            .map(|s| s.rate_with_policy(temperature, policy))
+            .sum()
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{error::ReaclibError as RError, parse_float, ParseError, RateEvaluator};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::BufRead;
+
+/// A weak rate that depends on temperature and density, given by a table like those of Fuller,
+/// Fowler & Newman (FFN) or Langanke & Martínez-Pinedo (LMP).
+///
+/// Weak rates in stellar matter depend on the electron density as well as the temperature, so
+/// they are distributed as tables on a grid of temperatures and densities, outside of reaclib.
+/// The rate is interpolated linearly in `log10(rate)` against `ln(T9)` and `log10(ρYe)`, and
+/// outside of the grid, the rate at the nearest edge is used.
+/// A temperature or density that is NaN or negative gives NaN.
+///
+/// Fixing the density with [`at_density`][Self::at_density] gives a [`RateEvaluator`], so that
+/// a weak rate can be used wherever a reaclib rate can.
+///
+/// # Format
+///
+/// [`from_reader`][Self::from_reader] reads the common layout of these tables, where each line
+/// has whitespace-separated columns:
+///
+/// ```text
+/// T9  log10(ρYe)  log10(rate) ...
+/// ```
+///
+/// with `T9` in GK, `ρYe` in g/cm³, and one or more columns of rates in s⁻¹, such as β⁺
+/// decay, electron capture, and the neutrino energy loss.
+/// Lines that don't start with a number, such as comments or a header of column names, are
+/// skipped.
+/// The lines can be in any order, but there must be a line for every combination of the
+/// temperatures and densities in the table.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{RateEvaluator, TabulatedWeakRate};
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new(r"
+/// ! t9   lrhoye   lbeta+   leps-
+///  1.0   7.0      -3.0     -2.0
+///  1.0   8.0      -3.0     -1.0
+///  2.0   7.0      -2.5     -1.5
+///  2.0   8.0      -2.5     -0.5
+/// ");
+/// // electron capture, the second column of rates
+/// let table = TabulatedWeakRate::from_reader(reader, 1).unwrap();
+/// assert!((table.rate(1.0, 1e7) - 1e-2).abs() < 1e-12);
+/// assert!((table.rate(1.0, 10f64.powf(7.5)) - 10f64.powf(-1.5)).abs() < 1e-12);
+///
+/// let evaluator = table.at_density(1e8);
+/// assert!((evaluator.rate(2.0) - 10f64.powf(-0.5)).abs() < 1e-12);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TabulatedWeakRate {
+    ln_temperatures: Vec<f64>,
+    log_densities: Vec<f64>,
+    // `log10(rate)`, with the densities varying fastest
+    log_rates: Vec<f64>,
+}
+
+impl TabulatedWeakRate {
+    /// Creates a new `TabulatedWeakRate` from `temperatures` (in GK), `log_densities`
+    /// (`log10(ρYe)`, with `ρYe` in g/cm³), and `log_rates` (`log10(rate)`, with the rate in
+    /// s⁻¹) at each temperature and density, with the densities varying fastest.
+    ///
+    /// Returns `None` if there isn't a rate for every temperature and density, there are no
+    /// points, or the temperatures aren't finite, positive, and strictly increasing, or the
+    /// densities aren't finite and strictly increasing.
+    #[must_use]
+    pub fn new(temperatures: &[f64], log_densities: &[f64], log_rates: &[f64]) -> Option<Self> {
+        if temperatures.is_empty()
+            || log_densities.is_empty()
+            || log_rates.len() != temperatures.len() * log_densities.len()
+            || temperatures[0] <= 0.0
+            || temperatures
+                .iter()
+                .chain(log_densities)
+                .any(|x| !x.is_finite())
+            || temperatures.windows(2).any(|w| w[0] >= w[1])
+            || log_densities.windows(2).any(|w| w[0] >= w[1])
+        {
+            return None;
+        }
+
+        Some(Self {
+            ln_temperatures: temperatures.iter().map(|t| t.ln()).collect(),
+            log_densities: log_densities.to_vec(),
+            log_rates: log_rates.to_vec(),
+        })
+    }
+
+    /// Reads a `TabulatedWeakRate` from `reader`, with the rates from `column`, counting from 0
+    /// after the temperature and density.
+    ///
+    /// See the [format][Self#format].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error, a number can't be parsed, a line doesn't have
+    /// `column`, or the lines don't make up a complete grid.
+    pub fn from_reader<R: BufRead>(reader: R, column: usize) -> Result<Self, RError> {
+        let mut rows = Vec::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let mut fields = line.split_whitespace();
+            let Some(first) = fields.next() else {
+                continue;
+            };
+            if parse_float(first).is_err() {
+                continue;
+            }
+            let mut fields = std::iter::once(first).chain(fields);
+            let mut next = |n| {
+                fields.nth(n).map_or_else(
+                    || {
+                        let message = format!("line {} has too few columns", i + 1);
+                        Err(ParseError::InvalidTable(message).into())
+                    },
+                    |field| parse_float(field).map_err(RError::from),
+                )
+            };
+            rows.push((next(0)?, next(0)?, next(column)?));
+        }
+
+        let axis = |value: fn(&(f64, f64, f64)) -> f64| {
+            let mut values = rows.iter().map(value).collect::<Vec<_>>();
+            values.sort_by(f64::total_cmp);
+            values.dedup();
+            values
+        };
+        let temperatures = axis(|r| r.0);
+        let log_densities = axis(|r| r.1);
+
+        let mut log_rates = vec![None; temperatures.len() * log_densities.len()];
+        for &(t, d, rate) in &rows {
+            let position = |axis: &[f64], x: f64| axis.partition_point(|&a| a < x);
+            let i = position(&temperatures, t) * log_densities.len() + position(&log_densities, d);
+            if log_rates[i].replace(rate).is_some() {
+                return Err(RError::from(ParseError::InvalidTable(format!(
+                    "more than one rate at T9 = {t}, log10(ρYe) = {d}"
+                ))));
+            }
+        }
+        let log_rates = log_rates
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                ParseError::InvalidTable("the table is missing some points".to_string())
+            })?;
+
+        Self::new(&temperatures, &log_densities, &log_rates).ok_or_else(|| {
+            ParseError::InvalidTable("the table has no valid points".to_string()).into()
+        })
+    }
+
+    /// The temperatures (in GK) at which the rate is tabulated.
+    pub fn temperatures(&self) -> impl Iterator<Item = f64> + '_ {
+        self.ln_temperatures.iter().map(|t| t.exp())
+    }
+
+    /// The densities (`log10(ρYe)`) at which the rate is tabulated.
+    #[must_use]
+    pub fn log_densities(&self) -> &[f64] {
+        &self.log_densities
+    }
+
+    /// Interpolate the rate at `temperature` (in GK) and `density` (`ρYe`, in g/cm³).
+    #[must_use]
+    pub fn rate(&self, temperature: f64, density: f64) -> f64 {
+        10f64.powf(self.log_rate(temperature, density.log10()))
+    }
+
+    /// Interpolate the natural logarithm of the rate at `temperature` (in GK) and `density`
+    /// (`ρYe`, in g/cm³).
+    #[must_use]
+    pub fn ln_rate(&self, temperature: f64, density: f64) -> f64 {
+        self.log_rate(temperature, density.log10()) * std::f64::consts::LN_10
+    }
+
+    /// Fix the density at `density` (`ρYe`, in g/cm³), giving a rate that only depends on
+    /// temperature.
+    #[must_use]
+    pub fn at_density(&self, density: f64) -> impl RateEvaluator + '_ {
+        AtDensity {
+            table: self,
+            log_density: density.log10(),
+        }
+    }
+
+    // `log10(rate)`, bilinearly interpolated
+    #[allow(clippy::many_single_char_names)]
+    fn log_rate(&self, temperature: f64, log_density: f64) -> f64 {
+        let (i, a) = interval(&self.ln_temperatures, temperature.ln());
+        let (j, b) = interval(&self.log_densities, log_density);
+        let n = self.log_densities.len();
+        let value = |(i, j): (usize, usize)| self.log_rates[i * n + j];
+        let along_density = |i| (1.0 - b) * value((i, j.0)) + b * value((i, j.1));
+        (1.0 - a) * along_density(i.0) + a * along_density(i.1)
+    }
+}
+
+// the indices of the points on `axis` on either side of `x`, and how far `x` is from the first
+// towards the second (from 0 to 1), clamped to the ends. a NaN `x` is NaN of the way along
+fn interval(axis: &[f64], x: f64) -> ((usize, usize), f64) {
+    if x.is_nan() {
+        return ((0, 0), f64::NAN);
+    }
+    let last = axis.len() - 1;
+    if x <= axis[0] {
+        return ((0, 0), 0.0);
+    }
+    if x >= axis[last] {
+        return ((last, last), 0.0);
+    }
+    let i = axis.partition_point(|&a| a <= x) - 1;
+    ((i, i + 1), (x - axis[i]) / (axis[i + 1] - axis[i]))
+}
+
+// a `TabulatedWeakRate` at a fixed density, from `TabulatedWeakRate::at_density`
+struct AtDensity<'a> {
+    table: &'a TabulatedWeakRate,
+    log_density: f64,
+}
+
+impl RateEvaluator for AtDensity<'_> {
+    fn ln_rate(&self, temperature: f64) -> f64 {
+        self.table.log_rate(temperature, self.log_density) * std::f64::consts::LN_10
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{error::ReaclibError as RError, Nuclide, ParseError, Reaction};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, io::BufRead};
+
+/// Ground state data and partition function of a nuclide, from a [`Winvn`] file.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct NuclideInfo {
+    /// The number of protons.
+    pub z: u8,
+    /// The number of neutrons.
+    pub n: u16,
+    /// The spin of the ground state.
+    pub spin: f64,
+    /// The mass excess, in MeV.
+    pub mass_excess: f64,
+    /// The normalized partition function at each of the [`Winvn::temperatures`].
+    pub partition_function: Vec<f64>,
+}
+
+impl NuclideInfo {
+    /// The number of nucleons.
+    #[must_use]
+    pub fn a(&self) -> u16 {
+        u16::from(self.z) + self.n
+    }
+
+    /// The statistical weight of the ground state, `2J + 1`.
+    #[must_use]
+    pub fn statistical_weight(&self) -> f64 {
+        2.0f64.mul_add(self.spin, 1.0)
+    }
+}
+
+/// Nuclear data from a winvn file, which accompanies reaclib.
+///
+/// The file starts with the number of nuclides, followed by a line with the temperatures (in GK)
+/// at which the partition functions are given, and a list of the nuclide names.
+/// After that, each nuclide has a line with its name, mass number, Z, N, spin, mass excess,
+/// and the source of the data, followed by lines with the partition function at each temperature.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::Winvn;
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new("2
+///  0.1 1.0 10.0
+/// n
+/// p
+/// n        1.000   0   1   0.5      8.07132  ame16
+///  1.000E+00 1.000E+00 1.000E+00
+/// p        1.000   1   0   0.5      7.28897  ame16
+///  1.000E+00 1.000E+00 1.000E+00
+/// ");
+/// let winvn = Winvn::from_reader(reader).unwrap();
+///
+/// let n = "n".parse().unwrap();
+/// assert_eq!(winvn.get(&n).unwrap().statistical_weight(), 2.0);
+/// assert_eq!(winvn.partition_function(&n, 2.0), Some(1.0));
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Winvn {
+    temperatures: Vec<f64>,
+    nuclides: HashMap<Nuclide, NuclideInfo>,
+}
+
+impl Winvn {
+    /// Reads a `Winvn` from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error or a parsing error, or if the file ends in the
+    /// middle of a nuclide.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, RError> {
+        let mut lines = reader.lines();
+        let mut next_line = || {
+            lines
+                .next()
+                .ok_or(ParseError::TooFewLines)?
+                .map_err(RError::from)
+        };
+
+        let count = next_line()?.trim().parse::<usize>()?;
+        let temperatures = next_line()?
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<f64>, _>>()?;
+        // the list of names is repeated in the entries, so it can be skipped
+        for _ in 0..count {
+            next_line()?;
+        }
+
+        let mut nuclides = HashMap::with_capacity(count);
+        for _ in 0..count {
+            let line = next_line()?;
+            let mut fields = line.split_whitespace();
+            let mut next_field = || fields.next().ok_or(ParseError::TooShortLine);
+            let name = next_field()?.parse::<Nuclide>()?;
+            // the mass number is the sum of z and n
+            next_field()?;
+            let z = next_field()?.parse()?;
+            let n = next_field()?.parse()?;
+            let spin = next_field()?.parse()?;
+            let mass_excess = next_field()?.parse()?;
+
+            let mut partition_function = Vec::with_capacity(temperatures.len());
+            while partition_function.len() < temperatures.len() {
+                for value in next_line()?.split_whitespace() {
+                    partition_function.push(value.parse()?);
+                }
+            }
+
+            nuclides.insert(
+                name,
+                NuclideInfo {
+                    z,
+                    n,
+                    spin,
+                    mass_excess,
+                    partition_function,
+                },
+            );
+        }
+
+        Ok(Self {
+            temperatures,
+            nuclides,
+        })
+    }
+
+    /// The temperatures (in GK) at which the partition functions are given.
+    #[must_use]
+    pub fn temperatures(&self) -> &[f64] {
+        &self.temperatures
+    }
+
+    /// The data for `nuclide`, if it is in the file.
+    #[must_use]
+    pub fn get(&self, nuclide: &Nuclide) -> Option<&NuclideInfo> {
+        self.nuclides.get(nuclide)
+    }
+
+    /// The normalized partition function of `nuclide` at `temperature` (in GK).
+    ///
+    /// The logarithm of the partition function is interpolated linearly in temperature.
+    /// Outside of the tabulated temperatures, the value at the nearest end is used, and a NaN
+    /// temperature gives NaN.
+    /// Returns `None` if the nuclide isn't in the file.
+    #[must_use]
+    pub fn partition_function(&self, nuclide: &Nuclide, temperature: f64) -> Option<f64> {
+        let values = &self.get(nuclide)?.partition_function;
+        if temperature.is_nan() {
+            return Some(f64::NAN);
+        }
+        let t = &self.temperatures;
+        let n = t.len().min(values.len());
+        if n == 0 {
+            return Some(1.0);
+        }
+
+        if n == 1 || temperature <= t[0] {
+            return Some(values[0]);
+        }
+        if temperature >= t[n - 1] {
+            return Some(values[n - 1]);
+        }
+        let i = t[..n].partition_point(|&ti| ti <= temperature) - 1;
+        let frac = (temperature - t[i]) / (t[i + 1] - t[i]);
+        let ln_value = frac.mul_add(values[i + 1].ln() - values[i].ln(), values[i].ln());
+        Some(ln_value.exp())
+    }
+
+    /// The ratio of the statistical weights `(2J + 1)` of the products of `reaction` to those of
+    /// its reactants.
+    ///
+    /// Returns `None` if any of the nuclides aren't in the file.
+    #[must_use]
+    pub fn spin_factor(&self, reaction: &Reaction) -> Option<f64> {
+        let weight = |nuclides: &[Nuclide]| {
+            nuclides
+                .iter()
+                .map(|n| Some(self.get(n)?.statistical_weight()))
+                .product::<Option<f64>>()
+        };
+        Some(weight(reaction.products())? / weight(reaction.reactants())?)
+    }
+
+    /// The ratio of the partition functions of the products of `reaction` to those of its
+    /// reactants, at `temperature` (in GK).
+    ///
+    /// The rates of reverse sets in reaclib don't include the partition functions, so they should
+    /// be multiplied by this factor.
+    /// Returns `None` if any of the nuclides aren't in the file.
+    #[must_use]
+    pub fn partition_function_ratio(&self, reaction: &Reaction, temperature: f64) -> Option<f64> {
+        let product = |nuclides: &[Nuclide]| {
+            nuclides
+                .iter()
+                .map(|n| self.partition_function(n, temperature))
+                .product::<Option<f64>>()
+        };
+        Some(product(reaction.products())? / product(reaction.reactants())?)
+    }
+
+    /// The ratio of the full statistical weights of the products of `reaction` to those of its
+    /// reactants, at `temperature` (in GK).
+    ///
+    /// This is the product of [`spin_factor`][Self::spin_factor] and
+    /// [`partition_function_ratio`][Self::partition_function_ratio], which is the factor from
+    /// detailed balance that relates a reverse rate to its forward rate, along with the mass and
+    /// Q-value terms.
+    /// Returns `None` if any of the nuclides aren't in the file.
+    #[must_use]
+    pub fn reverse_ratio(&self, reaction: &Reaction, temperature: f64) -> Option<f64> {
+        Some(self.spin_factor(reaction)? * self.partition_function_ratio(reaction, temperature)?)
+    }
+}
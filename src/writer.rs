@@ -0,0 +1,171 @@
+use crate::{error::ReaclibError as RError, Chapter, Format, ParseError, Set, SetError};
+use std::{fmt::Write as _, io::Write};
+
+/// A writer that outputs sets in the reaclib format.
+///
+/// For [`Format::Reaclib1`], a chapter header is written whenever the chapter changes, so sets
+/// should be grouped by chapter to avoid repeated headers.
+///
+/// # Examples
+///
+/// ```
+/// use reaclib::{Format, Iter, Writer};
+/// use std::io::Cursor;
+///
+/// let input = "1
+///          n    p                            wc12w     7.82300e-01
+/// -6.781610e+00 0.000000e+00 0.000000e+00 0.000000e+00
+///  0.000000e+00 0.000000e+00 0.000000e+00";
+/// let set = Iter::new(Cursor::new(input), Format::Reaclib2).next().unwrap().unwrap();
+///
+/// let mut writer = Writer::new(Vec::new(), Format::Reaclib2);
+/// writer.write_set(&set).unwrap();
+/// let output = writer.into_inner();
+///
+/// let reread = Iter::new(Cursor::new(output), Format::Reaclib2).next().unwrap().unwrap();
+/// assert_eq!(set, reread);
+/// ```
+pub struct Writer<W: Write> {
+    inner: W,
+    format: Format,
+    chapter: Option<Chapter>,
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a new `Writer` which writes to `writer`, according to the rules of `format`.
+    pub fn new(writer: W, format: Format) -> Self {
+        Self {
+            inner: writer,
+            format,
+            chapter: None,
+        }
+    }
+
+    /// Write a single set, preceded by a chapter header if needed.
+    ///
+    /// If the set has [`raw_params`][Set::raw_params], they are written in place of any
+    /// parameter whose value they still match.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error, if there is no chapter with the set's number
+    /// of reactants and products, or if a nuclide's name is too long for the 5 columns that it
+    /// is given.
+    pub fn write_set(&mut self, set: &Set) -> Result<(), RError> {
+        let chapter = Chapter::from_counts(set.reactants.len(), set.products.len()).ok_or(
+            SetError::NoMatchingChapter(set.reactants.len(), set.products.len()),
+        )?;
+        if let Some(n) = set
+            .reactants
+            .iter()
+            .chain(&set.products)
+            .find(|n| n.len() > 5)
+        {
+            return Err(ParseError::NuclideTooLong(n.to_string()).into());
+        }
+
+        match self.format {
+            Format::Reaclib1 => {
+                if self.chapter != Some(chapter) {
+                    writeln!(self.inner, "{:<74}", chapter.number())?;
+                    writeln!(self.inner, "{:74}", "")?;
+                    writeln!(self.inner, "{:74}", "")?;
+                    self.chapter = Some(chapter);
+                }
+            }
+            Format::Reaclib2 => writeln!(self.inner, "{}", chapter.number())?,
+        }
+
+        let mut nuclides = String::new();
+        for n in set.reactants.iter().chain(&set.products) {
+            write!(nuclides, "{n:>5}").expect("writing to a String can't fail");
+        }
+        let resonance = set.resonance.as_char();
+        let reverse = if set.reverse { 'v' } else { ' ' };
+        writeln!(
+            self.inner,
+            "     {nuclides:<38}{:>4}{resonance}{reverse}   {}{:10}",
+            set.label,
+            format_float(set.q_value, 12, 5),
+            "",
+        )?;
+
+        let params = (0..7)
+            .map(|i| match &set.raw_params {
+                Some(raw)
+                    if raw[i].trim().parse::<f64>().map(f64::to_bits)
+                        == Ok(set.params[i].to_bits()) =>
+                {
+                    raw[i].to_string()
+                }
+                _ => format_float(set.params[i], 13, 6),
+            })
+            .collect::<Vec<_>>();
+        writeln!(self.inner, "{}{:22}", params[..4].concat(), "")?;
+        writeln!(self.inner, "{}{:35}", params[4..].concat(), "")?;
+
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is an io error.
+    pub fn flush(&mut self) -> Result<(), RError> {
+        Ok(self.inner.flush()?)
+    }
+
+    /// Consumes the `Writer`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Write all of `sets` to `writer`, according to the rules of `format`.
+///
+/// See [`Writer`] for details.
+///
+/// # Errors
+///
+/// Will return `Err` if there is an io error, or if a set has no matching chapter.
+pub fn write_sets<'a, W: Write>(
+    writer: W,
+    sets: impl IntoIterator<Item = &'a Set>,
+    format: Format,
+) -> Result<(), RError> {
+    let mut writer = Writer::new(writer, format);
+    for set in sets {
+        writer.write_set(set)?;
+    }
+    writer.flush()
+}
+
+// format a float like fortran's `Ew.d`, e.g. ` 1.234560e+00`
+// rust doesn't pad the exponent or include its sign, so we do that ourselves.
+// a three-digit exponent doesn't fit, so the precision is reduced until it does, which keeps the
+// columns aligned (fortran would drop the `e` instead, which rust can't parse)
+pub(crate) fn format_float(x: f64, width: usize, precision: usize) -> String {
+    let format = |precision: usize| {
+        let s = format!("{x:.precision$e}");
+        match s.split_once('e') {
+            Some((mantissa, exponent)) => {
+                let (sign, digits) = match exponent.strip_prefix('-') {
+                    Some(digits) => ('-', digits),
+                    None => ('+', exponent),
+                };
+                format!("{mantissa}e{sign}{digits:0>2}")
+            }
+            // inf and nan don't have an exponent
+            None => s,
+        }
+    };
+    let mut s = format(precision);
+    for precision in (0..precision).rev() {
+        if s.len() <= width {
+            break;
+        }
+        s = format(precision);
+    }
+    format!("{s:>width$}")
+}